@@ -31,13 +31,38 @@ mod types;
 /// This macro is to define a model.
 #[proc_macro_attribute]
 pub fn model(
-    _attr: proc_macro::TokenStream,
+    attr: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
+    let strict = if attr.is_empty() {
+        false
+    } else {
+        let term = parse_macro_input!(attr as syn::Ident);
+        if term != "strict" {
+            return syn::Error::new(term.span(), "Only support `strict`.")
+                .to_compile_error()
+                .into();
+        }
+        true
+    };
     let input = parse_macro_input!(input as DeriveInput);
     let tokens = match input.data {
-        Data::Struct(data) => types::handle_struct(input.ident, input.vis, input.attrs, data),
-        Data::Enum(data) => types::handle_enum(input.ident, input.vis, input.attrs, data),
+        Data::Struct(data) => types::handle_struct(
+            input.ident,
+            input.vis,
+            input.attrs,
+            input.generics,
+            data,
+            strict,
+        ),
+        Data::Enum(data) => types::handle_enum(
+            input.ident,
+            input.vis,
+            input.attrs,
+            input.generics,
+            data,
+            strict,
+        ),
         _ => Err(syn::Error::new(
             input.span(),
             "Only support struct or enum.",
@@ -27,7 +27,12 @@ impl ToValidateToken for MinLengthTerm {
                 if value.len() >= #val {
                     Ok(value)
                 } else {
-                    Err(dade::Error::validate_err(#msg))
+                    Err(dade::Error::constraint_err(
+                        "min_length",
+                        #msg,
+                        Some(dade::ToJsonValue::to_json_value(&value)),
+                    )
+                    .with_limit(dade::JsonValue::Number(dade::Number::from(#val))))
                 }
             }
         }
@@ -60,7 +65,12 @@ impl ToValidateToken for MaxLengthTerm {
                 if value.len() <= #val {
                     Ok(value)
                 } else {
-                    Err(dade::Error::validate_err(#msg))
+                    Err(dade::Error::constraint_err(
+                        "max_length",
+                        #msg,
+                        Some(dade::ToJsonValue::to_json_value(&value)),
+                    )
+                    .with_limit(dade::JsonValue::Number(dade::Number::from(#val))))
                 }
             }
         }
@@ -93,7 +103,12 @@ impl ToValidateToken for MinItemsTerm {
                 if value.len() >= #val {
                     Ok(value)
                 } else {
-                    Err(dade::Error::validate_err(#msg))
+                    Err(dade::Error::constraint_err(
+                        "min_items",
+                        #msg,
+                        Some(dade::ToJsonValue::to_json_value(&value)),
+                    )
+                    .with_limit(dade::JsonValue::Number(dade::Number::from(#val))))
                 }
             }
         }
@@ -126,7 +141,12 @@ impl ToValidateToken for MaxItemsTerm {
                 if value.len() <= #val {
                     Ok(value)
                 } else {
-                    Err(dade::Error::validate_err(#msg))
+                    Err(dade::Error::constraint_err(
+                        "max_items",
+                        #msg,
+                        Some(dade::ToJsonValue::to_json_value(&value)),
+                    )
+                    .with_limit(dade::JsonValue::Number(dade::Number::from(#val))))
                 }
             }
         }
@@ -147,13 +167,38 @@ pub(crate) enum LitNumber {
     Float(LitFloat),
 }
 
+impl LitNumber {
+    /// Makes from a numeric literal, which must be finite to be a number in JSON.
+    pub(crate) fn new(lit: &Lit) -> Option<syn::Result<Self>> {
+        match lit {
+            Lit::Int(val) => Some(Ok(LitNumber::Int(val.clone()))),
+            Lit::Float(val) => Some(match val.base10_parse::<f64>() {
+                Ok(num) if num.is_finite() => Ok(LitNumber::Float(val.clone())),
+                Ok(_) => Err(syn::Error::new(val.span(), "Number must be finite.")),
+                Err(err) => Err(err),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Makes an expression of `dade::Number` for the literal.
+    pub(crate) fn to_number_token(&self) -> TokenStream {
+        match self {
+            LitNumber::Int(val) => quote! { dade::Number::from(#val) },
+            LitNumber::Float(val) if val.suffix() == "f32" => {
+                quote! { dade::Number::from_f32(#val).unwrap() }
+            }
+            LitNumber::Float(val) => quote! { dade::Number::from_f64(#val).unwrap() },
+        }
+    }
+}
+
 impl Parse for LitNumber {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let lit: Lit = input.parse()?;
-        match lit {
-            Lit::Int(val) => Ok(LitNumber::Int(val)),
-            Lit::Float(val) => Ok(LitNumber::Float(val)),
-            _ => Err(input.error("Un support Type")),
+        match LitNumber::new(&lit) {
+            Some(ret) => ret,
+            None => Err(input.error("Un support Type")),
         }
     }
 }
@@ -183,13 +228,19 @@ pub(crate) struct GtTerm {
 impl ToValidateToken for GtTerm {
     fn to_validate_token(&self, variable: &Ident) -> TokenStream {
         let val = &self.value;
+        let number = val.to_number_token();
         let msg = format!("{} must be greater than {}", variable, val.to_string());
         quote! {
             | value | {
                 if value > #val {
                     Ok(value)
                 } else {
-                    Err(dade::Error::validate_err(#msg))
+                    Err(dade::Error::constraint_err(
+                        "gt",
+                        #msg,
+                        Some(dade::ToJsonValue::to_json_value(&value)),
+                    )
+                    .with_limit(dade::JsonValue::Number(#number)))
                 }
             }
         }
@@ -199,9 +250,10 @@ impl ToValidateToken for GtTerm {
 impl ToSchema for GtTerm {
     fn to_schema(&self) -> TokenStream {
         let val = &self.value;
+        let number = val.to_number_token();
         quote! {
             "exclusiveMinimum".to_string(),
-            dade::JsonValue::Number(dade::Number::from(#val))
+            dade::JsonValue::Number(#number)
         }
     }
 }
@@ -213,6 +265,7 @@ pub(crate) struct GeTerm {
 impl ToValidateToken for GeTerm {
     fn to_validate_token(&self, variable: &Ident) -> TokenStream {
         let val = &self.value;
+        let number = val.to_number_token();
         let msg = format!(
             "{} must be equal to or greater than {}",
             variable,
@@ -223,7 +276,12 @@ impl ToValidateToken for GeTerm {
                 if value >= #val {
                     Ok(value)
                 } else {
-                    Err(dade::Error::validate_err(#msg))
+                    Err(dade::Error::constraint_err(
+                        "ge",
+                        #msg,
+                        Some(dade::ToJsonValue::to_json_value(&value)),
+                    )
+                    .with_limit(dade::JsonValue::Number(#number)))
                 }
             }
         }
@@ -233,9 +291,10 @@ impl ToValidateToken for GeTerm {
 impl ToSchema for GeTerm {
     fn to_schema(&self) -> TokenStream {
         let val = &self.value;
+        let number = val.to_number_token();
         quote! {
             "minimum".to_string(),
-            dade::JsonValue::Number(dade::Number::from(#val))
+            dade::JsonValue::Number(#number)
         }
     }
 }
@@ -247,13 +306,19 @@ pub(crate) struct LtTerm {
 impl ToValidateToken for LtTerm {
     fn to_validate_token(&self, variable: &Ident) -> TokenStream {
         let val = &self.value;
+        let number = val.to_number_token();
         let msg = format!("{} must be less than {}", variable, val.to_string());
         quote! {
             | value | {
                 if value < #val {
                     Ok(value)
                 } else {
-                    Err(dade::Error::validate_err(#msg))
+                    Err(dade::Error::constraint_err(
+                        "lt",
+                        #msg,
+                        Some(dade::ToJsonValue::to_json_value(&value)),
+                    )
+                    .with_limit(dade::JsonValue::Number(#number)))
                 }
             }
         }
@@ -263,9 +328,10 @@ impl ToValidateToken for LtTerm {
 impl ToSchema for LtTerm {
     fn to_schema(&self) -> TokenStream {
         let val = &self.value;
+        let number = val.to_number_token();
         quote! {
             "exclusiveMaximum".to_string(),
-            dade::JsonValue::Number(dade::Number::from(#val))
+            dade::JsonValue::Number(#number)
         }
     }
 }
@@ -277,6 +343,7 @@ pub(crate) struct LeTerm {
 impl ToValidateToken for LeTerm {
     fn to_validate_token(&self, variable: &Ident) -> TokenStream {
         let val = &self.value;
+        let number = val.to_number_token();
         let msg = format!(
             "{} must be equal to or less than {}",
             variable,
@@ -287,7 +354,12 @@ impl ToValidateToken for LeTerm {
                 if value <= #val {
                     Ok(value)
                 } else {
-                    Err(dade::Error::validate_err(#msg))
+                    Err(dade::Error::constraint_err(
+                        "le",
+                        #msg,
+                        Some(dade::ToJsonValue::to_json_value(&value)),
+                    )
+                    .with_limit(dade::JsonValue::Number(#number)))
                 }
             }
         }
@@ -297,9 +369,10 @@ impl ToValidateToken for LeTerm {
 impl ToSchema for LeTerm {
     fn to_schema(&self) -> TokenStream {
         let val = &self.value;
+        let number = val.to_number_token();
         quote! {
             "maximum".to_string(),
-            dade::JsonValue::Number(dade::Number::from(#val))
+            dade::JsonValue::Number(#number)
         }
     }
 }
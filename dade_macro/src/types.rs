@@ -2,12 +2,12 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens};
 use syn::spanned::Spanned;
 use syn::{
-    Attribute, DataEnum, DataStruct, Fields, GenericArgument, Ident, Lit, PathArguments, Type,
-    Visibility,
+    parse_quote, Attribute, DataEnum, DataStruct, Fields, GenericArgument, GenericParam, Generics,
+    Ident, Lit, PathArguments, Type, Visibility,
 };
 
 use crate::fields::ModelField;
-use crate::terms::{Condition, DefaultTerm, ToSchema, ToValidateToken};
+use crate::terms::{Condition, DefaultTerm, LitNumber, ToSchema, ToValidateToken};
 
 enum ModelType {
     Null,
@@ -35,6 +35,8 @@ impl ModelType {
                     Ok(ModelType::String)
                 } else if type_token == "bool" {
                     Ok(ModelType::Bool)
+                } else if type_path.path.segments.last().unwrap().ident == "Cow" {
+                    Ok(ModelType::String)
                 } else {
                     let segment = type_path.path.segments.iter().next().unwrap();
                     let ident = &segment.ident;
@@ -66,6 +68,15 @@ impl ModelType {
                     }
                 }
             }
+            Type::Reference(type_reference) => {
+                if type_reference.mutability.is_none()
+                    && type_reference.elem.to_token_stream().to_string() == "str"
+                {
+                    Ok(ModelType::String)
+                } else {
+                    Err(syn::Error::new(ty.span(), "Invalid type"))
+                }
+            }
             Type::Tuple(type_tuple) => {
                 if type_tuple.to_token_stream().to_string() == "()" {
                     Ok(ModelType::Null)
@@ -109,7 +120,7 @@ fn handle_null_type(
         }
         None => {
             let msg = format!("not found key, {}", variable_key);
-            quote! { Err(dade::Error::validate_err(#msg)) }
+            quote! { Err(dade::Error::constraint_err("missing", #msg, None)) }
         }
         Some(DefaultTerm::Lit(_)) => {
             return Err(syn::Error::new(
@@ -119,10 +130,10 @@ fn handle_null_type(
         }
     };
     statements.push(quote! {
-        let #variable: #variable_type = match dict.get(#variable_key) {
-            Some(val) => dade::FromJsonValue::from_json_value(val),
-            None => #default_val,
-        }?;
+        let #variable: #variable_type = match #variable {
+            Some(val) => val?,
+            None => #default_val?,
+        };
     });
     Ok(())
 }
@@ -179,20 +190,21 @@ fn handle_number_type(
     let default_val = match &model_field.default {
         Some(DefaultTerm::Lit(term)) => {
             let val = &term.value;
-            if !matches!(val, Lit::Int(_)) && !matches!(val, Lit::Float(_)) {
-                return Err(syn::Error::new(
-                    variable.span(),
-                    "Support default condition is only numeric",
-                ));
-            }
-            conds.push(
-                quote! { "default".to_string(), dade::JsonValue::Number(dade::Number::from(#val)) },
-            );
+            let number = match LitNumber::new(val) {
+                Some(number) => number?.to_number_token(),
+                None => {
+                    return Err(syn::Error::new(
+                        variable.span(),
+                        "Support default condition is only numeric",
+                    ))
+                }
+            };
+            conds.push(quote! { "default".to_string(), dade::JsonValue::Number(#number) });
             quote! { Ok(#val) }
         }
         None => {
             let msg = format!("not found key, {}", variable_key);
-            quote! { Err(dade::Error::validate_err(#msg)) }
+            quote! { Err(dade::Error::constraint_err("missing", #msg, None)) }
         }
         Some(DefaultTerm::Ident(_)) => {
             return Err(syn::Error::new(
@@ -202,10 +214,11 @@ fn handle_number_type(
         }
     };
     statements.push(quote! {
-        let #variable: #variable_type = (match dict.get(#variable_key) {
-            Some(val) => dade::FromJsonValue::from_json_value(val),
-            None => #default_val,
-        }) #(.and_then(#stmt))*?;
+        let #variable: #variable_type = match #variable {
+            Some(val) => val?,
+            None => #default_val?,
+        };
+        let #variable = Ok(#variable) #(.and_then(#stmt))*?;
     });
     Ok(())
 }
@@ -261,11 +274,11 @@ fn handle_string_type(
                 ));
             }
             conds.push(quote! { "default".to_string(), dade::JsonValue::String(#val.to_string()) });
-            quote! { Ok(#val.to_string()) }
+            quote! { Ok(#val.into()) }
         }
         None => {
             let msg = format!("not found key, {}", variable_key);
-            quote! { Err(dade::Error::validate_err(#msg)) }
+            quote! { Err(dade::Error::constraint_err("missing", #msg, None)) }
         }
         Some(DefaultTerm::Ident(_)) => {
             return Err(syn::Error::new(
@@ -275,8 +288,8 @@ fn handle_string_type(
         }
     };
     statements.push(quote! {
-        let #variable: #variable_type = (match dict.get(#variable_key) {
-            Some(val) => dade::FromJsonValue::from_json_value(val),
+        let #variable: #variable_type = (match #variable {
+            Some(val) => val,
             None => #default_val,
         })?;
         let #variable = Ok(#variable) #(.and_then(#stmt))*?;
@@ -314,7 +327,7 @@ fn handle_bool_type(
         }
         None => {
             let msg = format!("not found key, {}", variable_key);
-            quote! { Err(dade::Error::validate_err(#msg)) }
+            quote! { Err(dade::Error::constraint_err("missing", #msg, None)) }
         }
         Some(DefaultTerm::Ident(_)) => {
             return Err(syn::Error::new(
@@ -329,8 +342,8 @@ fn handle_bool_type(
         stmt.push(quote! { #fn_name });
     }
     statements.push(quote! {
-        let #variable: #variable_type = (match dict.get(#variable_key) {
-            Some(val) => dade::FromJsonValue::from_json_value(val),
+        let #variable: #variable_type = (match #variable {
+            Some(val) => val,
             None => #default_val,
         }) #(.and_then(#stmt))*?;
     });
@@ -342,7 +355,7 @@ fn handle_optional_type(
     model_field: &ModelField,
     variable: &Ident,
     variable_type: &Type,
-    variable_key: &TokenStream,
+    _variable_key: &TokenStream,
     statements: &mut Vec<TokenStream>,
     conds: &mut Vec<TokenStream>,
 ) -> Result<(), syn::Error> {
@@ -363,8 +376,17 @@ fn handle_optional_type(
         Some(DefaultTerm::Lit(term)) => match inner_type {
             ModelType::Number => {
                 let val = &term.value;
+                let number = match LitNumber::new(val) {
+                    Some(number) => number?.to_number_token(),
+                    None => {
+                        return Err(syn::Error::new(
+                            variable.span(),
+                            "Support default condition is `null` or value for inner type.",
+                        ))
+                    }
+                };
                 conds.push(quote! {
-                    "default".to_string(), dade::JsonValue::Number(dade::Number::from(#val))
+                    "default".to_string(), dade::JsonValue::Number(#number)
                 });
                 quote! { Some(#val) }
             }
@@ -373,7 +395,7 @@ fn handle_optional_type(
                 conds.push(quote! {
                     "default".to_string(), dade::JsonValue::String(#val.to_string())
                 });
-                quote! { Some(#val.to_string()) }
+                quote! { Some(#val.into()) }
             }
             ModelType::Bool => {
                 let val = &term.value;
@@ -437,8 +459,8 @@ fn handle_optional_type(
         cstmt.push(quote! { #fn_name });
     }
     statements.push(quote! {
-        let #variable: #variable_type = (match dict.get(#variable_key) {
-            Some(val) => dade::FromJsonValue::from_json_value(val),
+        let #variable: #variable_type = (match #variable {
+            Some(val) => val,
             None => Ok(#default_val),
         }).and_then(|x| {
             match x {
@@ -499,9 +521,9 @@ fn handle_array_type(
     }
     let msg = format!("not found key, {}", variable_key);
     statements.push(quote! {
-        let #variable: #variable_type = (match dict.get(#variable_key) {
-            Some(val) => dade::FromJsonValue::from_json_value(val),
-            None => Err(dade::Error::validate_err(#msg)),
+        let #variable: #variable_type = (match #variable {
+            Some(val) => val,
+            None => Err(dade::Error::constraint_err("missing", #msg, None)),
         })?;
         let #variable = Ok(#variable) #(.and_then(#stmt))*?;
     });
@@ -537,14 +559,87 @@ fn handle_other_type(
     }
     let msg = format!("not found key, {}", variable_key);
     statements.push(quote! {
-        let #variable: #variable_type = (match dict.get(#variable_key) {
-            Some(val) => dade::FromJsonValue::from_json_value(val),
-            None => Err(dade::Error::validate_err(#msg)),
+        let #variable: #variable_type = (match #variable {
+            Some(val) => val,
+            None => Err(dade::Error::constraint_err("missing", #msg, None)),
         }) #(.and_then(#stmt))*?;
     });
     Ok(())
 }
 
+/// Makes statements to write members of an object in the order of fields as declared.
+fn dump_object(members: Vec<(String, TokenStream)>) -> TokenStream {
+    let len = members.len();
+    let statements = members.iter().enumerate().map(|(idx, (key, value))| {
+        quote! {
+            dumper.object_key(#idx, #key, writer)?;
+            dade::DumpJson::dump_json(#value, dumper, writer)?;
+        }
+    });
+    quote! {
+        dumper.begin_object(writer)?;
+        #(#statements)*
+        dumper.end_object(#len, writer)
+    }
+}
+
+/// Makes statements to write items of an array.
+fn dump_array(items: Vec<TokenStream>) -> TokenStream {
+    let len = items.len();
+    let statements = items.iter().enumerate().map(|(idx, value)| {
+        quote! {
+            dumper.array_item(#idx, writer)?;
+            dade::DumpJson::dump_json(#value, dumper, writer)?;
+        }
+    });
+    quote! {
+        dumper.begin_array(writer)?;
+        #(#statements)*
+        dumper.end_array(#len, writer)
+    }
+}
+
+/// Makes a statement to bind the result of loading a field, which is None if the field is absent.
+fn bind_field(variable: &Ident, variable_type: &Type, value: TokenStream) -> TokenStream {
+    quote! {
+        let #variable: Option<dade::Result<#variable_type>> = #value;
+    }
+}
+
+/// Wraps statements for a field to collect an error into `errors` instead of returning it.
+fn collect_error(
+    variable: &Ident,
+    variable_type: &Type,
+    field_statements: Vec<TokenStream>,
+    loc: TokenStream,
+) -> TokenStream {
+    quote! {
+        let #variable: Option<#variable_type> = match (|| -> dade::Result<#variable_type> {
+            #(#field_statements)*
+            Ok(#variable)
+        })() {
+            Ok(val) => Some(val),
+            Err(err) => {
+                errors.push(err #loc);
+                None
+            }
+        };
+    }
+}
+
+/// Makes an expression to return an instance if all fields are valid, otherwise merged errors.
+fn construct_or_errors<T: ToTokens>(keys: &[T], construct: TokenStream) -> TokenStream {
+    if keys.is_empty() {
+        return quote! { Ok(#construct) };
+    }
+    quote! {
+        match (#(#keys,)*) {
+            (#(Some(#keys),)*) => Ok(#construct),
+            _ => Err(dade::Error::merge(errors)),
+        }
+    }
+}
+
 fn parse_attrs(attrs: &[Attribute]) -> (TokenStream, ModelField) {
     let mut bag = Vec::new();
     let mut model_field = ModelField::default();
@@ -561,17 +656,88 @@ fn parse_attrs(attrs: &[Attribute]) -> (TokenStream, ModelField) {
     (quote! {#(#bag)*}, model_field)
 }
 
+/// Checks that a model has no generic parameters other than lifetimes.
+fn check_generics(generics: &Generics) -> Result<(), syn::Error> {
+    match generics
+        .params
+        .iter()
+        .find(|param| !matches!(param, GenericParam::Lifetime(_)))
+    {
+        Some(param) => Err(syn::Error::new(
+            param.span(),
+            "Only support lifetime parameters.",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Makes the generics for `FromJsonRef<'dade>`, where the input outlives all lifetimes of a model.
+fn ref_generics(generics: &Generics) -> Generics {
+    let lifetimes: Vec<_> = generics.lifetimes().map(|def| &def.lifetime).collect();
+    let mut ref_generics = generics.clone();
+    if lifetimes.is_empty() {
+        ref_generics.params.insert(0, parse_quote! { 'dade });
+    } else {
+        ref_generics
+            .params
+            .insert(0, parse_quote! { 'dade: #(#lifetimes)+* });
+    }
+    ref_generics
+}
+
+/// The conversions of fields, which accept only a literal of an integer for an integer
+/// with `#[model(strict)]`.
+struct Conversions {
+    from_value: TokenStream,
+    from_ref: TokenStream,
+    load_field: TokenStream,
+}
+
+impl Conversions {
+    fn new(strict: bool) -> Self {
+        if strict {
+            Self {
+                from_value: quote! { dade::FromJsonValue::from_json_value_strict },
+                from_ref: quote! { dade::FromJsonRef::from_json_ref_strict },
+                load_field: quote! { load_field_strict },
+            }
+        } else {
+            Self {
+                from_value: quote! { dade::FromJsonValue::from_json_value },
+                from_ref: quote! { dade::FromJsonRef::from_json_ref },
+                load_field: quote! { load_field },
+            }
+        }
+    }
+}
+
 pub(crate) fn handle_struct(
     ident: Ident,
     vis: Visibility,
     attrs: Vec<Attribute>,
+    generics: Generics,
     data: DataStruct,
+    strict: bool,
 ) -> Result<TokenStream, syn::Error> {
+    let Conversions {
+        from_value,
+        from_ref,
+        load_field,
+    } = Conversions::new(strict);
+    check_generics(&generics)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ref_generics = ref_generics(&generics);
+    let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
     match data.fields {
         Fields::Named(fields_named) => {
             let mut fields = Vec::new();
             let mut maps = Vec::new();
+            let mut members = Vec::new();
             let mut keys = Vec::new();
+            let mut lookups = Vec::new();
+            let mut ref_lookups = Vec::new();
+            let mut slots = Vec::new();
+            let mut arms = Vec::new();
             let mut statements = Vec::new();
             let mut schemas = Vec::new();
             let mut required = Vec::new();
@@ -580,13 +746,13 @@ pub(crate) fn handle_struct(
                 let (attrs, model_field) = parse_attrs(&field.attrs);
                 let variable: &Ident = field.ident.as_ref().unwrap();
                 let variable_vis = &field.vis;
-                let variable_key = if let Some(alias) = &model_field.alias {
-                    let val = alias.value.value();
-                    quote! { #val }
+                let key = if let Some(alias) = &model_field.alias {
+                    alias.value.value()
                 } else {
-                    let val = variable.to_string();
-                    quote! { #val }
+                    variable.to_string()
                 };
+                let variable_key = quote! { #key };
+                members.push((key, quote! { &self.#variable }));
                 maps.push(quote! {
                     (
                         #variable_key.to_string(),
@@ -595,11 +761,31 @@ pub(crate) fn handle_struct(
                 });
                 keys.push(quote! {#variable});
                 let ty = &field.ty;
+                lookups.push(bind_field(
+                    variable,
+                    ty,
+                    quote! { dict.get(#variable_key).map(#from_value) },
+                ));
+                ref_lookups.push(bind_field(
+                    variable,
+                    ty,
+                    quote! { dict.get(#variable_key).map(#from_ref) },
+                ));
+                slots.push(quote! {
+                    let mut #variable: Option<dade::Result<#ty>> = None;
+                });
+                arms.push(quote! {
+                    #variable_key => {
+                        #variable = Some(loader.#load_field()?);
+                        Ok(true)
+                    }
+                });
                 let mut conds: Vec<TokenStream> = Vec::from([quote! {
                     "title".to_string(),
                     dade::JsonValue::String(dade::ToTitle::to_title(#variable_key))
                 }]);
                 let model_type = ModelType::new(ty)?;
+                let mut field_statements = Vec::new();
                 if model_field.default.is_none() && !matches!(model_type, ModelType::Optional(_)) {
                     required.push(quote! { #variable_key })
                 }
@@ -609,7 +795,7 @@ pub(crate) fn handle_struct(
                         variable,
                         ty,
                         &variable_key,
-                        &mut statements,
+                        &mut field_statements,
                         &mut conds,
                     )?,
                     ModelType::Number => handle_number_type(
@@ -617,7 +803,7 @@ pub(crate) fn handle_struct(
                         variable,
                         ty,
                         &variable_key,
-                        &mut statements,
+                        &mut field_statements,
                         &mut conds,
                     )?,
                     ModelType::String => handle_string_type(
@@ -625,7 +811,7 @@ pub(crate) fn handle_struct(
                         variable,
                         ty,
                         &variable_key,
-                        &mut statements,
+                        &mut field_statements,
                         &mut conds,
                     )?,
                     ModelType::Bool => handle_bool_type(
@@ -633,7 +819,7 @@ pub(crate) fn handle_struct(
                         variable,
                         ty,
                         &variable_key,
-                        &mut statements,
+                        &mut field_statements,
                         &mut conds,
                     )?,
                     ModelType::Optional(inner_type) => handle_optional_type(
@@ -642,7 +828,7 @@ pub(crate) fn handle_struct(
                         variable,
                         ty,
                         &variable_key,
-                        &mut statements,
+                        &mut field_statements,
                         &mut conds,
                     )?,
                     ModelType::Array => handle_array_type(
@@ -650,7 +836,7 @@ pub(crate) fn handle_struct(
                         variable,
                         ty,
                         &variable_key,
-                        &mut statements,
+                        &mut field_statements,
                         &mut conds,
                     )?,
                     ModelType::Other => handle_other_type(
@@ -658,10 +844,16 @@ pub(crate) fn handle_struct(
                         variable,
                         ty,
                         &variable_key,
-                        &mut statements,
+                        &mut field_statements,
                         &mut conds,
                     )?,
                 }
+                statements.push(collect_error(
+                    variable,
+                    ty,
+                    field_statements,
+                    quote! { .at_key(#variable_key) },
+                ));
                 schemas.push(quote! {
                     (
                         #variable_key.to_string(),
@@ -681,58 +873,89 @@ pub(crate) fn handle_struct(
             let name = ident.to_string();
             let data_type = data.struct_token;
             let def_name = format!("#/definitions/{}", ident);
+            let construct = construct_or_errors(&keys, quote! { #ident { #(#keys),* } });
+            let dump = dump_object(members);
+            // A model with lifetimes can be loaded only by `FromJsonRef`.
+            let owned_impls = if generics.params.is_empty() {
+                quote! {
+                    impl dade::FromJsonValue for #ident {
+                        fn from_json_value(value: &dade::JsonValue) -> dade::Result<Self> {
+                            match value {
+                                dade::JsonValue::Object(dict) => {
+                                    #(#lookups)*
+                                    let mut errors: Vec<dade::Error> = Vec::new();
+                                    #(#statements)*
+                                    #construct
+                                }
+                                _ => Err(dade::Error::constraint_err("type", "expect `JsonValue::Object`", Some(value.clone()))),
+                            }
+                        }
+                    }
+                    impl dade::FromJsonStream for #ident {
+                        fn from_json_stream<S: dade::Stream<u8>>(loader: &mut dade::JsonLoader<u8, S>) -> dade::Result<Self> {
+                            // Collecting duplicate keys needs the whole object.
+                            if loader.duplicate_keys() == dade::DuplicateKeys::CollectAll
+                                || !loader.begin_object()?
+                            {
+                                return dade::FromJsonValue::from_json_value(&loader.load_value()?);
+                            }
+                            #(#slots)*
+                            loader.load_members(|loader, key| match key {
+                                #(#arms)*
+                                _ => Ok(false),
+                            })?;
+                            let mut errors: Vec<dade::Error> = Vec::new();
+                            #(#statements)*
+                            #construct
+                        }
+                    }
+                }
+            } else {
+                quote! {}
+            };
             Ok(quote! {
-                #(#attrs)* #vis #data_type #ident { #(#fields),* }
-                impl dade::ToJsonValue for #ident {
+                #(#attrs)* #vis #data_type #ident #generics #where_clause { #(#fields),* }
+                impl #impl_generics dade::ToJsonValue for #ident #ty_generics #where_clause {
                     fn to_json_value(&self) -> dade::JsonValue {
                         dade::JsonValue::Object(
-                            std::collections::BTreeMap::from( [#(#maps),*] )
+                            dade::Map::from( [#(#maps),*] )
                         )
                     }
                 }
-                impl dade::FromJsonValue for #ident {
-                    fn from_json_value(value: &dade::JsonValue) -> dade::Result<Self> {
+                impl #impl_generics dade::DumpJson for #ident #ty_generics #where_clause {
+                    fn dump_json<W: std::io::Write>(&self, dumper: &mut dade::JsonDumper, writer: &mut W) -> std::io::Result<()> {
+                        #dump
+                    }
+                }
+                #owned_impls
+                impl #ref_impl_generics dade::FromJsonRef<'dade> for #ident #ty_generics #where_clause {
+                    fn from_json_ref(value: &dade::JsonValueRef<'dade>) -> dade::Result<Self> {
                         match value {
-                            dade::JsonValue::Object(dict) => {
+                            dade::JsonValueRef::Object(dict) => {
+                                #(#ref_lookups)*
+                                let mut errors: Vec<dade::Error> = Vec::new();
                                 #(#statements)*
-                                Ok(#ident { #(#keys),* })
+                                #construct
                             }
-                            _ => Err(dade::Error::validate_err("expect `JsonValue::Object`")),
+                            _ => Err(dade::Error::constraint_err("type", "expect `JsonValue::Object`", Some(dade::ToJsonValue::to_json_value(value)))),
                         }
                     }
                 }
-                impl dade::RegisterSchema for #ident {
+                impl #impl_generics dade::RegisterSchema for #ident #ty_generics #where_clause {
                     fn register_schema(defs: &mut std::collections::BTreeMap<String, dade::JsonValue>) -> dade::JsonValue {
                         if !defs.contains_key(&#name.to_string()) {
                             // Insert temporarily value.
                             defs.insert(#name.to_string(), dade::JsonValue::Null);
-                            let json_value = dade::JsonValue::Object(
-                                    std::collections::BTreeMap::from([
-                                        ("title".to_string(), dade::JsonValue::String(dade::ToTitle::to_title(#name))),
-                                        ("type".to_string(), dade::JsonValue::String("object".to_string())),
-                                        (
-                                            "properties".to_string(),
-                                            dade::JsonValue::Object( std::collections::BTreeMap::from([#(#schemas),*]))
-                                        ),
-                                        (
-                                            "required".to_string(),
-                                            dade::JsonValue::Array(
-                                                Vec::from([#(dade::JsonValue::String(#required.to_string())),*])
-                                            )
-                                        ),
-                                    ])
-                                );
+                            let json_value = dade::json!({
+                                "title": dade::ToTitle::to_title(#name),
+                                "type": "object",
+                                "properties": dade::JsonValue::Object(dade::Map::from([#(#schemas),*])),
+                                "required": Vec::<&str>::from([#(#required),*]),
+                            });
                             // Swap to proper value.
                             defs.insert(#name.to_string(), json_value);
                         }
-                        dade::JsonValue::Object(
-                            std::collections::BTreeMap::from([
-                                (
-                                    "$ref".to_string(),
-                                    dade::JsonValue::String(#def_name.to_string())
-                                ),
-                            ])
-                        )
+                        dade::json!({ "$ref": #def_name })
                     }
                 }
             })
@@ -822,23 +1045,55 @@ pub(crate) fn handle_struct(
                     "title".to_string(), dade::JsonValue::String(dade::ToTitle::to_title(#name))
                 });
                 let indices = syn::Index::from(0);
+                let fd_lookup =
+                    bind_field(&fd_variable, fd_ty, quote! { Some(#from_value(value)) });
+                let fd_load =
+                    bind_field(&fd_variable, fd_ty, quote! { Some(loader.#load_field()?) });
+                let fd_ref_lookup =
+                    bind_field(&fd_variable, fd_ty, quote! { Some(#from_ref(value)) });
+                let owned_impls = if generics.params.is_empty() {
+                    quote! {
+                        impl dade::FromJsonValue for #ident {
+                            fn from_json_value(value: &dade::JsonValue) -> dade::Result<Self> {
+                                #fd_lookup
+                                #(#fd_statements)*
+                                Ok(#ident ( #fd_variable ))
+                            }
+                        }
+                        impl dade::FromJsonStream for #ident {
+                            fn from_json_stream<S: dade::Stream<u8>>(loader: &mut dade::JsonLoader<u8, S>) -> dade::Result<Self> {
+                                #fd_load
+                                #(#fd_statements)*
+                                Ok(#ident ( #fd_variable ))
+                            }
+                        }
+                    }
+                } else {
+                    quote! {}
+                };
                 Ok(quote! {
-                    #(#attrs)* #vis #data_type #ident (
+                    #(#attrs)* #vis #data_type #ident #generics (
                         #fd_attrs #fd_vis #fd_ty
-                    );
-                    impl dade::ToJsonValue for #ident {
+                    ) #where_clause;
+                    impl #impl_generics dade::ToJsonValue for #ident #ty_generics #where_clause {
                         fn to_json_value(&self) -> dade::JsonValue {
                             dade::ToJsonValue::to_json_value(&self.#indices)
                         }
                     }
-                    impl dade::FromJsonValue for #ident {
-                        fn from_json_value(value: &dade::JsonValue) -> dade::Result<Self> {
-                            let dict = [value];
+                    impl #impl_generics dade::DumpJson for #ident #ty_generics #where_clause {
+                        fn dump_json<W: std::io::Write>(&self, dumper: &mut dade::JsonDumper, writer: &mut W) -> std::io::Result<()> {
+                            dade::DumpJson::dump_json(&self.#indices, dumper, writer)
+                        }
+                    }
+                    #owned_impls
+                    impl #ref_impl_generics dade::FromJsonRef<'dade> for #ident #ty_generics #where_clause {
+                        fn from_json_ref(value: &dade::JsonValueRef<'dade>) -> dade::Result<Self> {
+                            #fd_ref_lookup
                             #(#fd_statements)*
                             Ok(#ident ( #fd_variable ))
                         }
                     }
-                    impl dade::RegisterSchema for #ident {
+                    impl #impl_generics dade::RegisterSchema for #ident #ty_generics #where_clause {
                         fn register_schema(defs: &mut std::collections::BTreeMap<String, dade::JsonValue>) -> dade::JsonValue {
                             if !defs.contains_key(&#name.to_string()) {
                                 // Insert temporarily value.
@@ -850,20 +1105,17 @@ pub(crate) fn handle_struct(
                                 // Swap to proper value.
                                 defs.insert(#name.to_string(), json_value);
                             }
-                            dade::JsonValue::Object(
-                                std::collections::BTreeMap::from([
-                                    (
-                                        "$ref".to_string(),
-                                        dade::JsonValue::String(#def_name.to_string())
-                                    ),
-                                ])
-                            )
+                            dade::json!({ "$ref": #def_name })
                         }
                     }
                 })
             } else {
                 let mut fields = Vec::new();
                 let mut keys = Vec::new();
+                let mut lookups = Vec::new();
+                let mut ref_lookups = Vec::new();
+                let mut slots = Vec::new();
+                let mut arms = Vec::new();
                 let mut statements = Vec::new();
                 let mut properties = Vec::new();
                 let mut indices = Vec::new();
@@ -882,6 +1134,7 @@ pub(crate) fn handle_struct(
                     let fd_variable = format_ident!("val{}", idx);
                     let fd_variable_key = quote! { #idx };
                     let fd_model_type = ModelType::new(fd_ty)?;
+                    let mut fd_statements = Vec::new();
 
                     match &fd_model_type {
                         ModelType::Null => handle_null_type(
@@ -889,7 +1142,7 @@ pub(crate) fn handle_struct(
                             &fd_variable,
                             fd_ty,
                             &fd_variable_key,
-                            &mut statements,
+                            &mut fd_statements,
                             &mut fd_conds,
                         )?,
                         ModelType::Number => handle_number_type(
@@ -897,7 +1150,7 @@ pub(crate) fn handle_struct(
                             &fd_variable,
                             fd_ty,
                             &fd_variable_key,
-                            &mut statements,
+                            &mut fd_statements,
                             &mut fd_conds,
                         )?,
                         ModelType::String => handle_string_type(
@@ -905,7 +1158,7 @@ pub(crate) fn handle_struct(
                             &fd_variable,
                             fd_ty,
                             &fd_variable_key,
-                            &mut statements,
+                            &mut fd_statements,
                             &mut fd_conds,
                         )?,
                         ModelType::Bool => handle_bool_type(
@@ -913,7 +1166,7 @@ pub(crate) fn handle_struct(
                             &fd_variable,
                             fd_ty,
                             &fd_variable_key,
-                            &mut statements,
+                            &mut fd_statements,
                             &mut fd_conds,
                         )?,
                         ModelType::Optional(inner_type) => handle_optional_type(
@@ -922,7 +1175,7 @@ pub(crate) fn handle_struct(
                             &fd_variable,
                             fd_ty,
                             &fd_variable_key,
-                            &mut statements,
+                            &mut fd_statements,
                             &mut fd_conds,
                         )?,
                         ModelType::Array => handle_array_type(
@@ -930,7 +1183,7 @@ pub(crate) fn handle_struct(
                             &fd_variable,
                             fd_ty,
                             &fd_variable_key,
-                            &mut statements,
+                            &mut fd_statements,
                             &mut fd_conds,
                         )?,
                         ModelType::Other => handle_other_type(
@@ -938,10 +1191,32 @@ pub(crate) fn handle_struct(
                             &fd_variable,
                             fd_ty,
                             &fd_variable_key,
-                            &mut statements,
+                            &mut fd_statements,
                             &mut fd_conds,
                         )?,
                     }
+                    lookups.push(bind_field(
+                        &fd_variable,
+                        fd_ty,
+                        quote! { dict.get(#idx).map(#from_value) },
+                    ));
+                    ref_lookups.push(bind_field(
+                        &fd_variable,
+                        fd_ty,
+                        quote! { dict.get(#idx).map(#from_ref) },
+                    ));
+                    slots.push(quote! {
+                        let mut #fd_variable: Option<dade::Result<#fd_ty>> = None;
+                    });
+                    arms.push(quote! {
+                        #idx => #fd_variable = Some(loader.#load_field()?),
+                    });
+                    statements.push(collect_error(
+                        &fd_variable,
+                        fd_ty,
+                        fd_statements,
+                        quote! { .at_index(#idx) },
+                    ));
 
                     fields.push(quote! { #fd_attrs #fd_vis #fd_ty });
                     keys.push(fd_variable);
@@ -960,25 +1235,72 @@ pub(crate) fn handle_struct(
                 let name = ident.to_string();
                 let data_type = data.struct_token;
                 let def_name = format!("#/definitions/{}", ident);
+                let construct = construct_or_errors(&keys, quote! { #ident ( #(#keys),* ) });
+                let dump = dump_array(indices.iter().map(|idx| quote! { &self.#idx }).collect());
+                let owned_impls = if generics.params.is_empty() {
+                    quote! {
+                        impl dade::FromJsonValue for #ident {
+                            fn from_json_value(value: &dade::JsonValue) -> dade::Result<Self> {
+                                match value {
+                                    dade::JsonValue::Array(dict) => {
+                                        #(#lookups)*
+                                        let mut errors: Vec<dade::Error> = Vec::new();
+                                        #(#statements)*
+                                        #construct
+                                    }
+                                    _ => Err(dade::Error::constraint_err("type", "expect `JsonValue::Array`", Some(value.clone()))),
+                                }
+                            }
+                        }
+                        impl dade::FromJsonStream for #ident {
+                            fn from_json_stream<S: dade::Stream<u8>>(loader: &mut dade::JsonLoader<u8, S>) -> dade::Result<Self> {
+                                if !loader.begin_array()? {
+                                    return dade::FromJsonValue::from_json_value(&loader.load_value()?);
+                                }
+                                #(#slots)*
+                                loader.load_items(|loader, index| {
+                                    match index {
+                                        #(#arms)*
+                                        _ => loader.skip_value()?,
+                                    }
+                                    Ok(())
+                                })?;
+                                let mut errors: Vec<dade::Error> = Vec::new();
+                                #(#statements)*
+                                #construct
+                            }
+                        }
+                    }
+                } else {
+                    quote! {}
+                };
                 Ok(quote! {
-                    #(#attrs)* #vis #data_type #ident ( #(#fields),* );
-                    impl dade::ToJsonValue for #ident {
+                    #(#attrs)* #vis #data_type #ident #generics ( #(#fields),* ) #where_clause;
+                    impl #impl_generics dade::ToJsonValue for #ident #ty_generics #where_clause {
                         fn to_json_value(&self) -> dade::JsonValue {
                             dade::JsonValue::Array(Vec::from([#(dade::ToJsonValue::to_json_value(&self.#indices)),*]))
                         }
                     }
-                    impl dade::FromJsonValue for #ident {
-                        fn from_json_value(value: &dade::JsonValue) -> dade::Result<Self> {
+                    impl #impl_generics dade::DumpJson for #ident #ty_generics #where_clause {
+                        fn dump_json<W: std::io::Write>(&self, dumper: &mut dade::JsonDumper, writer: &mut W) -> std::io::Result<()> {
+                            #dump
+                        }
+                    }
+                    #owned_impls
+                    impl #ref_impl_generics dade::FromJsonRef<'dade> for #ident #ty_generics #where_clause {
+                        fn from_json_ref(value: &dade::JsonValueRef<'dade>) -> dade::Result<Self> {
                             match value {
-                                dade::JsonValue::Array(dict) => {
+                                dade::JsonValueRef::Array(dict) => {
+                                    #(#ref_lookups)*
+                                    let mut errors: Vec<dade::Error> = Vec::new();
                                     #(#statements)*
-                                    Ok(#ident ( #(#keys),* ))
+                                    #construct
                                 }
-                                _ => Err(dade::Error::validate_err("expect `JsonValue::Array`")),
+                                _ => Err(dade::Error::constraint_err("type", "expect `JsonValue::Array`", Some(dade::ToJsonValue::to_json_value(value)))),
                             }
                         }
                     }
-                    impl dade::RegisterSchema for #ident {
+                    impl #impl_generics dade::RegisterSchema for #ident #ty_generics #where_clause {
                         fn register_schema(defs: &mut std::collections::BTreeMap<String, dade::JsonValue>) -> dade::JsonValue {
                             if !defs.contains_key(&#name.to_string()) {
                                 // Insert temporarily value.
@@ -987,29 +1309,28 @@ pub(crate) fn handle_struct(
                                 let prefix_items = dade::JsonValue::Array(Vec::from([#(#properties),*]));
                                 defs.insert(
                                     #name.to_string(),
-                                    dade::JsonValue::Object(std::collections::BTreeMap::from([
-                                        ("title".to_string(), dade::JsonValue::String(#name.to_string())),
-                                        ("type".to_string(), dade::JsonValue::String("array".to_string())),
+                                    dade::json!({
+                                        "title": #name,
+                                        "type": "array",
                                         // TODO;
-                                        // ("items".to_string(), dade::JsonValue::Bool(false)),
-                                        ("prefixItems".to_string(), prefix_items),
-                                    ])),
+                                        // "items": false,
+                                        "prefixItems": prefix_items,
+                                    }),
                                 );
                             }
-                            dade::JsonValue::Object(
-                                std::collections::BTreeMap::from([
-                                    (
-                                        "$ref".to_string(),
-                                        dade::JsonValue::String(#def_name.to_string())
-                                    ),
-                                ])
-                            )
+                            dade::json!({ "$ref": #def_name })
                         }
                     }
                 })
             }
         }
         Fields::Unit => {
+            if !generics.params.is_empty() {
+                return Err(syn::Error::new(
+                    generics.span(),
+                    "No support generic parameters on the unit struct.",
+                ));
+            }
             let name = ident.to_string();
             let data_type = data.struct_token;
             let def_name = format!("#/definitions/{}", ident);
@@ -1017,36 +1338,39 @@ pub(crate) fn handle_struct(
                 #(#attrs)* #vis #data_type #ident { }
                 impl dade::ToJsonValue for #ident {
                     fn to_json_value(&self) -> dade::JsonValue {
-                        dade::JsonValue::Object(std::collections::BTreeMap::new())
+                        dade::JsonValue::Object(dade::Map::new())
+                    }
+                }
+                impl dade::DumpJson for #ident {
+                    fn dump_json<W: std::io::Write>(&self, dumper: &mut dade::JsonDumper, writer: &mut W) -> std::io::Result<()> {
+                        dumper.begin_object(writer)?;
+                        dumper.end_object(0, writer)
                     }
                 }
                 impl dade::FromJsonValue for #ident {
                     fn from_json_value(value: &dade::JsonValue) -> dade::Result<Self> {
                         match value {
                             dade::JsonValue::Object(dict) => Ok(#ident { }),
-                            _ => Err(dade::Error::validate_err("expect `JsonValue::Object`")),
+                            _ => Err(dade::Error::constraint_err("type", "expect `JsonValue::Object`", Some(value.clone()))),
                         }
                     }
                 }
+                impl dade::FromJsonStream for #ident {}
+                impl<'dade> dade::FromJsonRef<'dade> for #ident {
+                    fn from_json_ref(value: &dade::JsonValueRef<'dade>) -> dade::Result<Self> {
+                        dade::FromJsonValue::from_json_value(&dade::ToJsonValue::to_json_value(value))
+                    }
+                }
                 impl dade::RegisterSchema for #ident {
                     fn register_schema(defs: &mut std::collections::BTreeMap<String, dade::JsonValue>) -> dade::JsonValue {
                         if !defs.contains_key(&#name.to_string()) {
-                            let json_value = dade::JsonValue::Object(
-                                    std::collections::BTreeMap::from([
-                                        ("title".to_string(), dade::JsonValue::String(dade::ToTitle::to_title(#name))),
-                                        ("type".to_string(), dade::JsonValue::String("object".to_string())),
-                                    ])
-                                );
+                            let json_value = dade::json!({
+                                "title": dade::ToTitle::to_title(#name),
+                                "type": "object",
+                            });
                             defs.insert(#name.to_string(), json_value);
                         }
-                        dade::JsonValue::Object(
-                            std::collections::BTreeMap::from([
-                                (
-                                    "$ref".to_string(),
-                                    dade::JsonValue::String(#def_name.to_string())
-                                ),
-                            ])
-                        )
+                        dade::json!({ "$ref": #def_name })
                     }
                 }
             })
@@ -1058,10 +1382,20 @@ pub(crate) fn handle_enum(
     ident: Ident,
     vis: Visibility,
     attrs: Vec<Attribute>,
+    generics: Generics,
     data: DataEnum,
+    strict: bool,
 ) -> Result<TokenStream, syn::Error> {
+    let Conversions { from_value, .. } = Conversions::new(strict);
+    if !generics.params.is_empty() {
+        return Err(syn::Error::new(
+            generics.span(),
+            "No support generic parameters on enum.",
+        ));
+    }
     let mut fields = Vec::new();
     let mut to_jsons = Vec::new();
+    let mut dumps = Vec::new();
     let mut statements = Vec::new();
     let mut schemas = Vec::new();
     for variant in data.variants {
@@ -1072,6 +1406,7 @@ pub(crate) fn handle_enum(
             Fields::Named(field) => {
                 let mut fds = Vec::new();
                 let mut maps = Vec::new();
+                let mut members = Vec::new();
                 let mut idents = Vec::new();
                 let mut properties = Vec::new();
                 let mut required = Vec::new();
@@ -1080,13 +1415,13 @@ pub(crate) fn handle_enum(
                 for fd in field.named {
                     let (fd_attrs, fd_model_field) = parse_attrs(&fd.attrs);
                     let fd_variable = fd.ident.unwrap();
-                    let fd_variable_key = if let Some(alias) = &fd_model_field.alias {
-                        let val = alias.value.value();
-                        quote! { #val }
+                    let fd_key = if let Some(alias) = &fd_model_field.alias {
+                        alias.value.value()
                     } else {
-                        let val = fd_variable.to_string();
-                        quote! { #val }
+                        fd_variable.to_string()
                     };
+                    let fd_variable_key = quote! { #fd_key };
+                    members.push((fd_key, quote! { #fd_variable }));
                     let fd_ty = &fd.ty;
                     let fd_model_type = ModelType::new(fd_ty)?;
 
@@ -1094,6 +1429,11 @@ pub(crate) fn handle_enum(
                         "title".to_string(),
                         dade::JsonValue::String(dade::ToTitle::to_title(#fd_variable_key))
                     }]);
+                    fd_statements.push(bind_field(
+                        &fd_variable,
+                        fd_ty,
+                        quote! { dict.get(#fd_variable_key).map(#from_value) },
+                    ));
 
                     match &fd_model_type {
                         ModelType::Null => handle_null_type(
@@ -1182,7 +1522,11 @@ pub(crate) fn handle_enum(
                 }
                 fields.push(quote! { #attrs #variant_ident { #(#fds),* } });
                 to_jsons.push(quote! {
-                    #ident::#variant_ident{ #(#idents),* } => dade::JsonValue::Object(std::collections::BTreeMap::from([#(#maps),*]))
+                    #ident::#variant_ident{ #(#idents),* } => dade::JsonValue::Object(dade::Map::from([#(#maps),*]))
+                });
+                let dump = dump_object(members);
+                dumps.push(quote! {
+                    #ident::#variant_ident{ #(#idents),* } => { #dump }
                 });
                 statements.push(quote! {
                     if let dade::JsonValue::Object(dict) = value {
@@ -1197,12 +1541,12 @@ pub(crate) fn handle_enum(
                 });
                 let title = variant_ident.to_string();
                 schemas.push(quote! {
-                    dade::JsonValue::Object(std::collections::BTreeMap::from([
-                        ("title".to_string(), dade::JsonValue::String(#title.to_string())),
-                        ("type".to_string(), dade::JsonValue::String("object".to_string())),
-                        ("properties".to_string(), dade::JsonValue::Object(std::collections::BTreeMap::from([ #(#properties),* ]))),
-                        ("required".to_string(), dade::JsonValue::Array(Vec::from([ #(#required),* ]))),
-                    ]))
+                    dade::json!({
+                        "title": #title,
+                        "type": "object",
+                        "properties": dade::JsonValue::Object(dade::Map::from([ #(#properties),* ])),
+                        "required": dade::JsonValue::Array(Vec::from([ #(#required),* ])),
+                    })
                 });
             }
             Fields::Unnamed(field) => {
@@ -1223,6 +1567,12 @@ pub(crate) fn handle_enum(
                     let fd_ty = &fd.ty;
                     let fd_model_type = ModelType::new(fd_ty)?;
                     let mut fd_conds: Vec<TokenStream> = Vec::new();
+                    let fd_value = if field.unnamed.len() == 1 {
+                        quote! { Some(#from_value(value)) }
+                    } else {
+                        quote! { dict.get(#idx).map(#from_value) }
+                    };
+                    fd_statements.push(bind_field(&fd_variable, fd_ty, fd_value));
 
                     match &fd_model_type {
                         ModelType::Null => handle_null_type(
@@ -1301,9 +1651,11 @@ pub(crate) fn handle_enum(
                     to_jsons.push(quote! {
                         #ident::#variant_ident(#(#keys)*) => dade::ToJsonValue::to_json_value(#(#keys)*)
                     });
+                    dumps.push(quote! {
+                        #ident::#variant_ident(#(#keys)*) => dade::DumpJson::dump_json(#(#keys)*, dumper, writer)
+                    });
                     statements.push(quote! {
                         {
-                            let dict = [value];
                             let ret = (|| -> dade::Result<#ident> {
                                 #(#fd_statements)*
                                 Ok(#ident::#variant_ident ( #(#keys),* ))
@@ -1329,6 +1681,10 @@ pub(crate) fn handle_enum(
                             dade::JsonValue::Array(Vec::from([#(dade::ToJsonValue::to_json_value(#keys)),*]))
                         }
                     });
+                    let dump = dump_array(keys.iter().map(|key| quote! { #key }).collect());
+                    dumps.push(quote! {
+                        #ident::#variant_ident(#(#keys),*) => { #dump }
+                    });
                     statements.push(quote! {
                         if let dade::JsonValue::Array(dict) = value {
                             let ret = (|| -> dade::Result<#ident> {
@@ -1342,13 +1698,13 @@ pub(crate) fn handle_enum(
                     });
                     let title = variant_ident.to_string();
                     schemas.push(quote! {
-                        dade::JsonValue::Object(std::collections::BTreeMap::from([
-                            ("title".to_string(), dade::JsonValue::String(#title.to_string())),
-                            ("type".to_string(), dade::JsonValue::String("array".to_string())),
+                        dade::json!({
+                            "title": #title,
+                            "type": "array",
                             // TODO;
-                            // ("items".to_string(), dade::JsonValue::Bool(false)),
-                            ("prefixItems".to_string(), dade::JsonValue::Array(Vec::from([#(#properties),*]))),
-                        ]))
+                            // "items": false,
+                            "prefixItems": dade::JsonValue::Array(Vec::from([#(#properties),*])),
+                        })
                     });
                 }
             }
@@ -1372,6 +1728,9 @@ pub(crate) fn handle_enum(
                 to_jsons.push(quote! {
                         #ident::#variant_ident => dade::JsonValue::String(#cond.to_string())
                 });
+                dumps.push(quote! {
+                    #ident::#variant_ident => dumper.write_str(#cond, writer)
+                });
                 statements.push(quote! {
                     if let dade::JsonValue::String(val) = value {
                         if val == #cond { return Ok(#ident::#variant_ident); }
@@ -1379,10 +1738,7 @@ pub(crate) fn handle_enum(
                 });
                 let title = variant_ident.to_string();
                 schemas.push(quote! {
-                    dade::JsonValue::Object(std::collections::BTreeMap::from([
-                        ("title".to_string(), dade::JsonValue::String(#title.to_string())),
-                        ("const".to_string(), dade::JsonValue::String(#cond.to_string()))
-                    ]))
+                    dade::json!({ "title": #title, "const": #cond })
                 });
             }
         };
@@ -1397,10 +1753,21 @@ pub(crate) fn handle_enum(
                 match self { #(#to_jsons),* }
             }
         }
+        impl dade::DumpJson for #ident {
+            fn dump_json<W: std::io::Write>(&self, dumper: &mut dade::JsonDumper, writer: &mut W) -> std::io::Result<()> {
+                match self { #(#dumps),* }
+            }
+        }
         impl dade::FromJsonValue for #ident {
             fn from_json_value(value: &dade::JsonValue) -> dade::Result<Self> {
                 #(#statements)*
-                Err(dade::Error::validate_err("No value with expected"))
+                Err(dade::Error::constraint_err("enum", "No value with expected", Some(value.clone())))
+            }
+        }
+        impl dade::FromJsonStream for #ident {}
+        impl<'dade> dade::FromJsonRef<'dade> for #ident {
+            fn from_json_ref(value: &dade::JsonValueRef<'dade>) -> dade::Result<Self> {
+                dade::FromJsonValue::from_json_value(&dade::ToJsonValue::to_json_value(value))
             }
         }
         impl dade::RegisterSchema for #ident {
@@ -1410,18 +1777,9 @@ pub(crate) fn handle_enum(
                     defs.insert(#name.to_string(), dade::JsonValue::Null);
                     let json_value = dade::JsonValue::Array(Vec::from([ #(#schemas),*]));
                     // Swap to proper value.
-                    defs.insert(
-                        #name.to_string(),
-                        dade::JsonValue::Object(std::collections::BTreeMap::from([
-                            ("title".to_string(), dade::JsonValue::String(#name.to_string())),
-                            ("anyOf".to_string(), json_value),
-                        ])),
-                    );
+                    defs.insert(#name.to_string(), dade::json!({ "title": #name, "anyOf": json_value }));
                 }
-                dade::JsonValue::Object(std::collections::BTreeMap::from([(
-                    "$ref".to_string(),
-                    dade::JsonValue::String(#def_name.to_string()),
-                )]))
+                dade::json!({ "$ref": #def_name })
             }
         }
     })
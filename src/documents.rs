@@ -0,0 +1,91 @@
+use crate::error::Result;
+use crate::json::JsonValue;
+use crate::load::JsonLoader;
+use crate::stream::Stream;
+
+/// An iterator that loads successive values from one input, e.g. the output of `jq -c`.
+///
+/// Each item is the byte offset where the value starts and the value itself.
+/// Values are separated by optional whitespace by default, or each value is preceded by
+/// a record separator (`0x1E`) as RFC 7464 with `with_record_separator`.
+/// The limits of the given `JsonLoader` are applied to each value, except for the size of the input,
+/// which is applied to the whole input. The iteration ends after an error.
+///
+/// For example,
+///
+/// ```rust
+/// use dade::{json_dump, JsonDocuments, JsonLoader};
+///
+/// let documents: Vec<_> = JsonDocuments::new(JsonLoader::from("{\"a\": 1}\n[2]3"))
+///     .map(|item| item.map(|(offset, value)| (offset, json_dump(&value, false))))
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(
+///     documents,
+///     [
+///         (0, "{\"a\":1}".to_string()),
+///         (9, "[2]".to_string()),
+///         (12, "3".to_string())
+///     ]
+/// );
+/// ```
+pub struct JsonDocuments<S: Stream<u8>> {
+    loader: JsonLoader<u8, S>,
+    record_separator: bool,
+    done: bool,
+}
+
+impl<S: Stream<u8>> JsonDocuments<S> {
+    pub fn new(loader: JsonLoader<u8, S>) -> Self {
+        Self {
+            loader,
+            record_separator: false,
+            done: false,
+        }
+    }
+
+    /// Sets whether each value is preceded by a record separator as RFC 7464, which is false by default.
+    pub fn with_record_separator(mut self, record_separator: bool) -> Self {
+        self.record_separator = record_separator;
+        self
+    }
+
+    /// Returns a mutable reference to the underlying stream.
+    ///
+    /// With `ReadBytes`, this is needed to take an I/O error after the iteration fails.
+    pub fn stream_mut(&mut self) -> &mut S {
+        self.loader.stream_mut()
+    }
+
+    fn load_document(&mut self) -> Result<Option<(usize, JsonValue)>> {
+        if !self.loader.has_next()? {
+            return self.loader.finish().map(|_| None);
+        }
+        if self.record_separator {
+            self.loader.skip_record_separator()?;
+        }
+        let offset = self.loader.position();
+        Ok(Some((offset, self.loader.load_value()?)))
+    }
+}
+
+impl<S: Stream<u8>> Iterator for JsonDocuments<S> {
+    type Item = Result<(usize, JsonValue)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.load_document() {
+            Ok(Some(item)) => Some(Ok(item)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
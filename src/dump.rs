@@ -1,119 +1,374 @@
+use std::borrow::Cow;
+use std::io::Write;
+
+use crate::error::{Error, Result};
 use crate::json::JsonValue;
 
-const HEX_CODE: [char; 16] = [
-    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'a', 'b', 'c', 'd', 'e', 'f',
-];
+const HEX_CODE: [u8; 16] = *b"0123456789abcdef";
+
+/// Characters for indentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    /// Indent by the given number of spaces per level.
+    Spaces(usize),
+    /// Indent by the given number of tabs per level.
+    Tabs(usize),
+}
+
+/// Characters for line breaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Newline {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+}
+
+/// A format to pretty-print JSON.
+///
+/// By default, it indents by 2 spaces, breaks lines by `\n`, and puts a space after `:`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyFormat {
+    indent: Indent,
+    newline: Newline,
+    space_after_colon: bool,
+}
+
+impl PrettyFormat {
+    pub fn new() -> Self {
+        Self {
+            indent: Indent::Spaces(2),
+            newline: Newline::Lf,
+            space_after_colon: true,
+        }
+    }
+    /// Sets characters for indentation.
+    pub fn with_indent(mut self, indent: Indent) -> Self {
+        self.indent = indent;
+        self
+    }
+    /// Sets characters for line breaks.
+    pub fn with_newline(mut self, newline: Newline) -> Self {
+        self.newline = newline;
+        self
+    }
+    /// Sets whether to put a space after `:`.
+    pub fn with_space_after_colon(mut self, space_after_colon: bool) -> Self {
+        self.space_after_colon = space_after_colon;
+        self
+    }
+}
+
+impl Default for PrettyFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 pub struct JsonDumper {
     ensure_ascii: bool,
-    buffer: String,
+    pretty: Option<PrettyFormat>,
+    depth: usize,
 }
 
 impl JsonDumper {
     pub fn new(ensure_ascii: bool) -> Self {
         Self {
             ensure_ascii,
-            buffer: String::new(),
+            pretty: None,
+            depth: 0,
         }
     }
+    /// Pretty-prints JSON in the given format.
+    pub fn with_pretty(mut self, format: PrettyFormat) -> Self {
+        self.pretty = Some(format);
+        self
+    }
     #[inline]
     pub fn escape(val: &str, buffer: &mut String, ensure_ascii: bool) {
         buffer.clear();
-        buffer.push('"');
-        for c in val.chars() {
-            match c {
-                '\u{0}' => buffer.push_str("\\u0000"),
-                '\u{0022}' => buffer.push_str("\\\""),
-                '\u{005C}' => buffer.push_str("\\\\"),
+        let mut bytes = std::mem::take(buffer).into_bytes();
+        // Writing to Vec never fails.
+        JsonDumper::write_escaped(val, &mut bytes, ensure_ascii).unwrap();
+        // The escaped string consists of the characters of the valid string and ASCII.
+        *buffer = unsafe { String::from_utf8_unchecked(bytes) };
+    }
+    #[inline]
+    fn write_escaped_code<W: Write>(code: u16, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&[
+            b'\\',
+            b'u',
+            HEX_CODE[((code & 0xF000) >> 12) as usize],
+            HEX_CODE[((code & 0x0F00) >> 8) as usize],
+            HEX_CODE[((code & 0x00F0) >> 4) as usize],
+            HEX_CODE[(code & 0x000F) as usize],
+        ])
+    }
+    fn write_escaped<W: Write>(
+        val: &str,
+        writer: &mut W,
+        ensure_ascii: bool,
+    ) -> std::io::Result<()> {
+        writer.write_all(b"\"")?;
+        // The start of characters which are written as they are.
+        let mut start = 0;
+        for (pos, c) in val.char_indices() {
+            let escaped: &[u8] = match c {
+                '\u{0022}' => b"\\\"",
+                '\u{005C}' => b"\\\\",
                 // pass
-                // '\u{002F}' => self.buffer.push_str("\\/"),
-                '\u{0008}' => buffer.push_str("\\b"),
-                '\u{000C}' => buffer.push_str("\\f"),
-                '\u{000A}' => buffer.push_str("\\n"),
-                '\u{000D}' => buffer.push_str("\\r"),
-                '\u{0009}' => buffer.push_str("\\t"),
-                '\u{0020}'..='\u{0021}' | '\u{0023}'..='\u{005B}' | '\u{005D}'..='\u{007E}' => {
-                    buffer.push(c);
-                }
-                _ if !ensure_ascii && c.is_alphabetic() => {
-                    buffer.push(c);
+                // '\u{002F}' => b"\\/",
+                '\u{0008}' => b"\\b",
+                '\u{000C}' => b"\\f",
+                '\u{000A}' => b"\\n",
+                '\u{000D}' => b"\\r",
+                '\u{0009}' => b"\\t",
+                '\u{0020}'..='\u{007E}' => continue,
+                _ if !ensure_ascii && c.is_alphabetic() => continue,
+                _ => b"",
+            };
+            writer.write_all(&val.as_bytes()[start..pos])?;
+            start = pos + c.len_utf8();
+            if !escaped.is_empty() {
+                writer.write_all(escaped)?;
+            } else {
+                let mut codes = [0; 2];
+                for code in c.encode_utf16(&mut codes).iter() {
+                    JsonDumper::write_escaped_code(*code, writer)?;
                 }
-                _ if ensure_ascii || !c.is_alphabetic() => {
-                    if c.len_utf16() == 1 {
-                        let code = c as u16;
-                        buffer.push_str("\\u");
-                        buffer.push(HEX_CODE[((code & 0xF000) >> 12) as usize]);
-                        buffer.push(HEX_CODE[((code & 0x0F00) >> 8) as usize]);
-                        buffer.push(HEX_CODE[((code & 0x00F0) >> 4) as usize]);
-                        buffer.push(HEX_CODE[(code & 0x000F) as usize]);
-                    } else {
-                        let mut codes = [0; 2];
-                        c.encode_utf16(&mut codes);
-                        buffer.push_str("\\u");
-                        buffer.push(HEX_CODE[((codes[0] & 0xF000) >> 12) as usize]);
-                        buffer.push(HEX_CODE[((codes[0] & 0x0F00) >> 8) as usize]);
-                        buffer.push(HEX_CODE[((codes[0] & 0x00F0) >> 4) as usize]);
-                        buffer.push(HEX_CODE[(codes[0] & 0x000F) as usize]);
-                        buffer.push_str("\\u");
-                        buffer.push(HEX_CODE[((codes[1] & 0xF000) >> 12) as usize]);
-                        buffer.push(HEX_CODE[((codes[1] & 0x0F00) >> 8) as usize]);
-                        buffer.push(HEX_CODE[((codes[1] & 0x00F0) >> 4) as usize]);
-                        buffer.push(HEX_CODE[(codes[1] & 0x000F) as usize]);
-                    }
-                }
-                _ => buffer.push(c),
             }
         }
-        buffer.push('"');
-    }
-    pub fn dump(&mut self, value: &JsonValue) -> String {
-        match value {
-            JsonValue::Null => "null".to_string(),
-            JsonValue::Bool(val) => val.to_string(),
-            JsonValue::Number(val) => val.to_string(),
-            JsonValue::String(val) => {
-                JsonDumper::escape(val, &mut self.buffer, self.ensure_ascii);
-                self.buffer.to_string()
+        writer.write_all(&val.as_bytes()[start..])?;
+        writer.write_all(b"\"")
+    }
+    /// Writes a line break and indentation for the current depth, if pretty-printing.
+    #[inline]
+    fn write_newline<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        if let Some(format) = &self.pretty {
+            match format.newline {
+                Newline::Lf => writer.write_all(b"\n")?,
+                Newline::CrLf => writer.write_all(b"\r\n")?,
+            }
+            let (c, width) = match format.indent {
+                Indent::Spaces(width) => (b" ", width),
+                Indent::Tabs(width) => (b"\t", width),
+            };
+            for _ in 0..width * self.depth {
+                writer.write_all(c)?;
             }
+        }
+        Ok(())
+    }
+    #[inline]
+    fn write_colon<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self.pretty {
+            Some(PrettyFormat {
+                space_after_colon: true,
+                ..
+            }) => writer.write_all(b": "),
+            _ => writer.write_all(b":"),
+        }
+    }
+    /// Writes a string.
+    pub fn write_str<W: Write>(&mut self, val: &str, writer: &mut W) -> std::io::Result<()> {
+        JsonDumper::write_escaped(val, writer, self.ensure_ascii)
+    }
+    /// Writes the start of an object.
+    pub fn begin_object<W: Write>(&mut self, writer: &mut W) -> std::io::Result<()> {
+        self.depth += 1;
+        writer.write_all(b"{")
+    }
+    /// Writes a key of an object, which follows a separator unless it is of the first member.
+    pub fn object_key<W: Write>(
+        &mut self,
+        index: usize,
+        key: &str,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        if index > 0 {
+            writer.write_all(b",")?;
+        }
+        self.write_newline(writer)?;
+        JsonDumper::write_escaped(key, writer, self.ensure_ascii)?;
+        self.write_colon(writer)
+    }
+    /// Writes the end of an object which has the given number of members.
+    pub fn end_object<W: Write>(&mut self, len: usize, writer: &mut W) -> std::io::Result<()> {
+        self.depth -= 1;
+        if len > 0 {
+            self.write_newline(writer)?;
+        }
+        writer.write_all(b"}")
+    }
+    /// Writes the start of an array.
+    pub fn begin_array<W: Write>(&mut self, writer: &mut W) -> std::io::Result<()> {
+        self.depth += 1;
+        writer.write_all(b"[")
+    }
+    /// Writes a separator before an item of an array, which is nothing for the first item.
+    pub fn array_item<W: Write>(&mut self, index: usize, writer: &mut W) -> std::io::Result<()> {
+        if index > 0 {
+            writer.write_all(b",")?;
+        }
+        self.write_newline(writer)
+    }
+    /// Writes the end of an array which has the given number of items.
+    pub fn end_array<W: Write>(&mut self, len: usize, writer: &mut W) -> std::io::Result<()> {
+        self.depth -= 1;
+        if len > 0 {
+            self.write_newline(writer)?;
+        }
+        writer.write_all(b"]")
+    }
+    pub fn dump<T: DumpJson + ?Sized>(&mut self, value: &T) -> String {
+        let mut bytes = Vec::new();
+        // Writing to Vec never fails.
+        self.dump_to(value, &mut bytes).unwrap();
+        // The dumped JSON consists of valid strings and ASCII.
+        unsafe { String::from_utf8_unchecked(bytes) }
+    }
+    /// Dumps a value into the writer directly.
+    ///
+    /// The writer should be buffered, e.g. by `BufWriter`, since it is written in small pieces.
+    pub fn dump_to<T: DumpJson + ?Sized, W: Write>(
+        &mut self,
+        value: &T,
+        mut writer: W,
+    ) -> Result<()> {
+        self.depth = 0;
+        value
+            .dump_json(self, &mut writer)
+            .map_err(|err| Error::io_err(&err))
+    }
+}
+
+/// A trait defines as the format to write data as JSON directly.
+pub trait DumpJson {
+    fn dump_json<W: Write>(&self, dumper: &mut JsonDumper, writer: &mut W) -> std::io::Result<()>;
+}
+
+impl DumpJson for JsonValue {
+    fn dump_json<W: Write>(&self, dumper: &mut JsonDumper, writer: &mut W) -> std::io::Result<()> {
+        match self {
+            JsonValue::Null => writer.write_all(b"null"),
+            JsonValue::Bool(val) => val.dump_json(dumper, writer),
+            JsonValue::Number(val) => write!(writer, "{}", val),
+            JsonValue::String(val) => dumper.write_str(val, writer),
             JsonValue::Array(arr) => {
-                if arr.is_empty() {
-                    return "[]".to_string();
-                }
-                let mut duffer = String::from('[');
-                let mut iter = arr.iter();
-                duffer.push_str(self.dump(iter.next().unwrap()).as_str());
-                for val in iter {
-                    duffer.push(',');
-                    duffer.push_str(self.dump(val).as_str());
+                dumper.begin_array(writer)?;
+                for (idx, val) in arr.iter().enumerate() {
+                    dumper.array_item(idx, writer)?;
+                    val.dump_json(dumper, writer)?;
                 }
-                duffer.push(']');
-                duffer
+                dumper.end_array(arr.len(), writer)
             }
             JsonValue::Object(dict) => {
-                if dict.is_empty() {
-                    return "{}".to_string();
+                dumper.begin_object(writer)?;
+                for (idx, (key, val)) in dict.iter().enumerate() {
+                    dumper.object_key(idx, key, writer)?;
+                    val.dump_json(dumper, writer)?;
                 }
-                let mut duffer = String::from('{');
-                let mut iter = dict.iter();
-                let (key, value) = iter.next().unwrap();
-                duffer.push_str({
-                    JsonDumper::escape(key, &mut self.buffer, self.ensure_ascii);
-                    self.buffer.as_str()
-                });
-                duffer.push(':');
-                duffer.push_str(self.dump(value).as_str());
-                for (key, value) in iter {
-                    duffer.push(',');
-                    duffer.push_str({
-                        JsonDumper::escape(key, &mut self.buffer, self.ensure_ascii);
-                        self.buffer.as_str()
-                    });
-                    duffer.push(':');
-                    duffer.push_str(self.dump(value).as_str());
+                dumper.end_object(dict.len(), writer)
+            }
+        }
+    }
+}
+
+impl DumpJson for () {
+    fn dump_json<W: Write>(&self, _: &mut JsonDumper, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(b"null")
+    }
+}
+
+macro_rules! dump_json_for_num {
+    ( $( $i:ident ),* ) => {
+        $(
+            impl DumpJson for $i {
+                fn dump_json<W: Write>(&self, _: &mut JsonDumper, writer: &mut W) -> std::io::Result<()> {
+                    write!(writer, "{}", self)
                 }
-                duffer.push('}');
-                duffer
             }
+        )*
+    };
+}
+
+dump_json_for_num!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! dump_json_for_float {
+    ( $( $i:ident ),* ) => {
+        $(
+            impl DumpJson for $i {
+                /// Writes `null` if the value is NaN or infinite, which JSON cannot represent.
+                fn dump_json<W: Write>(&self, _: &mut JsonDumper, writer: &mut W) -> std::io::Result<()> {
+                    if self.is_finite() {
+                        write!(writer, "{}", self)
+                    } else {
+                        writer.write_all(b"null")
+                    }
+                }
+            }
+        )*
+    };
+}
+
+dump_json_for_float!(f32, f64);
+
+impl DumpJson for str {
+    fn dump_json<W: Write>(&self, dumper: &mut JsonDumper, writer: &mut W) -> std::io::Result<()> {
+        dumper.write_str(self, writer)
+    }
+}
+
+impl DumpJson for String {
+    fn dump_json<W: Write>(&self, dumper: &mut JsonDumper, writer: &mut W) -> std::io::Result<()> {
+        dumper.write_str(self, writer)
+    }
+}
+
+impl<'a> DumpJson for Cow<'a, str> {
+    fn dump_json<W: Write>(&self, dumper: &mut JsonDumper, writer: &mut W) -> std::io::Result<()> {
+        dumper.write_str(self, writer)
+    }
+}
+
+impl DumpJson for bool {
+    fn dump_json<W: Write>(&self, _: &mut JsonDumper, writer: &mut W) -> std::io::Result<()> {
+        if *self {
+            writer.write_all(b"true")
+        } else {
+            writer.write_all(b"false")
         }
     }
 }
+
+impl<T: DumpJson> DumpJson for Vec<T> {
+    fn dump_json<W: Write>(&self, dumper: &mut JsonDumper, writer: &mut W) -> std::io::Result<()> {
+        dumper.begin_array(writer)?;
+        for (idx, val) in self.iter().enumerate() {
+            dumper.array_item(idx, writer)?;
+            val.dump_json(dumper, writer)?;
+        }
+        dumper.end_array(self.len(), writer)
+    }
+}
+
+impl<T: DumpJson> DumpJson for Option<T> {
+    fn dump_json<W: Write>(&self, dumper: &mut JsonDumper, writer: &mut W) -> std::io::Result<()> {
+        match self {
+            None => writer.write_all(b"null"),
+            Some(val) => val.dump_json(dumper, writer),
+        }
+    }
+}
+
+impl<T: DumpJson + ?Sized> DumpJson for Box<T> {
+    fn dump_json<W: Write>(&self, dumper: &mut JsonDumper, writer: &mut W) -> std::io::Result<()> {
+        T::dump_json(self, dumper, writer)
+    }
+}
+
+impl<T: DumpJson + ?Sized> DumpJson for &T {
+    fn dump_json<W: Write>(&self, dumper: &mut JsonDumper, writer: &mut W) -> std::io::Result<()> {
+        T::dump_json(self, dumper, writer)
+    }
+}
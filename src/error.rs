@@ -1,3 +1,7 @@
+use crate::json::JsonValue;
+use crate::map::Map;
+use crate::number::Number;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 pub struct Error {
@@ -7,11 +11,189 @@ pub struct Error {
 pub enum ErrorType {
     ParseError,
     ValidateError,
+    IoError,
+}
+
+/// Kinds of syntax errors that occur while loading JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The input ended in the middle of a value.
+    UnexpectedEof,
+    /// A value is expected but another character is found.
+    ExpectedValue,
+    /// A `:` is expected after a key of an object.
+    ExpectedColon,
+    /// A `,` or a closing bracket is expected after a value in an array or an object.
+    ExpectedCommaOrEnd,
+    /// A key of an object is not a string.
+    KeyMustBeString,
+    /// A `,` is followed by a closing bracket.
+    TrailingComma,
+    /// A number does not conform to the grammar.
+    InvalidNumber,
+    /// A literal is not one of `true`, `false` and `null`.
+    InvalidLiteral,
+    /// An escape sequence in a string is invalid.
+    InvalidEscape,
+    /// An escaped surrogate in a string is not paired.
+    LoneSurrogate,
+    /// A string contains a bare control character.
+    ControlCharacter,
+    /// A string contains a byte sequence that is invalid as UTF-8.
+    InvalidUtf8,
+    /// An object has the same key twice.
+    DuplicateKey,
+    /// Characters remain after a value.
+    TrailingData,
+    /// Arrays and objects are nested deeper than the limit.
+    DepthLimitExceeded,
+    /// The input is larger than the limit.
+    InputTooLarge,
+    /// A string is longer than the limit.
+    StringTooLong,
+    /// A number literal is longer than the limit.
+    NumberTooLong,
+    /// An array has more items than the limit.
+    TooManyItems,
+    /// An object has more members than the limit.
+    TooManyMembers,
+    /// A record separator of RFC 7464 is expected before a value.
+    ExpectedRecordSeparator,
+}
+
+impl std::fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::UnexpectedEof => write!(f, "unexpected end of input"),
+            ParseErrorKind::ExpectedValue => write!(f, "expect value"),
+            ParseErrorKind::ExpectedColon => write!(f, "expect `:`"),
+            ParseErrorKind::ExpectedCommaOrEnd => write!(f, "expect `,` or closing bracket"),
+            ParseErrorKind::KeyMustBeString => write!(f, "key must be a string"),
+            ParseErrorKind::TrailingComma => write!(f, "trailing comma"),
+            ParseErrorKind::InvalidNumber => write!(f, "invalid number"),
+            ParseErrorKind::InvalidLiteral => write!(f, "invalid literal"),
+            ParseErrorKind::InvalidEscape => write!(f, "invalid escape"),
+            ParseErrorKind::LoneSurrogate => write!(f, "lone surrogate"),
+            ParseErrorKind::ControlCharacter => write!(f, "invalid control character"),
+            ParseErrorKind::InvalidUtf8 => write!(f, "invalid utf-8 character"),
+            ParseErrorKind::DuplicateKey => write!(f, "exists same key"),
+            ParseErrorKind::TrailingData => write!(f, "extra data"),
+            ParseErrorKind::DepthLimitExceeded => write!(f, "exceeds the maximum depth"),
+            ParseErrorKind::InputTooLarge => write!(f, "input is too large"),
+            ParseErrorKind::StringTooLong => write!(f, "string is too long"),
+            ParseErrorKind::NumberTooLong => write!(f, "number is too long"),
+            ParseErrorKind::TooManyItems => write!(f, "too many items in array"),
+            ParseErrorKind::TooManyMembers => write!(f, "too many members in object"),
+            ParseErrorKind::ExpectedRecordSeparator => write!(f, "expect record separator"),
+        }
+    }
 }
 
 struct ErrorImpl {
     message: Box<str>,
     err_type: ErrorType,
+    kind: Option<ParseErrorKind>,
+    location: Option<Location>,
+    line: Option<usize>,
+    errors: Vec<FieldError>,
+}
+
+/// An item of the path to a value in a model.
+pub enum LocItem {
+    /// A key of an object.
+    Key(String),
+    /// An index of an array.
+    Index(usize),
+}
+
+/// A failure of the validation for a value in a model.
+pub struct FieldError {
+    loc: Vec<LocItem>,
+    constraint: Box<str>,
+    message: Box<str>,
+    value: Option<JsonValue>,
+    limit: Option<JsonValue>,
+}
+
+impl FieldError {
+    /// Returns the path from the root of a model to the value.
+    pub fn loc(&self) -> &[LocItem] {
+        &self.loc
+    }
+    /// Returns the path from the root of a model to the value as a string, e.g. `items[3].price`.
+    pub fn loc_path(&self) -> String {
+        let mut path = String::new();
+        for item in self.loc.iter() {
+            match item {
+                LocItem::Key(key) => {
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(key);
+                }
+                LocItem::Index(index) => {
+                    path.push('[');
+                    path.push_str(index.to_string().as_str());
+                    path.push(']');
+                }
+            }
+        }
+        path
+    }
+    /// Returns the name of the failed constraint.
+    ///
+    /// This is the name of a term in `#[field]`, e.g. `ge` or `min_length`,
+    /// or one of `missing`, `type`, `enum` and `value`.
+    pub fn constraint(&self) -> &str {
+        &self.constraint
+    }
+    /// Returns the message of the failure.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+    /// Returns the value that failed the validation, if available.
+    pub fn value(&self) -> Option<&JsonValue> {
+        self.value.as_ref()
+    }
+    /// Returns the limit of the constraint, e.g. `1` for `ge = 1`, if available.
+    pub fn limit(&self) -> Option<&JsonValue> {
+        self.limit.as_ref()
+    }
+    fn to_json_value(&self) -> JsonValue {
+        let loc = self
+            .loc
+            .iter()
+            .map(|item| match item {
+                LocItem::Key(key) => JsonValue::String(key.clone()),
+                LocItem::Index(index) => JsonValue::Number(Number::from(*index)),
+            })
+            .collect();
+        let mut dict = Map::from([
+            ("loc".to_string(), JsonValue::Array(loc)),
+            (
+                "type".to_string(),
+                JsonValue::String(self.constraint.to_string()),
+            ),
+            (
+                "msg".to_string(),
+                JsonValue::String(self.message.to_string()),
+            ),
+        ]);
+        if let Some(limit) = &self.limit {
+            dict.insert(
+                "ctx".to_string(),
+                JsonValue::Object(Map::from([(self.constraint.to_string(), limit.clone())])),
+            );
+        }
+        JsonValue::Object(dict)
+    }
+}
+
+struct Location {
+    offset: usize,
+    line: usize,
+    column: usize,
+    snippet: Box<str>,
 }
 
 impl Error {
@@ -20,29 +202,272 @@ impl Error {
             err: Box::new(ErrorImpl {
                 message: message.into(),
                 err_type,
+                kind: None,
+                location: None,
+                line: None,
+                errors: Vec::new(),
             }),
         }
     }
     pub fn parse_err(message: &str) -> Self {
         Error::new(message, ErrorType::ParseError)
     }
+    pub fn parse_err_at(
+        kind: ParseErrorKind,
+        offset: usize,
+        line: usize,
+        column: usize,
+        snippet: &str,
+    ) -> Self {
+        let mut err = Error::new(kind.to_string().as_str(), ErrorType::ParseError);
+        err.err.kind = Some(kind);
+        err.err.location = Some(Location {
+            offset,
+            line,
+            column,
+            snippet: snippet.into(),
+        });
+        err
+    }
     pub fn validate_err(message: &str) -> Self {
-        Error::new(message, ErrorType::ValidateError)
+        Error::constraint_err("value", message, None)
+    }
+    /// Makes an error for a value that failed the given constraint.
+    pub fn constraint_err(constraint: &str, message: &str, value: Option<JsonValue>) -> Self {
+        let mut err = Error::new(message, ErrorType::ValidateError);
+        err.err.errors.push(FieldError {
+            loc: Vec::new(),
+            constraint: constraint.into(),
+            message: message.into(),
+            value,
+            limit: None,
+        });
+        err
+    }
+    /// Sets the limit of the constraint to each failure.
+    pub fn with_limit(mut self, limit: JsonValue) -> Self {
+        for err in self.err.errors.iter_mut() {
+            err.limit = Some(limit.clone());
+        }
+        self
+    }
+    /// Merges errors for each value into one error.
+    ///
+    /// An error which has no failure, e.g. made by `Error::new`, is kept as a failure of `value`.
+    pub fn merge(errors: Vec<Error>) -> Self {
+        let mut err = Error::new("", ErrorType::ValidateError);
+        for other in errors {
+            err.err.errors.extend(other.into_field_errors());
+        }
+        err
+    }
+    /// Prepends a key of an object to the path of each failure.
+    pub fn at_key(self, key: &str) -> Self {
+        self.at(LocItem::Key(key.to_string()))
+    }
+    /// Prepends an index of an array to the path of each failure.
+    pub fn at_index(self, index: usize) -> Self {
+        self.at(LocItem::Index(index))
+    }
+    fn at(mut self, item: LocItem) -> Self {
+        if self.err.errors.is_empty() && matches!(self.err.err_type, ErrorType::ValidateError) {
+            self.err.errors = self.field_errors();
+        }
+        if let Some((last, rest)) = self.err.errors.split_last_mut() {
+            for err in rest {
+                err.loc.insert(0, item.clone());
+            }
+            last.loc.insert(0, item);
+        }
+        self
+    }
+    /// Returns the failures, or this error as a failure of `value` if it has none.
+    fn into_field_errors(self) -> Vec<FieldError> {
+        if self.err.errors.is_empty() {
+            self.field_errors()
+        } else {
+            self.err.errors
+        }
+    }
+    fn field_errors(&self) -> Vec<FieldError> {
+        Vec::from([FieldError {
+            loc: Vec::new(),
+            constraint: "value".into(),
+            message: self.err.message.clone(),
+            value: None,
+            limit: None,
+        }])
+    }
+    /// Moves this error to the given line of a larger input, where the line starts at the given offset.
+    ///
+    /// This is for an error of a value loaded from one line, e.g. in JSON Lines.
+    /// Lines start from 1, and the line 0 is treated as 1.
+    pub fn at_line(mut self, line: usize, offset: usize) -> Self {
+        match self.err.location.as_mut() {
+            Some(loc) => {
+                loc.line += line.saturating_sub(1);
+                loc.offset += offset;
+            }
+            None => self.err.line = Some(line),
+        }
+        self
+    }
+    pub fn io_err(err: &std::io::Error) -> Self {
+        Error::new(err.to_string().as_str(), ErrorType::IoError)
     }
     pub fn err_type(&self) -> ErrorType {
         self.err.err_type
     }
+    /// Returns the kind of the syntax error, if this error occurred while loading JSON.
+    pub fn parse_error_kind(&self) -> Option<ParseErrorKind> {
+        self.err.kind
+    }
+    /// Returns the byte offset in the input where the error occurred.
+    pub fn offset(&self) -> Option<usize> {
+        self.err.location.as_ref().map(|loc| loc.offset)
+    }
+    /// Returns the line, starting from 1, where the error occurred.
+    pub fn line(&self) -> Option<usize> {
+        self.err
+            .location
+            .as_ref()
+            .map(|loc| loc.line)
+            .or(self.err.line)
+    }
+    /// Returns the column in bytes, starting from 1, where the error occurred.
+    pub fn column(&self) -> Option<usize> {
+        self.err.location.as_ref().map(|loc| loc.column)
+    }
+    /// Returns a part of the input around the error.
+    pub fn snippet(&self) -> Option<&str> {
+        self.err.location.as_ref().map(|loc| loc.snippet.as_ref())
+    }
+    /// Returns all failures of the validation.
+    pub fn errors(&self) -> &[FieldError] {
+        &self.err.errors
+    }
+    /// Returns the details of this error as a list of objects which have `loc`, `type`, `msg` and `ctx`.
+    ///
+    /// `ctx` has the limit of the constraint keyed by its name, and is omitted if there is no limit.
+    /// An error which is not caused by the validation is reported as one object
+    /// whose `type` is `parse_error` or `io_error`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dade::{json_dump, model, Model};
+    ///
+    /// #[model]
+    /// struct Item {
+    ///     #[field(ge = 1)]
+    ///     id: u64,
+    /// }
+    ///
+    /// let err = Item::parse("{\"id\": 0}").err().unwrap();
+    /// assert_eq!(
+    ///     json_dump(&err.details(), false),
+    ///     "[{\"loc\":[\"id\"],\"type\":\"ge\",\"msg\":\"id must be equal to or greater than 1\",\"ctx\":{\"ge\":1}}]"
+    /// );
+    /// ```
+    pub fn details(&self) -> JsonValue {
+        if !self.err.errors.is_empty() {
+            return JsonValue::Array(
+                self.err
+                    .errors
+                    .iter()
+                    .map(|err| err.to_json_value())
+                    .collect(),
+            );
+        }
+        let err_type = match self.err.err_type {
+            ErrorType::ParseError => "parse_error",
+            ErrorType::ValidateError => "value",
+            ErrorType::IoError => "io_error",
+        };
+        let mut dict = Map::from([
+            ("loc".to_string(), JsonValue::Array(Vec::new())),
+            ("type".to_string(), JsonValue::String(err_type.to_string())),
+            (
+                "msg".to_string(),
+                JsonValue::String(self.err.message.to_string()),
+            ),
+        ]);
+        if let Some(loc) = &self.err.location {
+            dict.insert(
+                "ctx".to_string(),
+                JsonValue::Object(Map::from([
+                    (
+                        "offset".to_string(),
+                        JsonValue::Number(Number::from(loc.offset)),
+                    ),
+                    (
+                        "line".to_string(),
+                        JsonValue::Number(Number::from(loc.line)),
+                    ),
+                    (
+                        "column".to_string(),
+                        JsonValue::Number(Number::from(loc.column)),
+                    ),
+                ])),
+            );
+        }
+        JsonValue::Array(Vec::from([JsonValue::Object(dict)]))
+    }
+}
+
+impl Clone for LocItem {
+    fn clone(&self) -> Self {
+        match self {
+            LocItem::Key(key) => LocItem::Key(key.clone()),
+            LocItem::Index(index) => LocItem::Index(*index),
+        }
+    }
+}
+
+impl std::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.loc.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.loc_path(), self.message)
+        }
+    }
 }
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}: {}", self.err_type(), self.err.message)
+        if !self.err.errors.is_empty() {
+            write!(f, "{}", self.err_type())?;
+            if let Some(line) = self.err.line {
+                write!(f, " at line {}", line)?;
+            }
+            write!(f, ": ")?;
+            for (i, err) in self.err.errors.iter().enumerate() {
+                if i > 0 {
+                    write!(f, "; ")?;
+                }
+                write!(f, "{}", err)?;
+            }
+            return Ok(());
+        }
+        write!(f, "{}: {}", self.err_type(), self.err.message)?;
+        if let (None, Some(line)) = (&self.err.location, self.err.line) {
+            write!(f, " at line {}", line)?;
+        }
+        if let Some(loc) = &self.err.location {
+            write!(
+                f,
+                " at line {} column {} (offset {}) near `{}`",
+                loc.line, loc.column, loc.offset, loc.snippet
+            )?;
+        }
+        Ok(())
     }
 }
 
 impl std::fmt::Debug for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}: {}", self.err_type(), self.err.message)
+        std::fmt::Display::fmt(self, f)
     }
 }
 
@@ -51,6 +476,7 @@ impl std::fmt::Display for ErrorType {
         match self {
             ErrorType::ParseError => write!(f, "Parse Error"),
             ErrorType::ValidateError => write!(f, "Validate Error"),
+            ErrorType::IoError => write!(f, "IO Error"),
         }
     }
 }
@@ -62,6 +488,7 @@ impl std::fmt::Debug for ErrorType {
         match self {
             ErrorType::ParseError => write!(f, "Parse Error"),
             ErrorType::ValidateError => write!(f, "Validate Error"),
+            ErrorType::IoError => write!(f, "IO Error"),
         }
     }
 }
@@ -71,6 +498,7 @@ impl Clone for ErrorType {
         match self {
             ErrorType::ParseError => ErrorType::ParseError,
             ErrorType::ValidateError => ErrorType::ValidateError,
+            ErrorType::IoError => ErrorType::IoError,
         }
     }
 }
@@ -0,0 +1,183 @@
+use crate::error::Result;
+use crate::json::JsonValue;
+use crate::load::JsonLoader;
+use crate::number::Number;
+use crate::stream::Stream;
+
+/// Events that occur while reading JSON.
+pub enum JsonEvent {
+    /// The start of an object, `{`.
+    StartObject,
+    /// A key of a member in an object.
+    Key(String),
+    /// The end of an object, `}`.
+    EndObject,
+    /// The start of an array, `[`.
+    StartArray,
+    /// The end of an array, `]`.
+    EndArray,
+    /// A string value.
+    String(String),
+    /// A number value.
+    Number(Number),
+    /// A boolean value.
+    Bool(bool),
+    /// A null value.
+    Null,
+}
+
+impl Clone for JsonEvent {
+    fn clone(&self) -> Self {
+        match self {
+            JsonEvent::StartObject => JsonEvent::StartObject,
+            JsonEvent::Key(key) => JsonEvent::Key(key.clone()),
+            JsonEvent::EndObject => JsonEvent::EndObject,
+            JsonEvent::StartArray => JsonEvent::StartArray,
+            JsonEvent::EndArray => JsonEvent::EndArray,
+            JsonEvent::String(val) => JsonEvent::String(val.clone()),
+            JsonEvent::Number(val) => JsonEvent::Number(val.clone()),
+            JsonEvent::Bool(val) => JsonEvent::Bool(*val),
+            JsonEvent::Null => JsonEvent::Null,
+        }
+    }
+}
+
+struct Container {
+    is_object: bool,
+    len: usize,
+}
+
+/// An iterator that reads JSON as events, without building the whole value.
+///
+/// Each item is the byte offset where the event starts and the event itself.
+/// The limits of the given `JsonLoader` are applied, and the iteration ends after an error.
+/// Duplicate keys are not checked, since that needs to hold all keys of an object.
+///
+/// For example,
+///
+/// ```rust
+/// use dade::{JsonEvent, JsonEventReader, JsonLoader};
+///
+/// let mut keys = Vec::new();
+/// for item in JsonEventReader::new(JsonLoader::from("{\"a\": [1, {\"b\": null}]}")) {
+///     if let (offset, JsonEvent::Key(key)) = item.unwrap() {
+///         keys.push((offset, key));
+///     }
+/// }
+/// assert_eq!(keys, [(1, "a".to_string()), (11, "b".to_string())]);
+/// ```
+pub struct JsonEventReader<S: Stream<u8>> {
+    loader: JsonLoader<u8, S>,
+    stack: Vec<Container>,
+    after_key: bool,
+    started: bool,
+    done: bool,
+}
+
+impl<S: Stream<u8>> JsonEventReader<S> {
+    pub fn new(loader: JsonLoader<u8, S>) -> Self {
+        Self {
+            loader,
+            stack: Vec::new(),
+            after_key: false,
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Returns a mutable reference to the underlying stream.
+    ///
+    /// With `ReadBytes`, this is needed to take an I/O error after the iteration fails.
+    pub fn stream_mut(&mut self) -> &mut S {
+        self.loader.stream_mut()
+    }
+
+    /// Returns the current depth of nested arrays and objects.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    fn read_value(&mut self) -> Result<(usize, JsonEvent)> {
+        if self.loader.begin_object()? {
+            self.stack.push(Container {
+                is_object: true,
+                len: 0,
+            });
+            return Ok((self.loader.position() - 1, JsonEvent::StartObject));
+        }
+        if self.loader.begin_array()? {
+            self.stack.push(Container {
+                is_object: false,
+                len: 0,
+            });
+            return Ok((self.loader.position() - 1, JsonEvent::StartArray));
+        }
+        let offset = self.loader.position();
+        let event = match self.loader.load_value()? {
+            JsonValue::Null => JsonEvent::Null,
+            JsonValue::Bool(val) => JsonEvent::Bool(val),
+            JsonValue::Number(val) => JsonEvent::Number(val),
+            JsonValue::String(val) => JsonEvent::String(val),
+            JsonValue::Array(_) | JsonValue::Object(_) => unreachable!(),
+        };
+        Ok((offset, event))
+    }
+
+    fn read_event(&mut self) -> Result<Option<(usize, JsonEvent)>> {
+        let container = match self.stack.last_mut() {
+            Some(container) => container,
+            None if self.started => {
+                self.loader.finish()?;
+                return Ok(None);
+            }
+            None => {
+                self.started = true;
+                return self.read_value().map(Some);
+            }
+        };
+        if container.is_object {
+            if self.after_key {
+                self.after_key = false;
+                return self.read_value().map(Some);
+            }
+            match self.loader.object_key(container.len)? {
+                Some((key, offset)) => {
+                    container.len += 1;
+                    self.after_key = true;
+                    Ok(Some((offset, JsonEvent::Key(key))))
+                }
+                None => {
+                    self.stack.pop();
+                    Ok(Some((self.loader.position() - 1, JsonEvent::EndObject)))
+                }
+            }
+        } else if self.loader.array_item(container.len)? {
+            container.len += 1;
+            self.read_value().map(Some)
+        } else {
+            self.stack.pop();
+            Ok(Some((self.loader.position() - 1, JsonEvent::EndArray)))
+        }
+    }
+}
+
+impl<S: Stream<u8>> Iterator for JsonEventReader<S> {
+    type Item = Result<(usize, JsonEvent)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.read_event() {
+            Ok(Some(item)) => Some(Ok(item)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
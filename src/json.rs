@@ -1,33 +1,10 @@
-use std::collections::BTreeMap;
-use std::num::{ParseFloatError, ParseIntError};
-use std::str::FromStr;
+use std::borrow::Cow;
 
 use crate::dump::JsonDumper;
 use crate::error::{Error, Result};
-
-pub struct Number {
-    value: String,
-}
-
-impl Number {
-    pub fn new(value: String) -> Self {
-        Self { value }
-    }
-    pub fn parse<F: FromStr>(&self) -> std::result::Result<F, F::Err> {
-        self.value.parse()
-    }
-    pub fn from<T: std::string::ToString>(val: T) -> Self {
-        Self {
-            value: val.to_string(),
-        }
-    }
-}
-
-impl ToString for Number {
-    fn to_string(&self) -> String {
-        self.value.to_string()
-    }
-}
+use crate::load::JsonLoader;
+use crate::map::Map;
+use crate::number::{FromLiteral, Number};
 
 /// Types for conforms JSON.
 pub enum JsonValue {
@@ -42,74 +19,439 @@ pub enum JsonValue {
     /// Correspond for array in JSON.
     Array(Vec<JsonValue>),
     /// Correspond for object in JSON.
-    Object(BTreeMap<String, JsonValue>),
+    Object(Map<String, JsonValue>),
 }
 
-impl ToString for JsonValue {
-    fn to_string(&self) -> String {
-        JsonDumper::new(false).dump(self)
+impl JsonValue {
+    /// Sorts members of objects by keys recursively, for a canonical output.
+    ///
+    /// An object keeps the order of keys as loaded or inserted, and this is to compare outputs
+    /// regardless of the order.
+    ///
+    /// For example,
+    ///
+    /// ```rust
+    /// use dade::{json_dump, json_load};
+    ///
+    /// let mut value = json_load("{\"b\": {\"d\": 1, \"c\": 2}, \"a\": []}").unwrap();
+    /// value.sort_keys();
+    /// assert_eq!(json_dump(&value, false), "{\"a\":[],\"b\":{\"c\":2,\"d\":1}}");
+    /// ```
+    pub fn sort_keys(&mut self) {
+        match self {
+            JsonValue::Array(vec) => vec.iter_mut().for_each(JsonValue::sort_keys),
+            JsonValue::Object(dict) => {
+                dict.sort_keys();
+                dict.values_mut().for_each(JsonValue::sort_keys);
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns true if it is null.
+    pub fn is_null(&self) -> bool {
+        matches!(self, JsonValue::Null)
+    }
+
+    /// Returns the value if it is a boolean.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(val) => Some(*val),
+            _ => None,
+        }
+    }
+
+    /// Returns the number if it is a number.
+    pub fn as_number(&self) -> Option<&Number> {
+        match self {
+            JsonValue::Number(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns the string if it is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns the items if it is an array.
+    pub fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns the mutable items if it is an array.
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns the members if it is an object.
+    pub fn as_object(&self) -> Option<&Map<String, JsonValue>> {
+        match self {
+            JsonValue::Object(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns the mutable members if it is an object.
+    pub fn as_object_mut(&mut self) -> Option<&mut Map<String, JsonValue>> {
+        match self {
+            JsonValue::Object(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns a member of an object by a key, or an item of an array by an index.
+    ///
+    /// For example,
+    ///
+    /// ```rust
+    /// use dade::{json_load, JsonValue};
+    ///
+    /// let value = json_load("{\"a\": [1, \"b\"]}").unwrap();
+    /// assert_eq!(value.get("a").and_then(|a| a.get(1)), Some(&JsonValue::from("b")));
+    /// assert_eq!(value.get(0), None);
+    /// // Indexing returns null for a missing value instead.
+    /// assert!(value["a"][2].is_null());
+    /// ```
+    pub fn get<I: JsonIndex>(&self, index: I) -> Option<&JsonValue> {
+        index.index_into(self)
+    }
+
+    /// Returns a mutable member of an object by a key, or an item of an array by an index.
+    pub fn get_mut<I: JsonIndex>(&mut self, index: I) -> Option<&mut JsonValue> {
+        index.index_into_mut(self)
+    }
+
+    /// Takes the value out, leaving null in its place.
+    pub fn take(&mut self) -> JsonValue {
+        std::mem::replace(self, JsonValue::Null)
     }
 }
 
-/// A trait defines as the format to convert data to an instance.
-pub trait FromJsonValue: Sized {
-    fn from_json_value(value: &JsonValue) -> Result<Self>;
+/// A trait defines as the format to look up a value in `JsonValue`,
+/// which is implemented for a key of an object and an index of an array.
+pub trait JsonIndex {
+    fn index_into<'v>(&self, value: &'v JsonValue) -> Option<&'v JsonValue>;
+    fn index_into_mut<'v>(&self, value: &'v mut JsonValue) -> Option<&'v mut JsonValue>;
 }
 
-impl FromJsonValue for () {
-    fn from_json_value(value: &JsonValue) -> Result<Self> {
-        match value {
-            JsonValue::Null => Ok(()),
-            _ => Err(Error::validate_err("expect `JsonValue::Null`")),
+impl JsonIndex for usize {
+    fn index_into<'v>(&self, value: &'v JsonValue) -> Option<&'v JsonValue> {
+        value.as_array()?.get(*self)
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut JsonValue) -> Option<&'v mut JsonValue> {
+        value.as_array_mut()?.get_mut(*self)
+    }
+}
+
+impl JsonIndex for str {
+    fn index_into<'v>(&self, value: &'v JsonValue) -> Option<&'v JsonValue> {
+        value.as_object()?.get(self)
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut JsonValue) -> Option<&'v mut JsonValue> {
+        value.as_object_mut()?.get_mut(self)
+    }
+}
+
+impl JsonIndex for String {
+    fn index_into<'v>(&self, value: &'v JsonValue) -> Option<&'v JsonValue> {
+        self.as_str().index_into(value)
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut JsonValue) -> Option<&'v mut JsonValue> {
+        self.as_str().index_into_mut(value)
+    }
+}
+
+impl<T: JsonIndex + ?Sized> JsonIndex for &T {
+    fn index_into<'v>(&self, value: &'v JsonValue) -> Option<&'v JsonValue> {
+        (**self).index_into(value)
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut JsonValue) -> Option<&'v mut JsonValue> {
+        (**self).index_into_mut(value)
+    }
+}
+
+static NULL: JsonValue = JsonValue::Null;
+
+impl<I: JsonIndex> std::ops::Index<I> for JsonValue {
+    type Output = JsonValue;
+
+    /// Returns the value by a key or an index, which is null if it does not exist.
+    fn index(&self, index: I) -> &JsonValue {
+        index.index_into(self).unwrap_or(&NULL)
+    }
+}
+
+impl<I: JsonIndex> std::ops::IndexMut<I> for JsonValue {
+    /// Returns the mutable value by a key or an index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value does not exist.
+    fn index_mut(&mut self, index: I) -> &mut JsonValue {
+        index
+            .index_into_mut(self)
+            .expect("no entry found for index")
+    }
+}
+
+impl Clone for JsonValue {
+    fn clone(&self) -> Self {
+        match self {
+            JsonValue::Null => JsonValue::Null,
+            JsonValue::Bool(val) => JsonValue::Bool(*val),
+            JsonValue::Number(val) => JsonValue::Number(val.clone()),
+            JsonValue::String(val) => JsonValue::String(val.clone()),
+            JsonValue::Array(val) => JsonValue::Array(val.clone()),
+            JsonValue::Object(val) => JsonValue::Object(val.clone()),
+        }
+    }
+}
+
+impl std::fmt::Debug for JsonValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonValue::Null => f.write_str("Null"),
+            JsonValue::Bool(val) => f.debug_tuple("Bool").field(val).finish(),
+            JsonValue::Number(val) => std::fmt::Debug::fmt(val, f),
+            JsonValue::String(val) => f.debug_tuple("String").field(val).finish(),
+            JsonValue::Array(val) => f.debug_tuple("Array").field(val).finish(),
+            JsonValue::Object(val) => f.debug_tuple("Object").field(val).finish(),
         }
     }
 }
 
-macro_rules! from_json_value_for_int {
+/// Values are equal by the exact values of numbers, and regardless of the order of object keys.
+impl PartialEq for JsonValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (JsonValue::Null, JsonValue::Null) => true,
+            (JsonValue::Bool(a), JsonValue::Bool(b)) => a == b,
+            (JsonValue::Number(a), JsonValue::Number(b)) => a == b,
+            (JsonValue::String(a), JsonValue::String(b)) => a == b,
+            (JsonValue::Array(a), JsonValue::Array(b)) => a == b,
+            (JsonValue::Object(a), JsonValue::Object(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for JsonValue {}
+
+impl std::fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&JsonDumper::new(false).dump(self))
+    }
+}
+
+impl std::str::FromStr for JsonValue {
+    type Err = Error;
+
+    fn from_str(json: &str) -> Result<Self> {
+        JsonLoader::from(json).load()
+    }
+}
+
+impl From<()> for JsonValue {
+    fn from(_: ()) -> Self {
+        JsonValue::Null
+    }
+}
+
+impl From<bool> for JsonValue {
+    fn from(val: bool) -> Self {
+        JsonValue::Bool(val)
+    }
+}
+
+impl From<Number> for JsonValue {
+    fn from(val: Number) -> Self {
+        JsonValue::Number(val)
+    }
+}
+
+macro_rules! json_value_from_int {
     ( $( $i:ident ),* ) => {
         $(
-            impl FromJsonValue for $i {
-                fn from_json_value(value: &JsonValue) -> Result<Self> {
-                    match value {
-                        JsonValue::Number(num) => num
-                            .value
-                            .parse()
-                            .map_err(|err: ParseIntError| Error::validate_err(err.to_string().as_str())),
-                        _ => Err(Error::validate_err("expect `JsonValue::Number`")),
-                    }
+            impl From<$i> for JsonValue {
+                fn from(val: $i) -> Self {
+                    JsonValue::Number(Number::from(val))
                 }
             }
         )*
     };
 }
 
-from_json_value_for_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+json_value_from_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// A non-finite value is converted to null, as JSON has neither NaN nor infinity.
+impl From<f32> for JsonValue {
+    fn from(val: f32) -> Self {
+        Number::from_f32(val).map_or(JsonValue::Null, JsonValue::Number)
+    }
+}
+
+/// A non-finite value is converted to null, as JSON has neither NaN nor infinity.
+impl From<f64> for JsonValue {
+    fn from(val: f64) -> Self {
+        Number::from_f64(val).map_or(JsonValue::Null, JsonValue::Number)
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(val: String) -> Self {
+        JsonValue::String(val)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(val: &str) -> Self {
+        JsonValue::String(val.to_string())
+    }
+}
+
+impl From<Cow<'_, str>> for JsonValue {
+    fn from(val: Cow<'_, str>) -> Self {
+        JsonValue::String(val.into_owned())
+    }
+}
+
+impl<T: Into<JsonValue>> From<Vec<T>> for JsonValue {
+    fn from(val: Vec<T>) -> Self {
+        JsonValue::Array(val.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Clone + Into<JsonValue>> From<&[T]> for JsonValue {
+    fn from(val: &[T]) -> Self {
+        JsonValue::Array(val.iter().cloned().map(Into::into).collect())
+    }
+}
+
+impl From<Map<String, JsonValue>> for JsonValue {
+    fn from(val: Map<String, JsonValue>) -> Self {
+        JsonValue::Object(val)
+    }
+}
+
+impl<T: Into<JsonValue>> From<Option<T>> for JsonValue {
+    fn from(val: Option<T>) -> Self {
+        val.map_or(JsonValue::Null, Into::into)
+    }
+}
+
+impl<T: Into<JsonValue>> FromIterator<T> for JsonValue {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        JsonValue::Array(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<K: Into<String>, V: Into<JsonValue>> FromIterator<(K, V)> for JsonValue {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        JsonValue::Object(
+            iter.into_iter()
+                .map(|(key, val)| (key.into(), val.into()))
+                .collect(),
+        )
+    }
+}
+
+/// A trait defines as the format to convert data to an instance.
+///
+/// An integer accepts a number whose exact value is an integer in range, e.g. `1.0` or `1e3`.
+pub trait FromJsonValue: Sized {
+    fn from_json_value(value: &JsonValue) -> Result<Self>;
 
-macro_rules! from_json_value_for_float {
+    /// Converts the same as `from_json_value`, except that an integer accepts only a literal
+    /// of an integer, which is used by a model with `#[model(strict)]`.
+    fn from_json_value_strict(value: &JsonValue) -> Result<Self> {
+        Self::from_json_value(value)
+    }
+}
+
+impl FromJsonValue for () {
+    fn from_json_value(value: &JsonValue) -> Result<Self> {
+        match value {
+            JsonValue::Null => Ok(()),
+            _ => Err(Error::constraint_err(
+                "type",
+                "expect `JsonValue::Null`",
+                Some(value.clone()),
+            )),
+        }
+    }
+}
+
+/// Converts a literal of a number by `convert`, or fails if `literal` is None.
+///
+/// `value` makes the value in an error, which is shared by `JsonValue` and `JsonValueRef`.
+pub(crate) fn number_from_literal<T>(
+    literal: Option<&str>,
+    convert: fn(&str) -> std::result::Result<T, String>,
+    value: impl FnOnce() -> JsonValue,
+) -> Result<T> {
+    match literal {
+        Some(literal) => {
+            convert(literal).map_err(|msg| Error::constraint_err("value", &msg, Some(value())))
+        }
+        None => Err(Error::constraint_err(
+            "type",
+            "expect `JsonValue::Number`",
+            Some(value()),
+        )),
+    }
+}
+
+macro_rules! from_json_value_for_number {
     ( $( $i:ident ),* ) => {
         $(
             impl FromJsonValue for $i {
                 fn from_json_value(value: &JsonValue) -> Result<Self> {
-                    match value {
-                        JsonValue::Number(num) => num
-                            .value
-                            .parse()
-                            .map_err(|err: ParseFloatError| Error::validate_err(err.to_string().as_str())),
-                        _ => Err(Error::validate_err("expect `JsonValue::Number`")),
-                    }
+                    number_from_literal(
+                        value.as_number().map(Number::as_str),
+                        $i::from_literal,
+                        || value.clone(),
+                    )
+                }
+
+                fn from_json_value_strict(value: &JsonValue) -> Result<Self> {
+                    number_from_literal(
+                        value.as_number().map(Number::as_str),
+                        $i::from_literal_strict,
+                        || value.clone(),
+                    )
                 }
             }
         )*
     };
 }
 
-from_json_value_for_float!(f32, f64);
+from_json_value_for_number!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
 
 impl FromJsonValue for String {
     fn from_json_value(value: &JsonValue) -> Result<Self> {
         match value {
             JsonValue::String(s) => Ok(s.to_string()),
-            _ => Err(Error::validate_err("expect `JsonValue::String`")),
+            _ => Err(Error::constraint_err(
+                "type",
+                "expect `JsonValue::String`",
+                Some(value.clone()),
+            )),
         }
     }
 }
@@ -118,23 +460,55 @@ impl FromJsonValue for bool {
     fn from_json_value(value: &JsonValue) -> Result<Self> {
         match value {
             JsonValue::Bool(b) => Ok(*b),
-            _ => Err(Error::validate_err("expect `JsonValue::Bool`")),
+            _ => Err(Error::constraint_err(
+                "type",
+                "expect `JsonValue::Bool`",
+                Some(value.clone()),
+            )),
+        }
+    }
+}
+
+/// Converts each item by `convert`, merging the errors of all items,
+/// which is shared by `JsonValue` and `JsonValueRef`.
+pub(crate) fn items_from<V, T>(items: &[V], convert: fn(&V) -> Result<T>) -> Result<Vec<T>> {
+    let mut buffer = Vec::with_capacity(items.len());
+    let mut errors = Vec::new();
+    for (idx, val) in items.iter().enumerate() {
+        match convert(val) {
+            Ok(val) => buffer.push(val),
+            Err(err) => errors.push(err.at_index(idx)),
         }
     }
+    if errors.is_empty() {
+        Ok(buffer)
+    } else {
+        Err(Error::merge(errors))
+    }
+}
+
+/// Converts each item by `convert`, which is either the strict one or not.
+fn vec_from_json_value<T>(
+    value: &JsonValue,
+    convert: fn(&JsonValue) -> Result<T>,
+) -> Result<Vec<T>> {
+    match value {
+        JsonValue::Array(arr) => items_from(arr, convert),
+        _ => Err(Error::constraint_err(
+            "type",
+            "expect `JsonValue::Array`",
+            Some(value.clone()),
+        )),
+    }
 }
 
 impl<T: FromJsonValue> FromJsonValue for Vec<T> {
     fn from_json_value(value: &JsonValue) -> Result<Self> {
-        match value {
-            JsonValue::Array(arr) => {
-                let mut buffer = Vec::with_capacity(arr.len());
-                for val in arr.iter() {
-                    buffer.push(FromJsonValue::from_json_value(val)?);
-                }
-                Ok(buffer)
-            }
-            _ => Err(Error::validate_err("expect `JsonValue::Array`")),
-        }
+        vec_from_json_value(value, T::from_json_value)
+    }
+
+    fn from_json_value_strict(value: &JsonValue) -> Result<Self> {
+        vec_from_json_value(value, T::from_json_value_strict)
     }
 }
 
@@ -145,12 +519,23 @@ impl<T: FromJsonValue> FromJsonValue for Option<T> {
             _ => Ok(Some(FromJsonValue::from_json_value(value)?)),
         }
     }
+
+    fn from_json_value_strict(value: &JsonValue) -> Result<Self> {
+        match value {
+            JsonValue::Null => Ok(None),
+            _ => Ok(Some(FromJsonValue::from_json_value_strict(value)?)),
+        }
+    }
 }
 
 impl<T: FromJsonValue> FromJsonValue for Box<T> {
     fn from_json_value(value: &JsonValue) -> Result<Self> {
         Ok(Box::new(FromJsonValue::from_json_value(value)?))
     }
+
+    fn from_json_value_strict(value: &JsonValue) -> Result<Self> {
+        Ok(Box::new(FromJsonValue::from_json_value_strict(value)?))
+    }
 }
 
 /// A trait defines as the format to get data from the instance.
@@ -158,25 +543,57 @@ pub trait ToJsonValue {
     fn to_json_value(&self) -> JsonValue;
 }
 
+impl ToJsonValue for JsonValue {
+    fn to_json_value(&self) -> JsonValue {
+        self.clone()
+    }
+}
+
+impl ToJsonValue for Number {
+    fn to_json_value(&self) -> JsonValue {
+        JsonValue::Number(self.clone())
+    }
+}
+
 impl ToJsonValue for () {
     fn to_json_value(&self) -> JsonValue {
         JsonValue::Null
     }
 }
 
-macro_rules! to_json_value_for_num {
+macro_rules! to_json_value_for_int {
     ( $( $i:ident ),* ) => {
         $(
             impl ToJsonValue for $i {
                 fn to_json_value(&self) -> JsonValue {
-                    JsonValue::Number(Number::new(self.to_string()))
+                    JsonValue::Number(Number::from(*self))
                 }
             }
         )*
     };
 }
 
-to_json_value_for_num!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+to_json_value_for_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl ToJsonValue for f32 {
+    /// Converts to `JsonValue::Null` if the value is NaN or infinite, which JSON cannot represent.
+    fn to_json_value(&self) -> JsonValue {
+        Number::from_f32(*self).map_or(JsonValue::Null, JsonValue::Number)
+    }
+}
+
+impl ToJsonValue for f64 {
+    /// Converts to `JsonValue::Null` if the value is NaN or infinite, which JSON cannot represent.
+    fn to_json_value(&self) -> JsonValue {
+        Number::from_f64(*self).map_or(JsonValue::Null, JsonValue::Number)
+    }
+}
+
+impl ToJsonValue for str {
+    fn to_json_value(&self) -> JsonValue {
+        JsonValue::String(self.to_string())
+    }
+}
 
 impl ToJsonValue for String {
     fn to_json_value(&self) -> JsonValue {
@@ -184,6 +601,12 @@ impl ToJsonValue for String {
     }
 }
 
+impl<'a> ToJsonValue for Cow<'a, str> {
+    fn to_json_value(&self) -> JsonValue {
+        JsonValue::String(self.to_string())
+    }
+}
+
 impl ToJsonValue for bool {
     fn to_json_value(&self) -> JsonValue {
         JsonValue::Bool(*self)
@@ -214,3 +637,9 @@ impl<T: ToJsonValue> ToJsonValue for Box<T> {
         T::to_json_value(self)
     }
 }
+
+impl<T: ToJsonValue + ?Sized> ToJsonValue for &T {
+    fn to_json_value(&self) -> JsonValue {
+        T::to_json_value(self)
+    }
+}
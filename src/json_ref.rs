@@ -0,0 +1,284 @@
+use std::borrow::Cow;
+use std::io::Write;
+
+use crate::dump::{DumpJson, JsonDumper};
+use crate::error::{Error, Result};
+use crate::json::{items_from, number_from_literal, JsonValue, ToJsonValue};
+use crate::map::Map;
+use crate::number::{FromLiteral, Number};
+
+/// Types for conforms JSON, which borrow from the input.
+///
+/// This is loaded by `JsonLoader::load_borrowed` from a string or bytes.
+/// A string or a key is copied only if it has escapes, and a number is kept as it is in the input.
+///
+/// For example,
+///
+/// ```rust
+/// use std::borrow::Cow;
+/// use dade::{JsonLoader, JsonValueRef};
+///
+/// let json = "[\"abc\", \"a\\nb\", 1.5]";
+/// let value = JsonLoader::from(json).load_borrowed().unwrap();
+/// match value {
+///     JsonValueRef::Array(vec) => {
+///         assert!(matches!(&vec[0], JsonValueRef::String(Cow::Borrowed("abc"))));
+///         assert!(matches!(&vec[1], JsonValueRef::String(Cow::Owned(s)) if s == "a\nb"));
+///         assert!(matches!(&vec[2], JsonValueRef::Number("1.5")));
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+pub enum JsonValueRef<'a> {
+    /// Correspond for null in JSON.
+    Null,
+    /// Correspond for boolean in JSON.
+    Bool(bool),
+    /// Correspond for number in JSON, which is the literal in the input.
+    Number(&'a str),
+    /// Correspond for string in JSON.
+    String(Cow<'a, str>),
+    /// Correspond for array in JSON.
+    Array(Vec<JsonValueRef<'a>>),
+    /// Correspond for object in JSON.
+    Object(Map<Cow<'a, str>, JsonValueRef<'a>>),
+}
+
+impl<'a> JsonValueRef<'a> {
+    /// Converts to `JsonValue`, which no longer borrows from the input.
+    pub fn into_owned(self) -> JsonValue {
+        match self {
+            JsonValueRef::Null => JsonValue::Null,
+            JsonValueRef::Bool(val) => JsonValue::Bool(val),
+            JsonValueRef::Number(val) => JsonValue::Number(Number::new(val.to_string())),
+            JsonValueRef::String(val) => JsonValue::String(val.into_owned()),
+            JsonValueRef::Array(vec) => {
+                JsonValue::Array(vec.into_iter().map(JsonValueRef::into_owned).collect())
+            }
+            JsonValueRef::Object(dict) => JsonValue::Object(
+                dict.into_iter()
+                    .map(|(key, val)| (key.into_owned(), val.into_owned()))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns the literal if this is a number.
+    fn as_literal(&self) -> Option<&'a str> {
+        match self {
+            JsonValueRef::Number(num) => Some(num),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Clone for JsonValueRef<'a> {
+    fn clone(&self) -> Self {
+        match self {
+            JsonValueRef::Null => JsonValueRef::Null,
+            JsonValueRef::Bool(val) => JsonValueRef::Bool(*val),
+            JsonValueRef::Number(val) => JsonValueRef::Number(val),
+            JsonValueRef::String(val) => JsonValueRef::String(val.clone()),
+            JsonValueRef::Array(val) => JsonValueRef::Array(val.clone()),
+            JsonValueRef::Object(val) => JsonValueRef::Object(val.clone()),
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for JsonValueRef<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&JsonDumper::new(false).dump(self))
+    }
+}
+
+impl<'a> ToJsonValue for JsonValueRef<'a> {
+    fn to_json_value(&self) -> JsonValue {
+        self.clone().into_owned()
+    }
+}
+
+impl<'a> DumpJson for JsonValueRef<'a> {
+    fn dump_json<W: Write>(&self, dumper: &mut JsonDumper, writer: &mut W) -> std::io::Result<()> {
+        match self {
+            JsonValueRef::Null => writer.write_all(b"null"),
+            JsonValueRef::Bool(val) => val.dump_json(dumper, writer),
+            JsonValueRef::Number(val) => writer.write_all(val.as_bytes()),
+            JsonValueRef::String(val) => dumper.write_str(val, writer),
+            JsonValueRef::Array(arr) => {
+                dumper.begin_array(writer)?;
+                for (idx, val) in arr.iter().enumerate() {
+                    dumper.array_item(idx, writer)?;
+                    val.dump_json(dumper, writer)?;
+                }
+                dumper.end_array(arr.len(), writer)
+            }
+            JsonValueRef::Object(dict) => {
+                dumper.begin_object(writer)?;
+                for (idx, (key, val)) in dict.iter().enumerate() {
+                    dumper.object_key(idx, key, writer)?;
+                    val.dump_json(dumper, writer)?;
+                }
+                dumper.end_object(dict.len(), writer)
+            }
+        }
+    }
+}
+
+/// A trait defines as the format to convert a borrowed value to an instance,
+/// which may borrow from the input as long as `'a`.
+///
+/// The errors are the same as `FromJsonValue`.
+pub trait FromJsonRef<'a>: Sized {
+    fn from_json_ref(value: &JsonValueRef<'a>) -> Result<Self>;
+
+    /// Converts the same as `from_json_ref`, except that an integer accepts only a literal
+    /// of an integer, which is used by a model with `#[model(strict)]`.
+    fn from_json_ref_strict(value: &JsonValueRef<'a>) -> Result<Self> {
+        Self::from_json_ref(value)
+    }
+}
+
+impl<'a> FromJsonRef<'a> for () {
+    fn from_json_ref(value: &JsonValueRef<'a>) -> Result<Self> {
+        match value {
+            JsonValueRef::Null => Ok(()),
+            _ => Err(Error::constraint_err(
+                "type",
+                "expect `JsonValue::Null`",
+                Some(value.to_json_value()),
+            )),
+        }
+    }
+}
+
+macro_rules! from_json_ref_for_number {
+    ( $( $i:ident ),* ) => {
+        $(
+            impl<'a> FromJsonRef<'a> for $i {
+                fn from_json_ref(value: &JsonValueRef<'a>) -> Result<Self> {
+                    number_from_literal(value.as_literal(), $i::from_literal, || {
+                        value.to_json_value()
+                    })
+                }
+
+                fn from_json_ref_strict(value: &JsonValueRef<'a>) -> Result<Self> {
+                    number_from_literal(value.as_literal(), $i::from_literal_strict, || {
+                        value.to_json_value()
+                    })
+                }
+            }
+        )*
+    };
+}
+
+from_json_ref_for_number!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl<'a> FromJsonRef<'a> for String {
+    fn from_json_ref(value: &JsonValueRef<'a>) -> Result<Self> {
+        match value {
+            JsonValueRef::String(s) => Ok(s.to_string()),
+            _ => Err(Error::constraint_err(
+                "type",
+                "expect `JsonValue::String`",
+                Some(value.to_json_value()),
+            )),
+        }
+    }
+}
+
+impl<'de: 'a, 'a> FromJsonRef<'de> for &'a str {
+    fn from_json_ref(value: &JsonValueRef<'de>) -> Result<Self> {
+        match value {
+            JsonValueRef::String(Cow::Borrowed(s)) => Ok(s),
+            // A string with escapes is not in the input as it is.
+            JsonValueRef::String(Cow::Owned(_)) => Err(Error::constraint_err(
+                "type",
+                "expect a string without escapes to borrow, use `Cow<str>` instead",
+                Some(value.to_json_value()),
+            )),
+            _ => Err(Error::constraint_err(
+                "type",
+                "expect `JsonValue::String`",
+                Some(value.to_json_value()),
+            )),
+        }
+    }
+}
+
+impl<'de: 'a, 'a> FromJsonRef<'de> for Cow<'a, str> {
+    fn from_json_ref(value: &JsonValueRef<'de>) -> Result<Self> {
+        match value {
+            JsonValueRef::String(Cow::Borrowed(s)) => Ok(Cow::Borrowed(s)),
+            JsonValueRef::String(Cow::Owned(s)) => Ok(Cow::Owned(s.clone())),
+            _ => Err(Error::constraint_err(
+                "type",
+                "expect `JsonValue::String`",
+                Some(value.to_json_value()),
+            )),
+        }
+    }
+}
+
+impl<'a> FromJsonRef<'a> for bool {
+    fn from_json_ref(value: &JsonValueRef<'a>) -> Result<Self> {
+        match value {
+            JsonValueRef::Bool(b) => Ok(*b),
+            _ => Err(Error::constraint_err(
+                "type",
+                "expect `JsonValue::Bool`",
+                Some(value.to_json_value()),
+            )),
+        }
+    }
+}
+
+/// Converts each item by `convert`, which is either the strict one or not.
+fn vec_from_json_ref<'a, T>(
+    value: &JsonValueRef<'a>,
+    convert: fn(&JsonValueRef<'a>) -> Result<T>,
+) -> Result<Vec<T>> {
+    match value {
+        JsonValueRef::Array(arr) => items_from(arr, convert),
+        _ => Err(Error::constraint_err(
+            "type",
+            "expect `JsonValue::Array`",
+            Some(value.to_json_value()),
+        )),
+    }
+}
+
+impl<'a, T: FromJsonRef<'a>> FromJsonRef<'a> for Vec<T> {
+    fn from_json_ref(value: &JsonValueRef<'a>) -> Result<Self> {
+        vec_from_json_ref(value, T::from_json_ref)
+    }
+
+    fn from_json_ref_strict(value: &JsonValueRef<'a>) -> Result<Self> {
+        vec_from_json_ref(value, T::from_json_ref_strict)
+    }
+}
+
+impl<'a, T: FromJsonRef<'a>> FromJsonRef<'a> for Option<T> {
+    fn from_json_ref(value: &JsonValueRef<'a>) -> Result<Self> {
+        match value {
+            JsonValueRef::Null => Ok(None),
+            _ => Ok(Some(FromJsonRef::from_json_ref(value)?)),
+        }
+    }
+
+    fn from_json_ref_strict(value: &JsonValueRef<'a>) -> Result<Self> {
+        match value {
+            JsonValueRef::Null => Ok(None),
+            _ => Ok(Some(FromJsonRef::from_json_ref_strict(value)?)),
+        }
+    }
+}
+
+impl<'a, T: FromJsonRef<'a>> FromJsonRef<'a> for Box<T> {
+    fn from_json_ref(value: &JsonValueRef<'a>) -> Result<Self> {
+        Ok(Box::new(FromJsonRef::from_json_ref(value)?))
+    }
+
+    fn from_json_ref_strict(value: &JsonValueRef<'a>) -> Result<Self> {
+        Ok(Box::new(FromJsonRef::from_json_ref_strict(value)?))
+    }
+}
@@ -140,27 +140,70 @@
 //!     related_items: Option<Vec<Box<Item>>>,
 //! }
 //! ```
+//!
+//! * If an integer must be written as an integer
+//!
+//! An integer field accepts a number whose exact value is an integer in range, such as `1.0` or `1e3`.
+//! With `strict`, it accepts only a literal of an integer, which applies to the fields of the model.
+//!
+//! ```rust
+//! use dade::{model, Model};
+//!
+//! #[model(strict)]
+//! struct Counter {
+//!     count: u64,
+//!     history: Vec<u64>,
+//! }
+//!
+//! assert!(Counter::parse("{\"count\": 1, \"history\": [2]}").is_ok());
+//! assert!(Counter::parse("{\"count\": 1.0, \"history\": []}").is_err());
+//! ```
 use std::collections::BTreeMap;
+use std::io::{BufReader, Read, Write};
 
 pub use dade_macro::model;
 
+mod macros;
+
 mod error;
-pub use crate::error::{Error, ErrorType, Result};
+pub use crate::error::{Error, ErrorType, FieldError, LocItem, ParseErrorKind, Result};
+
+mod event;
+pub use crate::event::{JsonEvent, JsonEventReader};
 
 mod json;
-pub use crate::json::{FromJsonValue, JsonValue, Number, ToJsonValue};
+pub use crate::json::{FromJsonValue, JsonIndex, JsonValue, ToJsonValue};
+
+mod json_ref;
+pub use crate::json_ref::{FromJsonRef, JsonValueRef};
+
+mod documents;
+pub use crate::documents::JsonDocuments;
 
 mod dump;
-pub use crate::dump::JsonDumper;
+pub use crate::dump::{DumpJson, Indent, JsonDumper, Newline, PrettyFormat};
+
+mod lines;
+pub use crate::lines::{JsonLines, JsonLinesWriter};
 
 mod load;
-pub use crate::load::JsonLoader;
+pub use crate::load::{DuplicateKeys, FromJsonStream, JsonLoader, DEFAULT_MAX_DEPTH};
+
+mod map;
+pub use crate::map::Map;
+
+mod pointer;
+
+mod patch;
+
+mod number;
+pub use crate::number::Number;
 
 mod schema;
 pub use crate::schema::RegisterSchema;
 
 mod stream;
-pub use crate::stream::{SliceBytes, Stream};
+pub use crate::stream::{ReadBytes, SliceBytes, Stream};
 
 /// A trait for converting string to titlecased.
 pub trait ToTitle {
@@ -203,6 +246,28 @@ pub fn json_dump(json: &JsonValue, ensure_ascii: bool) -> String {
     JsonDumper::new(ensure_ascii).dump(json)
 }
 
+/// Dump a JsonValue to pretty-printed string.
+///
+/// For example,
+///
+/// ```rust
+/// use dade::{json_dump_pretty, json_load, Indent, PrettyFormat};
+///
+/// let value = json_load("{\"a\": [1, 2], \"b\": {}}").unwrap();
+///
+/// let ret = json_dump_pretty(&value, false, PrettyFormat::new());
+/// assert_eq!(ret, "{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\": {}\n}");
+///
+/// let format = PrettyFormat::new()
+///     .with_indent(Indent::Tabs(1))
+///     .with_space_after_colon(false);
+/// let ret = json_dump_pretty(&value, false, format);
+/// assert_eq!(ret, "{\n\t\"a\":[\n\t\t1,\n\t\t2\n\t],\n\t\"b\":{}\n}");
+/// ```
+pub fn json_dump_pretty(json: &JsonValue, ensure_ascii: bool, format: PrettyFormat) -> String {
+    JsonDumper::new(ensure_ascii).with_pretty(format).dump(json)
+}
+
 /// Load from string to JsonValue.
 ///
 /// For example,
@@ -220,6 +285,44 @@ pub fn json_load(json: &str) -> Result<JsonValue> {
     JsonLoader::from(json).load()
 }
 
+/// Load from a reader to JsonValue.
+///
+/// The input is consumed through a buffer, so the whole input is never held in memory
+/// except for the resulting JsonValue.
+///
+/// For example,
+///
+/// ```rust
+/// use dade::{JsonValue, json_load_reader};
+///
+/// let ret = json_load_reader("[1, 2]".as_bytes());
+/// // ret is JsonValue::Array.
+/// ```
+pub fn json_load_reader<R: Read>(reader: R) -> Result<JsonValue> {
+    load_reader(reader, |loader| loader.load())
+}
+
+fn load_reader<R, T, F>(reader: R, f: F) -> Result<T>
+where
+    R: Read,
+    F: FnOnce(&mut JsonLoader<u8, ReadBytes<BufReader<R>>>) -> Result<T>,
+{
+    let mut loader = JsonLoader::from(ReadBytes::new(BufReader::new(reader)));
+    let ret = f(&mut loader);
+    match loader.stream_mut().take_error() {
+        Some(err) => Err(Error::io_err(&err)),
+        None => ret,
+    }
+}
+
+/// Load a model from the whole input, where a syntax error takes precedence over
+/// the errors of the model.
+fn load_model<T: FromJsonStream, S: Stream<u8>>(loader: &mut JsonLoader<u8, S>) -> Result<T> {
+    let ret = loader.load_field()?;
+    loader.finish()?;
+    ret
+}
+
 /// A trait defines the format to handle a model.
 ///
 /// This trait is efficiently handled data corresponding to an implemented struct.
@@ -234,33 +337,106 @@ pub trait Model {
     /// If the JSON string is invalid, the return is Err.
     /// Also, if valid, the return is Ok that contains an instance.
     fn parse_bytes(bytes: &[u8]) -> Result<Self>
+    where
+        Self: Sized;
+    /// Convert JSON read from a reader to a struct that implemented this trait.
+    /// If the JSON string is invalid, or the reader fails, the return is Err.
+    /// Also, if valid, the return is Ok that contains an instance.
+    fn parse_reader<R: Read>(reader: R) -> Result<Self>
+    where
+        Self: Sized;
+    /// Convert JSON loaded by a configured loader to a struct that implemented this trait.
+    /// This allows the limits and the relaxed syntax of `JsonLoader` to be used.
+    /// If the JSON string is invalid, the return is Err.
+    /// Also, if valid, the return is Ok that contains an instance.
+    fn parse_loader<S: Stream<u8>>(loader: &mut JsonLoader<u8, S>) -> Result<Self>
+    where
+        Self: Sized;
+    /// Convert the value at a JSON Pointer in a JSON string to a struct that implemented this trait.
+    /// The whole JSON string must be valid, while only the value at the pointer is validated,
+    /// and the paths of errors start from the root of the JSON string.
+    /// If the JSON string is invalid, or no value is at the pointer, the return is Err.
+    fn parse_at(json: &str, pointer: &str) -> Result<Self>
+    where
+        Self: Sized;
+    /// Merge a JSON Merge Patch string into the instance, and convert the result to a new struct
+    /// that implemented this trait, so the result is validated the same as the parsed one.
+    /// If the patch string is invalid, or the result is invalid, the return is Err.
+    fn apply_merge_patch(&self, patch: &str) -> Result<Self>
+    where
+        Self: Sized;
+    /// Convert each line of JSON Lines read from a reader to a struct that implemented this trait.
+    /// The return is an iterator that yields the result for each line.
+    fn parse_lines<R: Read>(reader: R) -> JsonLines<BufReader<R>, Self>
     where
         Self: Sized;
     /// Dump a JSON string from the instance.
     fn json(&self, ensure_ascii: bool) -> String;
+    /// Dump a pretty-printed JSON string in the given format from the instance.
+    fn json_pretty(&self, ensure_ascii: bool, format: PrettyFormat) -> String;
+    /// Write a JSON string from the instance to a writer.
+    /// If the writer fails, the return is Err.
+    fn write_json<W: Write>(&self, writer: W, ensure_ascii: bool) -> Result<()>;
     /// Export a JSON Schema with a model.
     fn schema() -> String;
 }
 
-impl<T: ToJsonValue + FromJsonValue + RegisterSchema> Model for T {
+impl<T: ToJsonValue + FromJsonStream + RegisterSchema + DumpJson> Model for T {
     fn parse(json: &str) -> Result<Self> {
-        FromJsonValue::from_json_value(&JsonLoader::from(json).load()?)
+        load_model(&mut JsonLoader::from(json))
     }
     fn parse_bytes(bytes: &[u8]) -> Result<Self> {
-        FromJsonValue::from_json_value(&JsonLoader::from(bytes).load()?)
+        load_model(&mut JsonLoader::from(bytes))
+    }
+    fn parse_reader<R: Read>(reader: R) -> Result<Self> {
+        load_reader(reader, load_model)
+    }
+    fn parse_loader<S: Stream<u8>>(loader: &mut JsonLoader<u8, S>) -> Result<Self> {
+        load_model(loader)
+    }
+    fn parse_at(json: &str, pointer: &str) -> Result<Self> {
+        let value = json_load(json)?;
+        let (target, loc) = pointer::locate(&value, pointer)?;
+        T::from_json_value(target).map_err(|err| {
+            loc.iter().rev().fold(err, |err, item| match item {
+                LocItem::Key(key) => err.at_key(key),
+                LocItem::Index(idx) => err.at_index(*idx),
+            })
+        })
+    }
+    fn apply_merge_patch(&self, patch: &str) -> Result<Self> {
+        let patch = json_load(patch)?;
+        let mut json_value = self.to_json_value();
+        json_value.merge_patch(&patch);
+        T::from_json_value(&json_value)
+    }
+    fn parse_lines<R: Read>(reader: R) -> JsonLines<BufReader<R>, Self> {
+        JsonLines::new(BufReader::new(reader))
     }
     fn json(&self, ensure_ascii: bool) -> String {
-        json_dump(&ToJsonValue::to_json_value(self), ensure_ascii)
+        JsonDumper::new(ensure_ascii).dump(self)
+    }
+    fn json_pretty(&self, ensure_ascii: bool, format: PrettyFormat) -> String {
+        JsonDumper::new(ensure_ascii).with_pretty(format).dump(self)
+    }
+    fn write_json<W: Write>(&self, writer: W, ensure_ascii: bool) -> Result<()> {
+        JsonDumper::new(ensure_ascii).dump_to(self, writer)
     }
     fn schema() -> String {
         let mut defs = BTreeMap::new();
-        let json_value = <T as RegisterSchema>::register_schema(&mut defs);
+        let mut json_value = <T as RegisterSchema>::register_schema(&mut defs);
+        // The schema is sorted by keys so that it does not depend on the order of fields.
+        json_value.sort_keys();
+        defs.values_mut().for_each(JsonValue::sort_keys);
         match json_value {
             JsonValue::Object(ref dict) => {
                 if let Some(JsonValue::String(def_name)) = dict.get(&"$ref".to_string()) {
-                    return JsonValue::Object(BTreeMap::from([
+                    return JsonValue::Object(Map::from([
                         ("$ref".to_string(), JsonValue::String(def_name.to_string())),
-                        ("definitions".to_string(), JsonValue::Object(defs)),
+                        (
+                            "definitions".to_string(),
+                            JsonValue::Object(defs.into_iter().collect()),
+                        ),
                     ]))
                     .to_string();
                 }
@@ -270,3 +446,52 @@ impl<T: ToJsonValue + FromJsonValue + RegisterSchema> Model for T {
         }
     }
 }
+
+/// A trait defines the format to handle a model which borrows from the input,
+/// e.g. a model with a field of `&'a str`.
+///
+/// This is implemented for all models, where strings without escapes are not copied
+/// until they are converted to fields.
+///
+/// For example,
+///
+/// ```rust
+/// use dade::{model, BorrowedModel};
+///
+/// #[model]
+/// struct User<'a> {
+///     id: u64,
+///     #[field(min_length = 1)]
+///     name: &'a str,
+/// }
+///
+/// let json = String::from("{\"id\": 1, \"name\": \"alice\"}");
+/// let user = User::parse_borrowed(&json).unwrap();
+/// assert_eq!(user.name, "alice");
+/// ```
+pub trait BorrowedModel<'a>: Sized {
+    /// Convert a JSON string to a struct that borrows from the string.
+    /// If the JSON string is invalid, the return is Err.
+    /// Also, if valid, the return is Ok that contains an instance.
+    fn parse_borrowed(json: &'a str) -> Result<Self>;
+    /// Convert bytes to a struct that borrows from the bytes.
+    /// If the JSON string is invalid, the return is Err.
+    /// Also, if valid, the return is Ok that contains an instance.
+    fn parse_bytes_borrowed(bytes: &'a [u8]) -> Result<Self>;
+    /// Convert JSON loaded by a configured loader to a struct that borrows from the input.
+    /// If the JSON string is invalid, the return is Err.
+    /// Also, if valid, the return is Ok that contains an instance.
+    fn parse_loader_borrowed(loader: &mut JsonLoader<u8, SliceBytes<'a>>) -> Result<Self>;
+}
+
+impl<'a, T: FromJsonRef<'a>> BorrowedModel<'a> for T {
+    fn parse_borrowed(json: &'a str) -> Result<Self> {
+        T::parse_loader_borrowed(&mut JsonLoader::from(json))
+    }
+    fn parse_bytes_borrowed(bytes: &'a [u8]) -> Result<Self> {
+        T::parse_loader_borrowed(&mut JsonLoader::from(bytes))
+    }
+    fn parse_loader_borrowed(loader: &mut JsonLoader<u8, SliceBytes<'a>>) -> Result<Self> {
+        T::from_json_ref(&loader.load_borrowed()?)
+    }
+}
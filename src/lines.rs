@@ -0,0 +1,111 @@
+use std::io::{BufRead, Write};
+use std::marker::PhantomData;
+
+use crate::dump::{DumpJson, JsonDumper};
+use crate::error::{Error, Result};
+use crate::load::{FromJsonStream, JsonLoader};
+
+/// An iterator that loads an instance from each line of JSON Lines.
+///
+/// Errors have the line number, starting from 1, and the offset in the whole input.
+/// A blank line is an error unless `with_skip_blank_lines` is set.
+/// The iteration ends after an I/O error, but continues after other errors.
+pub struct JsonLines<R: BufRead, T> {
+    reader: R,
+    buffer: Vec<u8>,
+    line: usize,
+    offset: usize,
+    skip_blank_lines: bool,
+    done: bool,
+    phantom: PhantomData<T>,
+}
+
+impl<R: BufRead, T: FromJsonStream> JsonLines<R, T> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: Vec::new(),
+            line: 0,
+            offset: 0,
+            skip_blank_lines: false,
+            done: false,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sets whether to skip lines which consist of whitespace only, which is false by default.
+    pub fn with_skip_blank_lines(mut self, skip_blank_lines: bool) -> Self {
+        self.skip_blank_lines = skip_blank_lines;
+        self
+    }
+}
+
+impl<R: BufRead, T: FromJsonStream> Iterator for JsonLines<R, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.offset += self.buffer.len();
+            self.buffer.clear();
+            match self.reader.read_until(b'\n', &mut self.buffer) {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    self.line += 1;
+                    if self.skip_blank_lines
+                        && self
+                            .buffer
+                            .iter()
+                            .all(|b| matches!(b, b' ' | b'\t' | b'\r' | b'\n'))
+                    {
+                        continue;
+                    }
+                    let line = self.buffer.strip_suffix(b"\n").unwrap_or(&self.buffer);
+                    return Some(
+                        crate::load_model(&mut JsonLoader::from(line))
+                            .map_err(|err| err.at_line(self.line, self.offset)),
+                    );
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(Error::io_err(&err).at_line(self.line + 1, self.offset)));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// A writer that writes each value as JSON in one line, for JSON Lines.
+///
+/// The writer should be buffered, e.g. by `BufWriter`, since it is written in small pieces.
+pub struct JsonLinesWriter<W: Write> {
+    writer: W,
+    dumper: JsonDumper,
+}
+
+impl<W: Write> JsonLinesWriter<W> {
+    pub fn new(writer: W, ensure_ascii: bool) -> Self {
+        Self {
+            writer,
+            dumper: JsonDumper::new(ensure_ascii),
+        }
+    }
+
+    /// Writes a value and a line feed.
+    pub fn write<T: DumpJson + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.dumper.dump_to(value, &mut self.writer)?;
+        self.writer
+            .write_all(b"\n")
+            .map_err(|err| Error::io_err(&err))
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush().map_err(|err| Error::io_err(&err))
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
@@ -1,9 +1,16 @@
-use std::collections::BTreeMap;
+use std::borrow::Cow;
+use std::collections::hash_map::RandomState;
+use std::collections::{BTreeSet, HashSet};
 use std::convert::From;
+use std::hash::{BuildHasher, Hash};
+use std::io::BufRead;
 
-use crate::error::{Error, Result};
-use crate::json::{JsonValue, Number};
-use crate::stream::{SliceBytes, Stream};
+use crate::error::{Error, ErrorType, ParseErrorKind, Result};
+use crate::json::{FromJsonValue, JsonValue};
+use crate::json_ref::JsonValueRef;
+use crate::map::Map;
+use crate::number::Number;
+use crate::stream::{ReadBytes, SliceBytes, Stream};
 
 static JSON_CTR: [bool; 256] = {
     const CT: bool = true;
@@ -29,46 +36,577 @@ static JSON_CTR: [bool; 256] = {
     ]
 };
 
+/// The number of bytes on each side of an error position that are shown in the error.
+const SNIPPET_SIZE: usize = 16;
+
+/// Returns true if the byte can start an unquoted key.
+#[inline]
+fn is_identifier_start(b: u8) -> bool {
+    b.is_ascii_alphabetic() || b == b'_' || b == b'$' || b >= 0x80
+}
+
+/// The maximum depth of nested arrays and objects by default.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// A policy for an object which has the same key more than once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateKeys {
+    /// Fails with `ParseErrorKind::DuplicateKey`, which is the default.
+    Reject,
+    /// Keeps the first value, skipping the following values.
+    KeepFirst,
+    /// Keeps the last value, at the position of the first key.
+    KeepLast,
+    /// Keeps all values as an array in order, e.g. `{"a": 1, "a": 2}` is loaded as `{"a": [1, 2]}`.
+    ///
+    /// The value of a key which is not duplicated is kept as it is.
+    ///
+    /// The values cannot be collected while streaming, so a model loads the whole object
+    /// with `load_value` under this policy, and then converts it from the loaded value.
+    CollectAll,
+}
+
+/// A value of an object, into which the values of a duplicate key are collected
+/// with `DuplicateKeys::CollectAll`.
+trait CollectValues {
+    /// Makes an array of this value, which is the first one, and the given value.
+    fn collect_first(&mut self, value: Self);
+    /// Appends the value to this array.
+    fn collect(&mut self, value: Self);
+}
+
+impl CollectValues for JsonValue {
+    fn collect_first(&mut self, value: Self) {
+        let first = std::mem::replace(self, JsonValue::Null);
+        *self = JsonValue::Array(Vec::from([first, value]));
+    }
+
+    fn collect(&mut self, value: Self) {
+        if let JsonValue::Array(vec) = self {
+            vec.push(value);
+        }
+    }
+}
+
+impl<'a> CollectValues for JsonValueRef<'a> {
+    fn collect_first(&mut self, value: Self) {
+        let first = std::mem::replace(self, JsonValueRef::Null);
+        *self = JsonValueRef::Array(Vec::from([first, value]));
+    }
+
+    fn collect(&mut self, value: Self) {
+        if let JsonValueRef::Array(vec) = self {
+            vec.push(value);
+        }
+    }
+}
+
 pub struct JsonLoader<T, S: Stream<T>> {
     bytes: S,
     buffer: Vec<T>,
+    depth: usize,
+    max_depth: usize,
+    max_input_size: usize,
+    max_string_length: usize,
+    max_number_length: usize,
+    max_array_length: usize,
+    max_object_members: usize,
+    allow_comments: bool,
+    allow_trailing_commas: bool,
+    allow_single_quotes: bool,
+    allow_unquoted_keys: bool,
+    duplicate_keys: DuplicateKeys,
 }
 
 impl<'a> From<&'a str> for JsonLoader<u8, SliceBytes<'a>> {
     fn from(json: &'a str) -> Self {
-        Self {
-            bytes: SliceBytes::new(json.as_bytes()),
-            buffer: Vec::new(),
-        }
+        Self::new(SliceBytes::new(json.as_bytes()))
     }
 }
 
 impl<'a> From<&'a [u8]> for JsonLoader<u8, SliceBytes<'a>> {
     fn from(bytes: &'a [u8]) -> Self {
+        Self::new(SliceBytes::new(bytes))
+    }
+}
+
+impl<R: BufRead> From<ReadBytes<R>> for JsonLoader<u8, ReadBytes<R>> {
+    fn from(bytes: ReadBytes<R>) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl<S: Stream<u8>> JsonLoader<u8, S> {
+    pub fn new(bytes: S) -> Self {
         Self {
-            bytes: SliceBytes::new(bytes),
+            bytes,
             buffer: Vec::new(),
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_input_size: usize::MAX,
+            max_string_length: usize::MAX,
+            max_number_length: usize::MAX,
+            max_array_length: usize::MAX,
+            max_object_members: usize::MAX,
+            allow_comments: false,
+            allow_trailing_commas: false,
+            allow_single_quotes: false,
+            allow_unquoted_keys: false,
+            duplicate_keys: DuplicateKeys::Reject,
         }
     }
-}
 
-impl<S: Stream<u8>> JsonLoader<u8, S> {
+    /// Sets the maximum depth of nested arrays and objects, which is `DEFAULT_MAX_DEPTH` by default.
+    ///
+    /// Loading deeper JSON fails with `ParseErrorKind::DepthLimitExceeded`.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the maximum number of bytes of the input, which is unlimited by default.
+    ///
+    /// Loading larger input fails with `ParseErrorKind::InputTooLarge`.
+    pub fn with_max_input_size(mut self, max_input_size: usize) -> Self {
+        self.max_input_size = max_input_size;
+        self
+    }
+
+    /// Sets the maximum number of bytes of a string after unescaping, which is unlimited by default.
+    ///
+    /// Loading a longer string, including a key of an object, fails with `ParseErrorKind::StringTooLong`.
+    pub fn with_max_string_length(mut self, max_string_length: usize) -> Self {
+        self.max_string_length = max_string_length;
+        self
+    }
+
+    /// Sets the maximum number of characters of a number literal, which is unlimited by default.
+    ///
+    /// Loading a longer number fails with `ParseErrorKind::NumberTooLong`.
+    pub fn with_max_number_length(mut self, max_number_length: usize) -> Self {
+        self.max_number_length = max_number_length;
+        self
+    }
+
+    /// Sets the maximum number of items in an array, which is unlimited by default.
+    ///
+    /// Loading a longer array fails with `ParseErrorKind::TooManyItems`.
+    pub fn with_max_array_length(mut self, max_array_length: usize) -> Self {
+        self.max_array_length = max_array_length;
+        self
+    }
+
+    /// Sets the maximum number of members in an object, which is unlimited by default.
+    ///
+    /// Loading a larger object fails with `ParseErrorKind::TooManyMembers`.
+    pub fn with_max_object_members(mut self, max_object_members: usize) -> Self {
+        self.max_object_members = max_object_members;
+        self
+    }
+
+    /// Sets whether to allow `//` and `/* */` comments where whitespace is allowed,
+    /// which is false by default.
+    pub fn with_comments(mut self, allow_comments: bool) -> Self {
+        self.allow_comments = allow_comments;
+        self
+    }
+
+    /// Sets whether to allow a `,` before the closing bracket of an array or an object,
+    /// which is false by default.
+    pub fn with_trailing_commas(mut self, allow_trailing_commas: bool) -> Self {
+        self.allow_trailing_commas = allow_trailing_commas;
+        self
+    }
+
+    /// Sets whether to allow strings quoted by `'`, where `\'` is also allowed as an escape,
+    /// which is false by default.
+    pub fn with_single_quotes(mut self, allow_single_quotes: bool) -> Self {
+        self.allow_single_quotes = allow_single_quotes;
+        self
+    }
+
+    /// Sets whether to allow keys of objects which are not quoted, which is false by default.
+    ///
+    /// An unquoted key consists of ASCII letters, digits, `_`, `$` and non-ASCII characters,
+    /// and does not start with a digit.
+    pub fn with_unquoted_keys(mut self, allow_unquoted_keys: bool) -> Self {
+        self.allow_unquoted_keys = allow_unquoted_keys;
+        self
+    }
+
+    /// Sets all of `with_comments`, `with_trailing_commas`, `with_single_quotes` and
+    /// `with_unquoted_keys`, to load JSON5-style input such as hand-written configs.
+    pub fn with_relaxed(self, relaxed: bool) -> Self {
+        self.with_comments(relaxed)
+            .with_trailing_commas(relaxed)
+            .with_single_quotes(relaxed)
+            .with_unquoted_keys(relaxed)
+    }
+
+    /// Sets the policy for an object which has the same key more than once,
+    /// which is `DuplicateKeys::Reject` by default.
+    pub fn with_duplicate_keys(mut self, duplicate_keys: DuplicateKeys) -> Self {
+        self.duplicate_keys = duplicate_keys;
+        self
+    }
+
+    /// Returns the policy for an object which has the same key more than once.
+    pub fn duplicate_keys(&self) -> DuplicateKeys {
+        self.duplicate_keys
+    }
+
+    /// Returns a mutable reference to the underlying stream.
+    pub fn stream_mut(&mut self) -> &mut S {
+        &mut self.bytes
+    }
+
+    /// Returns the number of bytes consumed so far.
+    pub fn position(&self) -> usize {
+        self.bytes.position()
+    }
+
     pub fn load(&mut self) -> Result<JsonValue> {
+        self.depth = 0;
         let val = self._load()?;
-        self.skip_control_char();
-        if !self.bytes.finish() {
-            return Err(Error::parse_err("extra data"));
-        }
+        self.finish()?;
         Ok(val)
     }
 
+    /// Loads the next value, leaving the input after it.
+    pub fn load_value(&mut self) -> Result<JsonValue> {
+        self._load()
+    }
+
+    /// Loads the next value to `T`.
+    ///
+    /// The outer `Err` is a syntax or I/O error, which stops loading, and the inner `Err` is
+    /// an error of the value, after which the input is still valid to load.
+    pub fn load_field<T: FromJsonStream>(&mut self) -> Result<Result<T>> {
+        match T::from_json_stream(self) {
+            Err(err) if !matches!(err.err_type(), ErrorType::ValidateError) => Err(err),
+            ret => Ok(ret),
+        }
+    }
+
+    /// Loads the next value to `T` the same as `load_field`, except by `from_json_stream_strict`.
+    pub fn load_field_strict<T: FromJsonStream>(&mut self) -> Result<Result<T>> {
+        match T::from_json_stream_strict(self) {
+            Err(err) if !matches!(err.err_type(), ErrorType::ValidateError) => Err(err),
+            ret => Ok(ret),
+        }
+    }
+
+    /// Checks that nothing but whitespace follows the loaded value.
+    pub fn finish(&mut self) -> Result<()> {
+        self.skip_control_char()?;
+        self.check_input_size()?;
+        if !self.bytes.at_end() {
+            return Err(self.error(ParseErrorKind::TrailingData));
+        }
+        Ok(())
+    }
+
+    /// Loads `null` if it is the next value, returning whether it was loaded.
+    pub fn load_null(&mut self) -> Result<bool> {
+        self.skip_to_value()?;
+        if let Some(0x6e) = self.bytes.peek() {
+            self.get_null()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Enters an object if it is the next value, returning whether it was entered.
+    ///
+    /// Then the members must be loaded by `load_members`.
+    pub fn begin_object(&mut self) -> Result<bool> {
+        self.skip_to_value()?;
+        if let Some(0x7b) = self.bytes.peek() {
+            self.enter()?;
+            self.bytes.skip();
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Enters an array if it is the next value, returning whether it was entered.
+    ///
+    /// Then the items must be loaded by `load_items`.
+    pub fn begin_array(&mut self) -> Result<bool> {
+        self.skip_to_value()?;
+        if let Some(0x5b) = self.bytes.peek() {
+            self.enter()?;
+            self.bytes.skip();
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Loads the members of an object entered by `begin_object`.
+    ///
+    /// `f` is called with each key, and loads the value then returns true,
+    /// or returns false to skip the value.
+    ///
+    /// A duplicate key follows `with_duplicate_keys`, where `f` is not called with
+    /// `DuplicateKeys::Reject`, which fails at the key, nor with `DuplicateKeys::KeepFirst`
+    /// unless `f` skipped the first value, and is called again otherwise. The values cannot be collected while streaming,
+    /// so `DuplicateKeys::CollectAll` should be handled by `load_value` instead.
+    pub fn load_members<F>(&mut self, mut f: F) -> Result<()>
+    where
+        F: FnMut(&mut Self, &str) -> Result<bool>,
+    {
+        // The keys whose values are loaded are kept, while the other keys are not kept
+        // but compared by their hashes only to reject a duplicate.
+        let keep_all = matches!(
+            self.duplicate_keys,
+            DuplicateKeys::KeepLast | DuplicateKeys::CollectAll
+        );
+        let reject = self.duplicate_keys == DuplicateKeys::Reject;
+        let mut loaded = BTreeSet::new();
+        let mut skipped = HashSet::new();
+        let hasher = RandomState::new();
+        let mut key = Vec::new();
+        let mut index = 0;
+        while let Some(key_pos) = self.member_key(index, &mut key)? {
+            index += 1;
+            // `member_key` returns valid UTF-8.
+            let key = unsafe { std::str::from_utf8_unchecked(&key) };
+            if keep_all {
+                if !f(self, key)? {
+                    self.skip_value()?;
+                }
+                continue;
+            }
+            let hash = if reject { hasher.hash_one(key) } else { 0 };
+            if loaded.contains(key) || skipped.contains(&hash) {
+                if reject {
+                    return Err(self.error_at(ParseErrorKind::DuplicateKey, key_pos));
+                }
+                self.skip_value()?;
+                continue;
+            }
+            if f(self, key)? {
+                loaded.insert(key.to_string());
+            } else {
+                self.skip_value()?;
+                if reject {
+                    skipped.insert(hash);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads the items of an array entered by `begin_array`.
+    ///
+    /// `f` is called with each index, and must load the item.
+    pub fn load_items<F>(&mut self, mut f: F) -> Result<()>
+    where
+        F: FnMut(&mut Self, usize) -> Result<()>,
+    {
+        let mut index = 0;
+        while self.array_item(index)? {
+            f(self, index)?;
+            index += 1;
+        }
+        Ok(())
+    }
+
+    /// Skips the next value, checking its syntax without building it.
+    pub fn skip_value(&mut self) -> Result<()> {
+        self.skip_to_value()?;
+        match self.bytes.peek() {
+            // string
+            Some(0x22) => {
+                self.bytes.skip();
+                self.read_string(0x22)
+            }
+            Some(0x27) if self.allow_single_quotes => {
+                self.bytes.skip();
+                self.read_string(0x27)
+            }
+            // number
+            Some(0x30..=0x39 | 0x2d) => {
+                self.read_number()?;
+                // Release the anchor.
+                self.bytes.read();
+                Ok(())
+            }
+            // array
+            Some(0x5b) => {
+                self.enter()?;
+                self.bytes.skip();
+                self.load_items(|loader, _| loader.skip_value())
+            }
+            // object
+            Some(0x7b) => {
+                self.enter()?;
+                self.bytes.skip();
+                self.load_members(|_, _| Ok(false))
+            }
+            // false
+            Some(0x66) => self.get_false().map(|_| ()),
+            // true
+            Some(0x74) => self.get_true().map(|_| ()),
+            // null
+            Some(0x6e) => self.get_null().map(|_| ()),
+            _ => Err(self.unexpected(ParseErrorKind::ExpectedValue)),
+        }
+    }
+
+    #[cold]
+    fn error(&self, kind: ParseErrorKind) -> Error {
+        self.error_at(kind, self.bytes.position())
+    }
+
+    /// Makes an error for an unexpected character, or for the end of input if no character remains.
+    #[cold]
+    fn unexpected(&mut self, kind: ParseErrorKind) -> Error {
+        if self.bytes.peek().is_none() {
+            self.error(ParseErrorKind::UnexpectedEof)
+        } else {
+            self.error(kind)
+        }
+    }
+
+    #[cold]
+    fn error_at(&self, kind: ParseErrorKind, pos: usize) -> Error {
+        let (line, column) = self.bytes.line_column(pos);
+        let snippet: String = String::from_utf8_lossy(self.bytes.around(pos, SNIPPET_SIZE))
+            // The edges may split a character.
+            .trim_matches(char::REPLACEMENT_CHARACTER)
+            .chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .collect();
+        Error::parse_err_at(kind, pos, line, column, snippet.as_str())
+    }
+
+    /// Enters an array or an object, checking the depth.
     #[inline]
-    fn skip_control_char(&mut self) {
-        self.bytes.while_if(|&b| JSON_CTR[b as usize]);
+    fn enter(&mut self) -> Result<()> {
+        if self.depth >= self.max_depth {
+            return Err(self.error(ParseErrorKind::DepthLimitExceeded));
+        }
+        self.depth += 1;
+        Ok(())
     }
 
+    /// Returns the position up to which the current string can extend within the limits.
     #[inline]
-    fn get_number(&mut self) -> Result<JsonValue> {
+    fn string_limit(&self) -> usize {
+        let remaining = self.max_string_length.saturating_sub(self.buffer.len());
+        self.bytes
+            .position()
+            .saturating_add(remaining)
+            .min(self.max_input_size)
+    }
+
+    #[cold]
+    fn string_limit_error(&self) -> Error {
+        if self.bytes.position() >= self.max_input_size {
+            self.error(ParseErrorKind::InputTooLarge)
+        } else {
+            self.error(ParseErrorKind::StringTooLong)
+        }
+    }
+
+    /// Fails if the input has exceeded its size limit.
+    #[inline]
+    fn check_input_size(&self) -> Result<()> {
+        if self.bytes.position() > self.max_input_size {
+            return Err(self.error(ParseErrorKind::InputTooLarge));
+        }
+        Ok(())
+    }
+
+    /// Skips bytes while `cond` holds, checking the size of the input on each byte.
+    #[inline]
+    fn skip_while<F>(&mut self, cond: F) -> Result<()>
+    where
+        F: Fn(&u8) -> bool,
+    {
+        while self.bytes.next_if(&cond) {
+            self.check_input_size()?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn skip_control_char(&mut self) -> Result<()> {
+        self.skip_while(|&b| JSON_CTR[b as usize])?;
+        if self.allow_comments {
+            self.skip_comments()?;
+        }
+        Ok(())
+    }
+
+    /// Skips comments and whitespace after them.
+    fn skip_comments(&mut self) -> Result<()> {
+        loop {
+            if self.bytes.expect(b"//") {
+                self.skip_while(|&b| b != 0x0a)?;
+            } else if self.bytes.expect(b"/*") {
+                while !self.bytes.expect(b"*/") {
+                    if self.bytes.peek().is_none() {
+                        return Err(self.error(ParseErrorKind::UnexpectedEof));
+                    }
+                    self.bytes.skip();
+                    self.check_input_size()?;
+                }
+            } else {
+                return Ok(());
+            }
+            self.skip_while(|&b| JSON_CTR[b as usize])?;
+        }
+    }
+
+    /// Skips whitespace, returning whether any input remains.
+    pub(crate) fn has_next(&mut self) -> Result<bool> {
+        self.skip_control_char()?;
+        Ok(self.bytes.peek().is_some())
+    }
+
+    /// Skips record separators of RFC 7464 and whitespace after them.
+    pub(crate) fn skip_record_separator(&mut self) -> Result<()> {
+        if !self.bytes.next_if(|&b| b == 0x1e) {
+            return Err(self.unexpected(ParseErrorKind::ExpectedRecordSeparator));
+        }
+        self.skip_while(|&b| b == 0x1e || JSON_CTR[b as usize])
+    }
+
+    /// Skips whitespace before a value, checking the size of the input.
+    #[inline]
+    fn skip_to_value(&mut self) -> Result<()> {
+        self.skip_control_char()?;
+        self.check_input_size()
+    }
+
+    #[cold]
+    fn number_limit_error(&self, start: usize) -> Error {
+        if self.bytes.position() > self.max_input_size {
+            self.error(ParseErrorKind::InputTooLarge)
+        } else {
+            self.error_at(ParseErrorKind::NumberTooLong, start)
+        }
+    }
+
+    /// Skips digits of a number up to `limit`, which is the position the number can extend to.
+    #[inline]
+    fn skip_digits(&mut self, start: usize, limit: usize) -> Result<()> {
+        while self.bytes.next_if(|b| (0x30..=0x39).contains(b)) {
+            if self.bytes.position() > limit {
+                return Err(self.number_limit_error(start));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a number, leaving the anchor at the start of it.
+    #[inline]
+    fn read_number(&mut self) -> Result<()> {
         // number = [ minus ] int [ frac ] [ exp ]
         // decimal-point = %x2E       ; .
         // digit1-9 = %x31-39         ; 1-9
@@ -79,29 +617,34 @@ impl<S: Stream<u8>> JsonLoader<u8, S> {
         // minus = %x2D               ; -
         // plus = %x2B                ; +
         // zero = %x30                ; 0
+        let start = self.bytes.position();
+        let limit = start
+            .saturating_add(self.max_number_length)
+            .min(self.max_input_size);
         self.bytes.anchor();
         // [ minus ]
         self.bytes.next_if(|&b| b == 0x2d);
         // int = zero / ( digit1-9 *DIGIT )
-        match self.bytes.next() {
+        match self.bytes.peek() {
             // digit1-9
             Some(0x31..=0x39) => {
+                self.bytes.skip();
                 // *DIGIT
-                self.bytes.while_if(|b| (0x30..=0x39).contains(b));
+                self.skip_digits(start, limit)?;
             }
             // zero
-            Some(0x30) => {}
-            _ => return Err(Error::parse_err("extra data")),
+            Some(0x30) => self.bytes.skip(),
+            _ => return Err(self.unexpected(ParseErrorKind::InvalidNumber)),
         }
         // frac = decimal-point 1*DIGIT
         // decimal-point
         if self.bytes.next_if(|&b| b == 0x2e) {
             // 1 DIGIT
             if !self.bytes.next_if(|b| (0x30..=0x39).contains(b)) {
-                return Err(Error::parse_err("extra data"));
+                return Err(self.unexpected(ParseErrorKind::InvalidNumber));
             }
             // *DIGIT
-            self.bytes.while_if(|b| (0x30..=0x39).contains(b));
+            self.skip_digits(start, limit)?;
         }
         // exp = e [ minus / plus ] 1*DIGIT
         if self.bytes.next_if(|&b| b == 0x65 || b == 0x45) {
@@ -109,11 +652,20 @@ impl<S: Stream<u8>> JsonLoader<u8, S> {
             self.bytes.next_if(|&b| b == 0x2d || b == 0x2b);
             // 1 DIGIT
             if !self.bytes.next_if(|b| (0x30..=0x39).contains(b)) {
-                return Err(Error::parse_err("extra data"));
+                return Err(self.unexpected(ParseErrorKind::InvalidNumber));
             }
             // *DIGIT
-            self.bytes.while_if(|b| (0x30..=0x39).contains(b))
+            self.skip_digits(start, limit)?;
+        }
+        if self.bytes.position() > limit {
+            return Err(self.number_limit_error(start));
         }
+        Ok(())
+    }
+
+    #[inline]
+    fn get_number(&mut self) -> Result<JsonValue> {
+        self.read_number()?;
         unsafe {
             Ok(JsonValue::Number(Number::new(String::from_utf8_unchecked(
                 Vec::from(self.bytes.read()),
@@ -125,19 +677,24 @@ impl<S: Stream<u8>> JsonLoader<u8, S> {
     fn handle_escaped_unicode(&mut self) -> Result<()> {
         // ref: https://www.unicode.org/versions/Unicode14.0.0/ch03.pdf
         macro_rules! to_num {
-            () => {
-                match self.bytes.next() {
+            () => {{
+                let num = match self.bytes.peek() {
                     // 0..=9
                     Some(bbb @ 0x30u8..=0x39) => ((*bbb as u16) - 48),
                     // A..=F
                     Some(bbb @ 0x41..=0x46) => ((*bbb as u16) - 55),
                     // a..=f
                     Some(bbb @ 0x61..=0x66) => ((*bbb as u16) - 87),
-                    _ => return Err(Error::parse_err("extra data")),
-                }
-            };
+                    _ => return Err(self.unexpected(ParseErrorKind::InvalidEscape)),
+                };
+                self.bytes.skip();
+                num
+            }};
         }
 
+        // The position of `\u`.
+        let start = self.bytes.position() - 2;
+
         let code = (to_num!() << 12) + (to_num!() << 8) + (to_num!() << 4) + to_num!();
         if !(0xD800..=0xDFFF).contains(&code) {
             if code < 0x80 {
@@ -156,14 +713,14 @@ impl<S: Stream<u8>> JsonLoader<u8, S> {
             }
         } else {
             if code > 0xDBFF {
-                return Err(Error::parse_err("no surrogate key"));
+                return Err(self.error_at(ParseErrorKind::LoneSurrogate, start));
             }
-            if !(Some(&0x5c) == self.bytes.next() && Some(&0x75) == self.bytes.next()) {
-                return Err(Error::parse_err("extra data"));
+            if !self.bytes.expect(b"\\u") {
+                return Err(self.error_at(ParseErrorKind::LoneSurrogate, start));
             }
             let code2 = (to_num!() << 12) + (to_num!() << 8) + (to_num!() << 4) + to_num!();
             if !(0xDC00..=0xDFFF).contains(&code2) {
-                return Err(Error::parse_err("no surrogate key"));
+                return Err(self.error_at(ParseErrorKind::LoneSurrogate, start));
             }
             let u = ((code & 0b0000001111000000) >> 6) + 1;
             let x = ((code & 0b0000000000111111) << 10) + (code2 & 0b0000001111111111);
@@ -179,23 +736,32 @@ impl<S: Stream<u8>> JsonLoader<u8, S> {
 
     #[inline]
     fn handle_escaped_str(&mut self) -> Result<()> {
-        match self.bytes.next() {
-            Some(0x75) => self.handle_escaped_unicode()?,
-            Some(0x22) => self.buffer.push(0x22),
-            Some(0x5c) => self.buffer.push(0x5c),
-            Some(0x2f) => self.buffer.push(0x2f),
-            Some(0x62) => self.buffer.push(0x08),
-            Some(0x66) => self.buffer.push(0x0c),
-            Some(0x6e) => self.buffer.push(0x0a),
-            Some(0x72) => self.buffer.push(0x0d),
-            Some(0x74) => self.buffer.push(0x09),
-            _ => return Err(Error::parse_err("invalid control character")),
-        }
+        let escaped = match self.bytes.peek() {
+            Some(0x75) => {
+                self.bytes.skip();
+                return self.handle_escaped_unicode();
+            }
+            Some(0x22) => 0x22,
+            Some(0x27) if self.allow_single_quotes => 0x27,
+            Some(0x5c) => 0x5c,
+            Some(0x2f) => 0x2f,
+            Some(0x62) => 0x08,
+            Some(0x66) => 0x0c,
+            Some(0x6e) => 0x0a,
+            Some(0x72) => 0x0d,
+            Some(0x74) => 0x09,
+            _ => return Err(self.unexpected(ParseErrorKind::InvalidEscape)),
+        };
+        self.bytes.skip();
+        self.buffer.push(escaped);
         Ok(())
     }
 
+    /// Reads a string after the opening quotation mark into the buffer.
+    ///
+    /// `quote` is the quotation mark, which is `'` for a single-quoted string.
     #[inline]
-    fn _get_string(&mut self) -> Result<String> {
+    fn read_string(&mut self, quote: u8) -> Result<()> {
         // string = quotation-mark *char quotation-mark
         //
         // char = unescaped /
@@ -217,19 +783,24 @@ impl<S: Stream<u8>> JsonLoader<u8, S> {
         // unescaped = %x20-21 / %x23-5B / %x5D-10FFFF
         self.bytes.anchor();
         self.buffer.clear();
+        let mut limit = self.string_limit();
 
         macro_rules! handle_unicode {
             ($range: expr) => {
                 if !self.bytes.next_if(|b| $range.contains(b)) {
-                    return Err(Error::parse_err("invalid utf-8 character"));
+                    return Err(self.unexpected(ParseErrorKind::InvalidUtf8));
                 }
             };
         }
 
         loop {
+            if self.bytes.position() >= limit && matches!(self.bytes.peek(), Some(&b) if b != quote)
+            {
+                return Err(self.string_limit_error());
+            }
             match self.bytes.peek() {
                 // ref: https://www.unicode.org/versions/Unicode14.0.0/ch03.pdf
-                Some(0x20..=0x21 | 0x23..=0x5b | 0x5d..=0x7f) => {
+                Some(0x20..=0x21 | 0x23..=0x26 | 0x28..=0x5b | 0x5d..=0x7f) => {
                     self.bytes.skip();
                 }
                 Some((0xe1..=0xec)) => {
@@ -237,18 +808,23 @@ impl<S: Stream<u8>> JsonLoader<u8, S> {
                     handle_unicode!(0x80..=0xbf);
                     handle_unicode!(0x80..=0xbf);
                 }
-                Some(0x22) => unsafe {
-                    self.buffer.extend_from_slice(self.bytes.read());
+                Some(&b @ (0x22 | 0x27)) => {
+                    if b == quote {
+                        self.buffer.extend_from_slice(self.bytes.read());
+                        self.bytes.skip();
+                        return Ok(());
+                    }
                     self.bytes.skip();
-                    return Ok(String::from_utf8_unchecked(Vec::from(
-                        self.buffer.as_slice(),
-                    )));
-                },
+                }
                 Some(0x5c) => {
                     self.buffer.extend_from_slice(self.bytes.read());
                     self.bytes.skip();
                     self.handle_escaped_str()?;
+                    if self.buffer.len() > self.max_string_length {
+                        return Err(self.string_limit_error());
+                    }
                     self.bytes.anchor();
+                    limit = self.string_limit();
                 }
                 Some((0xc2..=0xdf)) => {
                     self.bytes.skip();
@@ -287,15 +863,50 @@ impl<S: Stream<u8>> JsonLoader<u8, S> {
                     handle_unicode!(0x80..=0xbf);
                     handle_unicode!(0x80..=0xbf);
                 }
-                None => return Err(Error::parse_err("unterminated string")),
-                _ => return Err(Error::parse_err("invalid control character")),
+                None => return Err(self.error(ParseErrorKind::UnexpectedEof)),
+                Some(0x00..=0x1f) => return Err(self.error(ParseErrorKind::ControlCharacter)),
+                _ => return Err(self.error(ParseErrorKind::InvalidUtf8)),
+            }
+        }
+    }
+
+    #[inline]
+    fn _get_string(&mut self, quote: u8) -> Result<String> {
+        self.read_string(quote)?;
+        unsafe { Ok(String::from_utf8_unchecked(self.buffer.clone())) }
+    }
+
+    /// Reads an unquoted key, which consists of ASCII letters, digits, `_`, `$` and non-ASCII characters.
+    fn get_identifier(&mut self) -> Result<String> {
+        let start = self.bytes.position();
+        self.read_identifier()?;
+        String::from_utf8(self.bytes.read().to_vec())
+            .map_err(|_| self.error_at(ParseErrorKind::InvalidUtf8, start))
+    }
+
+    /// Reads an unquoted key, leaving the anchor at its start.
+    ///
+    /// The caller must validate the key as UTF-8.
+    fn read_identifier(&mut self) -> Result<()> {
+        self.buffer.clear();
+        let limit = self.string_limit();
+        self.bytes.anchor();
+        loop {
+            match self.bytes.peek() {
+                Some(&b) if is_identifier_start(b) || b.is_ascii_digit() => {
+                    if self.bytes.position() >= limit {
+                        return Err(self.string_limit_error());
+                    }
+                    self.bytes.skip();
+                }
+                _ => return Ok(()),
             }
         }
     }
 
     #[inline]
-    fn get_string(&mut self) -> Result<JsonValue> {
-        Ok(JsonValue::String(self._get_string()?))
+    fn get_string(&mut self, quote: u8) -> Result<JsonValue> {
+        Ok(JsonValue::String(self._get_string(quote)?))
     }
 
     #[inline]
@@ -303,7 +914,7 @@ impl<S: Stream<u8>> JsonLoader<u8, S> {
         if self.bytes.expect(b"null") {
             Ok(JsonValue::Null)
         } else {
-            Err(Error::parse_err("extra data"))
+            Err(self.unexpected(ParseErrorKind::InvalidLiteral))
         }
     }
 
@@ -312,7 +923,7 @@ impl<S: Stream<u8>> JsonLoader<u8, S> {
         if self.bytes.expect(b"true") {
             Ok(JsonValue::Bool(true))
         } else {
-            Err(Error::parse_err("extra data"))
+            Err(self.unexpected(ParseErrorKind::InvalidLiteral))
         }
     }
 
@@ -321,75 +932,229 @@ impl<S: Stream<u8>> JsonLoader<u8, S> {
         if self.bytes.expect(b"false") {
             Ok(JsonValue::Bool(false))
         } else {
-            Err(Error::parse_err("extra data"))
+            Err(self.unexpected(ParseErrorKind::InvalidLiteral))
         }
     }
 
+    /// Moves to the next item of an array, returning false at the end of the array.
     #[inline]
-    fn get_array(&mut self) -> Result<JsonValue> {
-        self.skip_control_char();
-        if self.bytes.next_if(|&b| b == 0x5d) {
-            return Ok(JsonValue::Array(Vec::new()));
+    pub(crate) fn array_item(&mut self, index: usize) -> Result<bool> {
+        self.skip_control_char()?;
+        if index > 0 {
+            match self.bytes.peek() {
+                Some(0x2c) => {
+                    self.bytes.skip();
+                    self.skip_control_char()?;
+                    if let Some(0x5d) = self.bytes.peek() {
+                        if !self.allow_trailing_commas {
+                            return Err(self.error(ParseErrorKind::TrailingComma));
+                        }
+                        self.bytes.skip();
+                        self.depth -= 1;
+                        return Ok(false);
+                    }
+                }
+                Some(0x5d) => {
+                    self.bytes.skip();
+                    self.depth -= 1;
+                    return Ok(false);
+                }
+                _ => return Err(self.unexpected(ParseErrorKind::ExpectedCommaOrEnd)),
+            }
+        } else if self.bytes.next_if(|&b| b == 0x5d) {
+            self.depth -= 1;
+            return Ok(false);
+        }
+        if index >= self.max_array_length {
+            return Err(self.error(ParseErrorKind::TooManyItems));
+        }
+        Ok(true)
+    }
+
+    /// Moves to the next member of an object, returning the key and its position,
+    /// or None at the end of the object.
+    #[inline]
+    pub(crate) fn object_key(&mut self, index: usize) -> Result<Option<(String, usize)>> {
+        let (quote, key_pos) = match self.next_key(index)? {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+        let key = match quote {
+            Some(quote) => self._get_string(quote)?,
+            None => self.get_identifier()?,
+        };
+        self.expect_colon()?;
+        Ok(Some((key, key_pos)))
+    }
+
+    /// Reads the next key of an object into `key` as valid UTF-8, returning the position of the key,
+    /// or None at the end of the object.
+    ///
+    /// The memory of `key` is reused for the following key, so a key is not allocated each time.
+    fn member_key(&mut self, index: usize, key: &mut Vec<u8>) -> Result<Option<usize>> {
+        let (quote, key_pos) = match self.next_key(index)? {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+        match quote {
+            Some(quote) => self.read_string(quote)?,
+            None => {
+                let start = self.bytes.position();
+                self.read_identifier()?;
+                let identifier = self.bytes.read();
+                if std::str::from_utf8(identifier).is_err() {
+                    return Err(self.error_at(ParseErrorKind::InvalidUtf8, start));
+                }
+                self.buffer.extend_from_slice(identifier);
+            }
+        }
+        std::mem::swap(key, &mut self.buffer);
+        self.expect_colon()?;
+        Ok(Some(key_pos))
+    }
+
+    /// Moves to the start of the next key of an object, returning the quotation mark, which is
+    /// None for an unquoted key, and the position of the key, or None at the end of the object.
+    ///
+    /// The opening quotation mark is consumed.
+    #[inline]
+    fn next_key(&mut self, index: usize) -> Result<Option<(Option<u8>, usize)>> {
+        self.skip_control_char()?;
+        if index > 0 {
+            match self.bytes.peek() {
+                Some(0x2c) => {
+                    self.bytes.skip();
+                    self.skip_control_char()?;
+                    if self.allow_trailing_commas && self.bytes.next_if(|&b| b == 0x7d) {
+                        self.depth -= 1;
+                        return Ok(None);
+                    }
+                }
+                Some(0x7d) => {
+                    self.bytes.skip();
+                    self.depth -= 1;
+                    return Ok(None);
+                }
+                _ => return Err(self.unexpected(ParseErrorKind::ExpectedCommaOrEnd)),
+            }
+        } else if self.bytes.next_if(|&b| b == 0x7d) {
+            self.depth -= 1;
+            return Ok(None);
+        }
+        let key_pos = self.bytes.position();
+        let quote = match self.bytes.peek() {
+            Some(0x22) => Some(0x22),
+            Some(0x27) if self.allow_single_quotes => Some(0x27),
+            Some(&b) if self.allow_unquoted_keys && is_identifier_start(b) => None,
+            Some(0x7d) if index > 0 => return Err(self.error(ParseErrorKind::TrailingComma)),
+            _ => return Err(self.unexpected(ParseErrorKind::KeyMustBeString)),
+        };
+        if index >= self.max_object_members {
+            return Err(self.error_at(ParseErrorKind::TooManyMembers, key_pos));
+        }
+        if quote.is_some() {
+            self.bytes.skip();
         }
+        Ok(Some((quote, key_pos)))
+    }
+
+    #[inline]
+    fn expect_colon(&mut self) -> Result<()> {
+        self.skip_control_char()?;
+        if !self.bytes.next_if(|&b| b == 0x3a) {
+            return Err(self.unexpected(ParseErrorKind::ExpectedColon));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get_array(&mut self) -> Result<JsonValue> {
         let mut vec = Vec::new();
-        loop {
+        while self.array_item(vec.len())? {
             vec.push(self._load()?);
-            self.skip_control_char();
-            match self.bytes.next() {
-                Some(0x2c) => {}
-                Some(0x5d) => return Ok(JsonValue::Array(vec)),
-                _ => return Err(Error::parse_err("extra data")),
-            }
         }
+        Ok(JsonValue::Array(vec))
     }
 
     #[inline]
     fn get_object(&mut self) -> Result<JsonValue> {
-        self.skip_control_char();
-        if self.bytes.next_if(|&b| b == 0x7d) {
-            return Ok(JsonValue::Object(BTreeMap::new()));
+        let mut dict = Map::new();
+        let mut collected = BTreeSet::new();
+        let mut index = 0;
+        while let Some((key, key_pos)) = self.object_key(index)? {
+            index += 1;
+            self.load_member(&mut dict, &mut collected, key, key_pos, Self::_load)?;
         }
-        let mut dict = BTreeMap::new();
+        Ok(JsonValue::Object(dict))
+    }
 
-        self.skip_control_char();
-        while Some(&0x22) == self.bytes.next() {
-            let key = self._get_string()?;
-            self.skip_control_char();
-            if !self.bytes.next_if(|&b| b == 0x3a) {
-                return Err(Error::parse_err("extra data"));
+    /// Loads the value of a member by `load` into `dict`, following the policy for a duplicate key.
+    ///
+    /// `collected` has the keys whose values are already collected into an array.
+    #[inline]
+    fn load_member<K, V>(
+        &mut self,
+        dict: &mut Map<K, V>,
+        collected: &mut BTreeSet<K>,
+        key: K,
+        key_pos: usize,
+        load: fn(&mut Self) -> Result<V>,
+    ) -> Result<()>
+    where
+        K: Hash + Ord + Clone,
+        V: CollectValues,
+    {
+        if !dict.contains_key(&key) {
+            let value = load(self)?;
+            dict.insert(key, value);
+            return Ok(());
+        }
+        match self.duplicate_keys {
+            DuplicateKeys::Reject => {
+                return Err(self.error_at(ParseErrorKind::DuplicateKey, key_pos));
             }
-            if dict.insert(key, self._load()?).is_some() {
-                return Err(Error::parse_err("exists same key"));
+            DuplicateKeys::KeepFirst => self.skip_value()?,
+            DuplicateKeys::KeepLast => {
+                let value = load(self)?;
+                dict.insert(key, value);
             }
-            self.skip_control_char();
-            match self.bytes.next() {
-                Some(0x2c) => {}
-                Some(0x7d) => return Ok(JsonValue::Object(dict)),
-                _ => return Err(Error::parse_err("extra data")),
+            DuplicateKeys::CollectAll => {
+                let value = load(self)?;
+                let slot = dict.get_mut(&key).unwrap();
+                if collected.insert(key) {
+                    slot.collect_first(value);
+                } else {
+                    slot.collect(value);
+                }
             }
-            self.skip_control_char();
         }
-        Err(Error::parse_err("expect string"))
+        Ok(())
     }
 
     #[inline]
     fn _load(&mut self) -> Result<JsonValue> {
-        self.skip_control_char();
+        self.skip_to_value()?;
         match self.bytes.peek() {
             // string
             Some(0x22) => {
                 self.bytes.skip();
-                self.get_string()
+                self.get_string(0x22)
+            }
+            Some(0x27) if self.allow_single_quotes => {
+                self.bytes.skip();
+                self.get_string(0x27)
             }
             // number
             Some(0x30..=0x39 | 0x2d) => self.get_number(),
             // array
             Some(0x5b) => {
+                self.enter()?;
                 self.bytes.skip();
                 self.get_array()
             }
             // object
             Some(0x7b) => {
+                self.enter()?;
                 self.bytes.skip();
                 self.get_object()
             }
@@ -399,7 +1164,237 @@ impl<S: Stream<u8>> JsonLoader<u8, S> {
             Some(0x74) => self.get_true(),
             // null
             Some(0x6e) => self.get_null(),
-            _ => Err(Error::parse_err("expect value, found no data")),
+            _ => Err(self.unexpected(ParseErrorKind::ExpectedValue)),
         }
     }
 }
+
+impl<'a> JsonLoader<u8, SliceBytes<'a>> {
+    /// Loads the whole input to `JsonValueRef`, which borrows from the input.
+    pub fn load_borrowed(&mut self) -> Result<JsonValueRef<'a>> {
+        self.depth = 0;
+        let val = self._load_borrowed()?;
+        self.finish()?;
+        Ok(val)
+    }
+
+    /// Reads a string after the opening quotation mark, borrowing it unless it has escapes.
+    #[inline]
+    fn get_borrowed_string(&mut self, quote: u8) -> Result<Cow<'a, str>> {
+        let start = self.bytes.position();
+        self.read_string(quote)?;
+        let raw = self.bytes.slice(start, self.bytes.position() - 1);
+        // An escape is always longer than the character, so the same length means no escapes.
+        unsafe {
+            if raw.len() == self.buffer.len() {
+                Ok(Cow::Borrowed(std::str::from_utf8_unchecked(raw)))
+            } else {
+                Ok(Cow::Owned(String::from_utf8_unchecked(self.buffer.clone())))
+            }
+        }
+    }
+
+    #[inline]
+    fn get_borrowed_number(&mut self) -> Result<&'a str> {
+        let start = self.bytes.position();
+        self.read_number()?;
+        // Release the anchor.
+        self.bytes.read();
+        unsafe {
+            Ok(std::str::from_utf8_unchecked(
+                self.bytes.slice(start, self.bytes.position()),
+            ))
+        }
+    }
+
+    #[inline]
+    fn borrowed_object_key(&mut self, index: usize) -> Result<Option<(Cow<'a, str>, usize)>> {
+        let (quote, key_pos) = match self.next_key(index)? {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+        let key = match quote {
+            Some(quote) => self.get_borrowed_string(quote)?,
+            None => {
+                self.read_identifier()?;
+                self.bytes.read();
+                let raw = self.bytes.slice(key_pos, self.bytes.position());
+                match std::str::from_utf8(raw) {
+                    Ok(key) => Cow::Borrowed(key),
+                    Err(_) => return Err(self.error_at(ParseErrorKind::InvalidUtf8, key_pos)),
+                }
+            }
+        };
+        self.expect_colon()?;
+        Ok(Some((key, key_pos)))
+    }
+
+    fn _load_borrowed(&mut self) -> Result<JsonValueRef<'a>> {
+        self.skip_to_value()?;
+        match self.bytes.peek() {
+            // string
+            Some(0x22) => {
+                self.bytes.skip();
+                Ok(JsonValueRef::String(self.get_borrowed_string(0x22)?))
+            }
+            Some(0x27) if self.allow_single_quotes => {
+                self.bytes.skip();
+                Ok(JsonValueRef::String(self.get_borrowed_string(0x27)?))
+            }
+            // number
+            Some(0x30..=0x39 | 0x2d) => Ok(JsonValueRef::Number(self.get_borrowed_number()?)),
+            // array
+            Some(0x5b) => {
+                self.enter()?;
+                self.bytes.skip();
+                let mut vec = Vec::new();
+                while self.array_item(vec.len())? {
+                    vec.push(self._load_borrowed()?);
+                }
+                Ok(JsonValueRef::Array(vec))
+            }
+            // object
+            Some(0x7b) => {
+                self.enter()?;
+                self.bytes.skip();
+                let mut dict = Map::new();
+                let mut collected = BTreeSet::new();
+                let mut index = 0;
+                while let Some((key, key_pos)) = self.borrowed_object_key(index)? {
+                    index += 1;
+                    self.load_member(
+                        &mut dict,
+                        &mut collected,
+                        key,
+                        key_pos,
+                        Self::_load_borrowed,
+                    )?;
+                }
+                Ok(JsonValueRef::Object(dict))
+            }
+            // false
+            Some(0x66) => self.get_false().map(|_| JsonValueRef::Bool(false)),
+            // true
+            Some(0x74) => self.get_true().map(|_| JsonValueRef::Bool(true)),
+            // null
+            Some(0x6e) => self.get_null().map(|_| JsonValueRef::Null),
+            _ => Err(self.unexpected(ParseErrorKind::ExpectedValue)),
+        }
+    }
+}
+
+/// A trait defines as the format to load an instance directly from `JsonLoader`.
+///
+/// By default, the next value is loaded to `JsonValue` and converted by `FromJsonValue`.
+/// An implementation must consume the whole value unless it returns a syntax or I/O error.
+pub trait FromJsonStream: FromJsonValue {
+    fn from_json_stream<S: Stream<u8>>(loader: &mut JsonLoader<u8, S>) -> Result<Self> {
+        Self::from_json_value(&loader.load_value()?)
+    }
+
+    /// Loads the same as `from_json_stream`, except that an integer accepts only a literal
+    /// of an integer, the same as `FromJsonValue::from_json_value_strict`.
+    fn from_json_stream_strict<S: Stream<u8>>(loader: &mut JsonLoader<u8, S>) -> Result<Self> {
+        Self::from_json_stream(loader)
+    }
+}
+
+impl FromJsonStream for () {}
+
+impl FromJsonStream for bool {}
+
+macro_rules! from_json_stream_for_int {
+    ( $( $i:ident ),* ) => {
+        $(
+            impl FromJsonStream for $i {
+                fn from_json_stream_strict<S: Stream<u8>>(
+                    loader: &mut JsonLoader<u8, S>,
+                ) -> Result<Self> {
+                    Self::from_json_value_strict(&loader.load_value()?)
+                }
+            }
+        )*
+    };
+}
+
+from_json_stream_for_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl FromJsonStream for f32 {}
+
+impl FromJsonStream for f64 {}
+
+impl FromJsonStream for String {
+    fn from_json_stream<S: Stream<u8>>(loader: &mut JsonLoader<u8, S>) -> Result<Self> {
+        match loader.load_value()? {
+            JsonValue::String(s) => Ok(s),
+            value => Self::from_json_value(&value),
+        }
+    }
+}
+
+/// Loads each item by `load`, which is either the strict one or not.
+fn vec_from_json_stream<T: FromJsonStream, S: Stream<u8>>(
+    loader: &mut JsonLoader<u8, S>,
+    load: fn(&mut JsonLoader<u8, S>) -> Result<Result<T>>,
+    convert: fn(&JsonValue) -> Result<Vec<T>>,
+) -> Result<Vec<T>> {
+    if !loader.begin_array()? {
+        return convert(&loader.load_value()?);
+    }
+    let mut buffer = Vec::new();
+    let mut errors = Vec::new();
+    loader.load_items(|loader, idx| {
+        match load(loader)? {
+            Ok(val) => buffer.push(val),
+            Err(err) => errors.push(err.at_index(idx)),
+        }
+        Ok(())
+    })?;
+    if errors.is_empty() {
+        Ok(buffer)
+    } else {
+        Err(Error::merge(errors))
+    }
+}
+
+impl<T: FromJsonStream> FromJsonStream for Vec<T> {
+    fn from_json_stream<S: Stream<u8>>(loader: &mut JsonLoader<u8, S>) -> Result<Self> {
+        vec_from_json_stream(loader, JsonLoader::load_field, Self::from_json_value)
+    }
+
+    fn from_json_stream_strict<S: Stream<u8>>(loader: &mut JsonLoader<u8, S>) -> Result<Self> {
+        vec_from_json_stream(
+            loader,
+            JsonLoader::load_field_strict,
+            Self::from_json_value_strict,
+        )
+    }
+}
+
+impl<T: FromJsonStream> FromJsonStream for Option<T> {
+    fn from_json_stream<S: Stream<u8>>(loader: &mut JsonLoader<u8, S>) -> Result<Self> {
+        if loader.load_null()? {
+            Ok(None)
+        } else {
+            Ok(Some(T::from_json_stream(loader)?))
+        }
+    }
+
+    fn from_json_stream_strict<S: Stream<u8>>(loader: &mut JsonLoader<u8, S>) -> Result<Self> {
+        if loader.load_null()? {
+            Ok(None)
+        } else {
+            Ok(Some(T::from_json_stream_strict(loader)?))
+        }
+    }
+}
+
+impl<T: FromJsonStream> FromJsonStream for Box<T> {
+    fn from_json_stream<S: Stream<u8>>(loader: &mut JsonLoader<u8, S>) -> Result<Self> {
+        Ok(Box::new(T::from_json_stream(loader)?))
+    }
+
+    fn from_json_stream_strict<S: Stream<u8>>(loader: &mut JsonLoader<u8, S>) -> Result<Self> {
+        Ok(Box::new(T::from_json_stream_strict(loader)?))
+    }
+}
@@ -0,0 +1,169 @@
+/// Builds `JsonValue` from a JSON-like literal.
+///
+/// `null`, `true`, `false`, arrays and objects are written as in JSON, and any other value is
+/// an expression of a type implementing `ToJsonValue`, which is borrowed rather than moved.
+/// A key is an expression converted by `ToString`, and parentheses are needed for a key of
+/// multiple tokens, such as `(format!("k{}", idx))`.
+///
+/// For example,
+///
+/// ```rust
+/// use dade::{json, json_dump};
+///
+/// let name = "dade".to_string();
+/// let tags = vec!["json", "model"];
+/// let value = json!({
+///     "name": name,
+///     "version": [0, 1.5, null],
+///     "tags": tags,
+///     "nested": {"empty": [], "ok": true},
+///     (name.to_uppercase()): -1,
+/// });
+/// assert_eq!(
+///     json_dump(&value, false),
+///     "{\"name\":\"dade\",\"version\":[0,1.5,null],\"tags\":[\"json\",\"model\"],\
+///      \"nested\":{\"empty\":[],\"ok\":true},\"DADE\":-1}"
+/// );
+/// assert_eq!(value["nested"]["ok"], json!(true));
+/// ```
+#[macro_export]
+macro_rules! json {
+    ( $( $json:tt )+ ) => {
+        $crate::json_internal!($($json)+)
+    };
+}
+
+/// The implementation of `json!`, which is not a public API.
+///
+/// Arrays and objects are munched token by token, since an element or a value is either
+/// a JSON-like literal or an expression up to the next comma.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! json_internal {
+    // Arrays, where elements are accumulated in the brackets.
+    (@array [ $( $elems:expr, )* ]) => {
+        ::std::vec![$($elems,)*]
+    };
+    (@array [ $( $elems:expr ),* ]) => {
+        ::std::vec![$($elems),*]
+    };
+    (@array [ $( $elems:expr, )* ] null $( $rest:tt )*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!(null)] $($rest)*)
+    };
+    (@array [ $( $elems:expr, )* ] true $( $rest:tt )*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!(true)] $($rest)*)
+    };
+    (@array [ $( $elems:expr, )* ] false $( $rest:tt )*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!(false)] $($rest)*)
+    };
+    (@array [ $( $elems:expr, )* ] [ $( $array:tt )* ] $( $rest:tt )*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!([$($array)*])] $($rest)*)
+    };
+    (@array [ $( $elems:expr, )* ] { $( $object:tt )* } $( $rest:tt )*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!({$($object)*})] $($rest)*)
+    };
+    (@array [ $( $elems:expr, )* ] $next:expr, $( $rest:tt )*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!($next),] $($rest)*)
+    };
+    (@array [ $( $elems:expr, )* ] $last:expr) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!($last)])
+    };
+    (@array [ $( $elems:expr ),* ] , $( $rest:tt )*) => {
+        $crate::json_internal!(@array [$($elems,)*] $($rest)*)
+    };
+    (@array [ $( $elems:expr ),* ] $unexpected:tt $( $rest:tt )*) => {
+        $crate::json_unexpected!($unexpected)
+    };
+
+    // Objects, as `@object map (key) (rest) (rest)`, where the key is accumulated in the
+    // parentheses and the rest is copied to point at an unexpected token.
+    (@object $object:ident () () ()) => {};
+    (@object $object:ident [ $( $key:tt )+ ] ($value:expr) , $( $rest:tt )*) => {
+        let _ = $object.insert(::std::string::ToString::to_string(&($($key)+)), $value);
+        $crate::json_internal!(@object $object () ($($rest)*) ($($rest)*));
+    };
+    (@object $object:ident [ $( $key:tt )+ ] ($value:expr) $unexpected:tt $( $rest:tt )*) => {
+        $crate::json_unexpected!($unexpected);
+    };
+    (@object $object:ident [ $( $key:tt )+ ] ($value:expr)) => {
+        let _ = $object.insert(::std::string::ToString::to_string(&($($key)+)), $value);
+    };
+    (@object $object:ident ( $( $key:tt )+ ) (: null $( $rest:tt )*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!(null)) $($rest)*);
+    };
+    (@object $object:ident ( $( $key:tt )+ ) (: true $( $rest:tt )*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!(true)) $($rest)*);
+    };
+    (@object $object:ident ( $( $key:tt )+ ) (: false $( $rest:tt )*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!(false)) $($rest)*);
+    };
+    (@object $object:ident ( $( $key:tt )+ ) (: [ $( $array:tt )* ] $( $rest:tt )*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!([$($array)*])) $($rest)*);
+    };
+    (@object $object:ident ( $( $key:tt )+ ) (: { $( $map:tt )* } $( $rest:tt )*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!({$($map)*})) $($rest)*);
+    };
+    (@object $object:ident ( $( $key:tt )+ ) (: $value:expr , $( $rest:tt )*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!($value)) , $($rest)*);
+    };
+    (@object $object:ident ( $( $key:tt )+ ) (: $value:expr) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!($value)));
+    };
+    // A value is missing.
+    (@object $object:ident ( $( $key:tt )+ ) (:) $copy:tt) => {
+        $crate::json_internal!();
+    };
+    (@object $object:ident ( $( $key:tt )+ ) () $copy:tt) => {
+        $crate::json_internal!();
+    };
+    // A key is missing.
+    (@object $object:ident () (: $( $rest:tt )*) ($colon:tt $( $copy:tt )*)) => {
+        $crate::json_unexpected!($colon);
+    };
+    (@object $object:ident ( $( $key:tt )* ) (, $( $rest:tt )*) ($comma:tt $( $copy:tt )*)) => {
+        $crate::json_unexpected!($comma);
+    };
+    (@object $object:ident () (( $key:expr ) : $( $rest:tt )*) $copy:tt) => {
+        $crate::json_internal!(@object $object ($key) (: $($rest)*) (: $($rest)*));
+    };
+    (@object $object:ident ( $( $key:tt )* ) ($tt:tt $( $rest:tt )*) $copy:tt) => {
+        $crate::json_internal!(@object $object ($($key)* $tt) ($($rest)*) ($($rest)*));
+    };
+
+    // Values.
+    (null) => {
+        $crate::JsonValue::Null
+    };
+    (true) => {
+        $crate::JsonValue::Bool(true)
+    };
+    (false) => {
+        $crate::JsonValue::Bool(false)
+    };
+    ([]) => {
+        $crate::JsonValue::Array(::std::vec::Vec::new())
+    };
+    ([ $( $tt:tt )+ ]) => {
+        $crate::JsonValue::Array($crate::json_internal!(@array [] $($tt)+))
+    };
+    ({}) => {
+        $crate::JsonValue::Object($crate::Map::new())
+    };
+    ({ $( $tt:tt )+ }) => {
+        $crate::JsonValue::Object({
+            let mut object = $crate::Map::new();
+            $crate::json_internal!(@object object () ($($tt)+) ($($tt)+));
+            object
+        })
+    };
+    ($other:expr) => {
+        $crate::ToJsonValue::to_json_value(&$other)
+    };
+}
+
+/// Reports an unexpected token of `json!` at its span, since no rule accepts any token.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! json_unexpected {
+    () => {};
+}
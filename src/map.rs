@@ -0,0 +1,304 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The number of members above which keys are looked up by a hash table instead of a linear search.
+const INDEX_THRESHOLD: usize = 16;
+
+type Iter<'a, K, V> =
+    std::iter::Map<std::slice::Iter<'a, (K, V)>, fn(&'a (K, V)) -> (&'a K, &'a V)>;
+type IterMut<'a, K, V> =
+    std::iter::Map<std::slice::IterMut<'a, (K, V)>, fn(&'a mut (K, V)) -> (&'a K, &'a mut V)>;
+
+/// A map which keeps the insertion order of keys, for members of a JSON object.
+///
+/// Inserting an existing key replaces the value in place, and removing a key shifts the members
+/// after it, so the order is always the order in which the keys first appeared.
+///
+/// For example,
+///
+/// ```rust
+/// use dade::{json_dump, json_load, JsonValue, Map};
+///
+/// let mut dict = Map::new();
+/// dict.insert("b".to_string(), JsonValue::Bool(true));
+/// dict.insert("a".to_string(), JsonValue::Null);
+/// assert_eq!(json_dump(&JsonValue::Object(dict), false), "{\"b\":true,\"a\":null}");
+///
+/// let value = json_load("{\"z\": 1, \"y\": 2}").unwrap();
+/// assert_eq!(json_dump(&value, false), "{\"z\":1,\"y\":2}");
+/// ```
+pub struct Map<K, V> {
+    entries: Vec<(K, V)>,
+    /// The positions of keys, which is built only for a large map.
+    index: Option<HashMap<K, usize>>,
+}
+
+impl<K, V> Map<K, V> {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            index: None,
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+            index: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over members in order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.entries.iter().map(|(key, val)| (key, val))
+    }
+
+    /// Returns an iterator over members in order, with mutable references to values.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.entries.iter_mut().map(|(key, val)| (&*key, val))
+    }
+
+    /// Returns an iterator over keys in order.
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator {
+        self.entries.iter().map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over values in order.
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator {
+        self.entries.iter().map(|(_, val)| val)
+    }
+
+    /// Returns an iterator over mutable references to values in order.
+    pub fn values_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut V> + ExactSizeIterator {
+        self.entries.iter_mut().map(|(_, val)| val)
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Map<K, V> {
+    fn position<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match &self.index {
+            Some(index) => index.get(key).copied(),
+            None => self.entries.iter().position(|(k, _)| k.borrow() == key),
+        }
+    }
+
+    fn rebuild_index(&mut self) {
+        self.index = if self.entries.len() > INDEX_THRESHOLD {
+            Some(
+                self.entries
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, (key, _))| (key.clone(), idx))
+                    .collect(),
+            )
+        } else {
+            None
+        };
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.position(key).map(|idx| &self.entries[idx].1)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.position(key).map(|idx| &mut self.entries[idx].1)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.position(key).is_some()
+    }
+
+    /// Inserts a member, returning the previous value of the key.
+    ///
+    /// A new key is appended to the end, and an existing key keeps its position.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(idx) = self.position(&key) {
+            return Some(std::mem::replace(&mut self.entries[idx].1, value));
+        }
+        match &mut self.index {
+            Some(index) => {
+                index.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+            }
+            None => {
+                self.entries.push((key, value));
+                if self.entries.len() > INDEX_THRESHOLD {
+                    self.rebuild_index();
+                }
+            }
+        }
+        None
+    }
+
+    /// Removes a member, returning its value. The members after it are shifted.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.position(key)?;
+        let (removed, value) = self.entries.remove(idx);
+        if let Some(index) = &mut self.index {
+            index.remove::<K>(&removed);
+            for (key, _) in &self.entries[idx..] {
+                if let Some(pos) = index.get_mut::<K>(key) {
+                    *pos -= 1;
+                }
+            }
+        }
+        Some(value)
+    }
+
+    /// Removes a member, returning its value. The last member is moved to its position,
+    /// so this does not keep the order but takes constant time.
+    pub fn swap_remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.position(key)?;
+        let (removed, value) = self.entries.swap_remove(idx);
+        if let Some(index) = &mut self.index {
+            index.remove::<K>(&removed);
+            if let Some((moved, _)) = self.entries.get(idx) {
+                index.insert(moved.clone(), idx);
+            }
+        }
+        Some(value)
+    }
+
+    /// Sorts members by keys.
+    pub fn sort_keys(&mut self)
+    where
+        K: Ord,
+    {
+        self.entries.sort_by(|a, b| a.0.cmp(&b.0));
+        if self.index.is_some() {
+            self.rebuild_index();
+        }
+    }
+}
+
+impl<K: Clone, V: Clone> Clone for Map<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+            index: self.index.clone(),
+        }
+    }
+}
+
+impl<K: std::fmt::Debug, V: std::fmt::Debug> std::fmt::Debug for Map<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Maps are equal if they have the same members, regardless of the order.
+impl<K: Hash + Eq + Clone, V: PartialEq> PartialEq for Map<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Eq> Eq for Map<K, V> {}
+
+impl<K, V> Default for Map<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, Q> std::ops::Index<&Q> for Map<K, V>
+where
+    K: Hash + Eq + Clone + Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value of the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not in the map.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Extend<(K, V)> for Map<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> FromIterator<(K, V)> for Map<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Map::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Hash + Eq + Clone, V, const N: usize> From<[(K, V); N]> for Map<K, V> {
+    fn from(arr: [(K, V); N]) -> Self {
+        arr.into_iter().collect()
+    }
+}
+
+impl<K, V> IntoIterator for Map<K, V> {
+    type Item = (K, V);
+    type IntoIter = std::vec::IntoIter<(K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a Map<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut Map<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
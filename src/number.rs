@@ -0,0 +1,510 @@
+use std::cmp::Ordering;
+use std::num::{ParseFloatError, ParseIntError};
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::json::JsonValue;
+use crate::load::JsonLoader;
+
+/// A number in JSON, which keeps the literal as it is to dump it without loss.
+///
+/// The value is compared by its exact value, e.g. `1`, `1.0` and `10e-1` are equal,
+/// and it can be converted to a primitive type only if the conversion is exact.
+///
+/// For example,
+///
+/// ```rust
+/// use dade::{json_load, JsonValue, Number};
+///
+/// let num = match json_load("1.50e1").unwrap() {
+///     JsonValue::Number(num) => num,
+///     _ => unreachable!(),
+/// };
+/// assert_eq!(num.to_string(), "1.50e1");
+/// assert!(num.is_integer());
+/// assert_eq!(num.as_u64(), Some(15));
+/// assert!(num == Number::from(15));
+///
+/// assert!(Number::from_f64(f64::NAN).is_none());
+/// assert_eq!(Number::from_f64(0.5).unwrap().as_f64(), Some(0.5));
+/// ```
+pub struct Number {
+    value: String,
+}
+
+/// The exact value of a number literal, which is `digits * 10^exponent`.
+///
+/// `digits` has neither leading nor trailing zeros, and is empty for zero.
+struct Decimal<'a> {
+    negative: bool,
+    digits: Vec<&'a [u8]>,
+    exponent: Exponent,
+}
+
+/// The number of digits of an exponent which is kept in `i128` as it is.
+const SMALL_EXPONENT_DIGITS: usize = 36;
+
+/// The exact exponent of `Decimal`, since a literal can have an exponent of any length.
+#[derive(PartialEq)]
+enum Exponent {
+    /// An exponent whose absolute value is less than `10^SMALL_EXPONENT_DIGITS`.
+    Small(i128),
+    /// The sign and the digits without leading zeros of a larger exponent.
+    Huge(bool, Vec<u8>),
+}
+
+impl Exponent {
+    /// Makes from the literal of an exponent, adding `shift` to it.
+    fn new(literal: &[u8], shift: i128) -> Self {
+        let (negative, digits) = match literal.first() {
+            Some(b'-') => (true, &literal[1..]),
+            Some(b'+') => (false, &literal[1..]),
+            _ => (false, literal),
+        };
+        let zeros = digits.iter().take_while(|&&b| b == b'0').count();
+        let digits = &digits[zeros..];
+        if digits.len() <= SMALL_EXPONENT_DIGITS {
+            let value = Self::parse(digits);
+            return Self::from_i128(if negative {
+                shift - value
+            } else {
+                shift + value
+            });
+        }
+        // The shift is much smaller than the exponent, so the sign is kept.
+        let add = negative == (shift < 0);
+        let mut digits = Vec::from(digits);
+        let mut carry = shift.unsigned_abs();
+        for digit in digits.iter_mut().rev() {
+            if carry == 0 {
+                break;
+            }
+            let diff = (carry % 10) as u8;
+            carry /= 10;
+            if add {
+                *digit += diff;
+                if *digit > b'9' {
+                    *digit -= 10;
+                    carry += 1;
+                }
+            } else if *digit - b'0' >= diff {
+                *digit -= diff;
+            } else {
+                *digit += 10 - diff;
+                carry += 1;
+            }
+        }
+        if carry > 0 {
+            digits.splice(0..0, carry.to_string().into_bytes());
+        }
+        let zeros = digits.iter().take_while(|&&b| b == b'0').count();
+        digits.drain(..zeros);
+        if digits.len() <= SMALL_EXPONENT_DIGITS {
+            let value = Self::parse(&digits);
+            Self::Small(if negative { -value } else { value })
+        } else {
+            Self::Huge(negative, digits)
+        }
+    }
+
+    fn from_i128(value: i128) -> Self {
+        if value.unsigned_abs() < 10u128.pow(SMALL_EXPONENT_DIGITS as u32) {
+            Self::Small(value)
+        } else {
+            Self::Huge(value < 0, value.unsigned_abs().to_string().into_bytes())
+        }
+    }
+
+    fn parse(digits: &[u8]) -> i128 {
+        digits
+            .iter()
+            .fold(0, |acc, &b| acc * 10 + (b - b'0') as i128)
+    }
+
+    fn is_negative(&self) -> bool {
+        match self {
+            Self::Small(value) => *value < 0,
+            Self::Huge(negative, _) => *negative,
+        }
+    }
+}
+
+impl<'a> Decimal<'a> {
+    /// Splits a literal which conforms to JSON.
+    fn new(literal: &'a str) -> Self {
+        let bytes = literal.as_bytes();
+        let negative = bytes.first() == Some(&b'-');
+        let body = if negative { &bytes[1..] } else { bytes };
+        let (mantissa, exp) = match body.iter().position(|&b| b == b'e' || b == b'E') {
+            Some(pos) => (&body[..pos], &body[pos + 1..]),
+            None => (body, &body[body.len()..]),
+        };
+        let (int, frac) = match mantissa.iter().position(|&b| b == b'.') {
+            Some(pos) => (&mantissa[..pos], &mantissa[pos + 1..]),
+            None => (mantissa, &mantissa[mantissa.len()..]),
+        };
+        let mut digits = Vec::from([int, frac]);
+        let mut shift = -(frac.len() as i128);
+        // Strip leading zeros.
+        while let Some(part) = digits.first_mut() {
+            let zeros = part.iter().take_while(|&&b| b == b'0').count();
+            *part = &part[zeros..];
+            if part.is_empty() {
+                digits.remove(0);
+            } else {
+                break;
+            }
+        }
+        // Strip trailing zeros.
+        while let Some(part) = digits.last_mut() {
+            let zeros = part.iter().rev().take_while(|&&b| b == b'0').count();
+            *part = &part[..part.len() - zeros];
+            shift += zeros as i128;
+            if part.is_empty() {
+                digits.pop();
+            } else {
+                break;
+            }
+        }
+        let exponent = if digits.is_empty() {
+            Exponent::Small(0)
+        } else {
+            Exponent::new(exp, shift)
+        };
+        Self {
+            negative,
+            digits,
+            exponent,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.digits.iter().map(|part| part.len()).sum()
+    }
+
+    fn digits(&self) -> impl Iterator<Item = u8> + '_ {
+        self.digits.iter().flat_map(|part| part.iter().copied())
+    }
+
+    /// Returns the absolute value if it is an integer which fits in `u128`.
+    fn to_u128(&self) -> Option<u128> {
+        let exponent = match self.exponent {
+            Exponent::Small(exponent) if exponent >= 0 => exponent,
+            _ => return None,
+        };
+        if self.len() as i128 + exponent > 39 {
+            return None;
+        }
+        let mut value: u128 = 0;
+        for b in self.digits() {
+            value = value.checked_mul(10)?.checked_add((b - b'0') as u128)?;
+        }
+        for _ in 0..exponent {
+            value = value.checked_mul(10)?;
+        }
+        Some(value)
+    }
+
+    /// Returns true if the value is exactly `val`, which is finite and not zero.
+    fn is_exact(&self, val: f64) -> bool {
+        // Every `f64` is `digits * 10^exponent` within 767 digits and the exponent in this range.
+        let exponent = match self.exponent {
+            Exponent::Small(exponent) if (-1074..=308).contains(&exponent) => exponent as i32,
+            _ => return false,
+        };
+        if self.len() > 767 {
+            return false;
+        }
+        // Decompose `val` into `mantissa * 2^power`.
+        let bits = val.to_bits();
+        let biased = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = bits & ((1 << 52) - 1);
+        let (mantissa, power) = if biased == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | 1 << 52, biased - 1075)
+        };
+        // Compare `digits * 5^exponent * 2^exponent` with `mantissa * 2^power` as integers,
+        // moving the negative powers to the other side.
+        let mut lhs = Big(Vec::new());
+        for b in self.digits() {
+            lhs.mul_add(10, (b - b'0') as u32);
+        }
+        let mut rhs = Big::from_u64(mantissa);
+        if exponent >= 0 {
+            lhs.mul_pow5(exponent as u32);
+        } else {
+            rhs.mul_pow5(exponent.unsigned_abs());
+        }
+        if exponent >= power {
+            lhs.shl((exponent - power) as u32);
+        } else {
+            rhs.shl((power - exponent) as u32);
+        }
+        lhs.0 == rhs.0
+    }
+}
+
+/// An unsigned integer of any size, whose limbs are in little endian without zeros at the top.
+struct Big(Vec<u32>);
+
+impl Big {
+    fn from_u64(val: u64) -> Self {
+        let mut big = Self(Vec::from([val as u32, (val >> 32) as u32]));
+        while big.0.last() == Some(&0) {
+            big.0.pop();
+        }
+        big
+    }
+
+    /// Multiplies by `mul`, then adds `add`.
+    fn mul_add(&mut self, mul: u32, add: u32) {
+        let mut carry = add as u64;
+        for limb in self.0.iter_mut() {
+            let val = *limb as u64 * mul as u64 + carry;
+            *limb = val as u32;
+            carry = val >> 32;
+        }
+        if carry > 0 {
+            self.0.push(carry as u32);
+        }
+    }
+
+    fn mul_pow5(&mut self, mut exp: u32) {
+        // 5^13 is the largest power which fits in `u32`.
+        while exp > 0 {
+            let step = exp.min(13);
+            self.mul_add(5u32.pow(step), 0);
+            exp -= step;
+        }
+    }
+
+    fn shl(&mut self, bits: u32) {
+        if self.0.is_empty() {
+            return;
+        }
+        let (limbs, bits) = ((bits / 32) as usize, bits % 32);
+        if bits > 0 {
+            self.mul_add(1 << bits, 0);
+        }
+        self.0.splice(0..0, std::iter::repeat_n(0, limbs));
+    }
+}
+
+impl<'a> PartialEq for Decimal<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.is_zero() || other.is_zero() {
+            return self.is_zero() && other.is_zero();
+        }
+        self.negative == other.negative
+            && self.exponent == other.exponent
+            && self.digits().cmp(other.digits()) == Ordering::Equal
+    }
+}
+
+/// Converts a literal which conforms to JSON to an integer by the exact value,
+/// e.g. `1.0`, `1e3` and `-0` are integers.
+pub(crate) fn integer_from_literal<T>(literal: &str) -> std::result::Result<T, &'static str>
+where
+    T: TryFrom<i128> + TryFrom<u128>,
+{
+    let decimal = Decimal::new(literal);
+    if decimal.exponent.is_negative() {
+        return Err("number is not an integer");
+    }
+    let overflow = if decimal.negative {
+        "number too small to fit in target type"
+    } else {
+        "number too large to fit in target type"
+    };
+    let value = decimal.to_u128().ok_or(overflow)?;
+    if decimal.negative && value != 0 {
+        let value = 0i128.checked_sub_unsigned(value).ok_or(overflow)?;
+        <T as TryFrom<i128>>::try_from(value).map_err(|_| overflow)
+    } else {
+        <T as TryFrom<u128>>::try_from(value).map_err(|_| overflow)
+    }
+}
+
+/// A primitive type converted from a literal of a number,
+/// which is shared by `JsonValue` and `JsonValueRef` so that they convert the same.
+pub(crate) trait FromLiteral: Sized {
+    /// Converts a literal, where an integer accepts a number whose exact value is an integer.
+    fn from_literal(literal: &str) -> std::result::Result<Self, String>;
+
+    /// Converts a literal, where an integer accepts only a literal of an integer.
+    fn from_literal_strict(literal: &str) -> std::result::Result<Self, String> {
+        Self::from_literal(literal)
+    }
+}
+
+macro_rules! from_literal_for_int {
+    ( $( $i:ident ),* ) => {
+        $(
+            impl FromLiteral for $i {
+                fn from_literal(literal: &str) -> std::result::Result<Self, String> {
+                    integer_from_literal(literal).map_err(|msg| msg.to_string())
+                }
+
+                fn from_literal_strict(literal: &str) -> std::result::Result<Self, String> {
+                    literal.parse().map_err(|err: ParseIntError| err.to_string())
+                }
+            }
+        )*
+    };
+}
+
+from_literal_for_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! from_literal_for_float {
+    ( $( $i:ident ),* ) => {
+        $(
+            impl FromLiteral for $i {
+                fn from_literal(literal: &str) -> std::result::Result<Self, String> {
+                    literal.parse().map_err(|err: ParseFloatError| err.to_string())
+                }
+            }
+        )*
+    };
+}
+
+from_literal_for_float!(f32, f64);
+
+impl Number {
+    /// Makes from a literal which conforms to JSON, which is not checked.
+    pub(crate) fn new(value: String) -> Self {
+        Self { value }
+    }
+
+    /// Makes from `f64`, which is `None` if the value is NaN or infinite.
+    pub fn from_f64(val: f64) -> Option<Self> {
+        if val.is_finite() {
+            Some(Self::new(val.to_string()))
+        } else {
+            None
+        }
+    }
+
+    /// Makes from `f32`, which is `None` if the value is NaN or infinite.
+    pub fn from_f32(val: f32) -> Option<Self> {
+        if val.is_finite() {
+            Some(Self::new(val.to_string()))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the literal as it is.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Parses the literal to `F`, which is the nearest value for a float.
+    pub fn parse<F: FromStr>(&self) -> std::result::Result<F, F::Err> {
+        self.value.parse()
+    }
+
+    /// Returns true if the value is an integer, including a literal such as `1.0` or `1e3`.
+    pub fn is_integer(&self) -> bool {
+        let decimal = Decimal::new(&self.value);
+        !decimal.exponent.is_negative()
+    }
+
+    /// Returns the value if it is an integer which fits in `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        let decimal = Decimal::new(&self.value);
+        let value = decimal.to_u128()?;
+        if decimal.negative {
+            0i64.checked_sub_unsigned(u64::try_from(value).ok()?)
+        } else {
+            i64::try_from(value).ok()
+        }
+    }
+
+    /// Returns the value if it is an integer which fits in `u64`.
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_u128().and_then(|value| u64::try_from(value).ok())
+    }
+
+    /// Returns the value if it is an integer which fits in `u128`.
+    pub fn as_u128(&self) -> Option<u128> {
+        let decimal = Decimal::new(&self.value);
+        let value = decimal.to_u128()?;
+        if decimal.negative && value != 0 {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// Returns the value if it is exactly representable as `f64`.
+    ///
+    /// For example, `0.5` is `Some`, while `0.1` is `None`; use `parse` for the nearest value.
+    pub fn as_f64(&self) -> Option<f64> {
+        let val: f64 = self.value.parse().ok()?;
+        let decimal = Decimal::new(&self.value);
+        if val.is_finite() && (decimal.is_zero() || val != 0.0 && decimal.is_exact(val)) {
+            Some(val)
+        } else {
+            None
+        }
+    }
+}
+
+macro_rules! number_from_int {
+    ( $( $i:ident ),* ) => {
+        $(
+            impl From<$i> for Number {
+                fn from(val: $i) -> Self {
+                    Self::new(val.to_string())
+                }
+            }
+        )*
+    };
+}
+
+number_from_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Clone for Number {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+        }
+    }
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value || Decimal::new(&self.value) == Decimal::new(&other.value)
+    }
+}
+
+impl Eq for Number {}
+
+impl std::fmt::Debug for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Number({})", self.value)
+    }
+}
+
+impl FromStr for Number {
+    type Err = Error;
+
+    /// Parses a literal which conforms to JSON, without surrounding whitespace.
+    fn from_str(literal: &str) -> Result<Self> {
+        match JsonLoader::from(literal).load()? {
+            JsonValue::Number(num) if num.value == literal => Ok(num),
+            _ => Err(Error::parse_err("expect a number literal")),
+        }
+    }
+}
+
+impl std::fmt::Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.value)
+    }
+}
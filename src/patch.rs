@@ -0,0 +1,323 @@
+use crate::error::{Error, Result};
+use crate::json::JsonValue;
+use crate::map::Map;
+use crate::pointer::{escape_token, pointer_err};
+
+/// An operation of JSON Patch, which borrows pointers and a value from the patch document.
+enum Operation<'a> {
+    Add(&'a str, &'a JsonValue),
+    Remove(&'a str),
+    Replace(&'a str, &'a JsonValue),
+    Move(&'a str, &'a str),
+    Copy(&'a str, &'a str),
+    Test(&'a str, &'a JsonValue),
+}
+
+fn member<'a>(dict: &'a Map<String, JsonValue>, key: &str) -> Result<&'a JsonValue> {
+    dict.get(key).ok_or_else(|| {
+        Error::constraint_err("missing", &format!("not found key, {}", key), None).at_key(key)
+    })
+}
+
+fn pointer_member<'a>(dict: &'a Map<String, JsonValue>, key: &str) -> Result<&'a str> {
+    match member(dict, key)? {
+        JsonValue::String(pointer) => Ok(pointer),
+        value => {
+            Err(
+                Error::constraint_err("type", "expect `JsonValue::String`", Some(value.clone()))
+                    .at_key(key),
+            )
+        }
+    }
+}
+
+impl<'a> Operation<'a> {
+    fn new(value: &'a JsonValue) -> Result<Self> {
+        let dict = match value {
+            JsonValue::Object(dict) => dict,
+            _ => {
+                return Err(Error::constraint_err(
+                    "type",
+                    "expect `JsonValue::Object`",
+                    Some(value.clone()),
+                ))
+            }
+        };
+        let op = member(dict, "op")?;
+        match op.as_str() {
+            Some("add") => Ok(Operation::Add(
+                pointer_member(dict, "path")?,
+                member(dict, "value")?,
+            )),
+            Some("remove") => Ok(Operation::Remove(pointer_member(dict, "path")?)),
+            Some("replace") => Ok(Operation::Replace(
+                pointer_member(dict, "path")?,
+                member(dict, "value")?,
+            )),
+            Some("move") => Ok(Operation::Move(
+                pointer_member(dict, "from")?,
+                pointer_member(dict, "path")?,
+            )),
+            Some("copy") => Ok(Operation::Copy(
+                pointer_member(dict, "from")?,
+                pointer_member(dict, "path")?,
+            )),
+            Some("test") => Ok(Operation::Test(
+                pointer_member(dict, "path")?,
+                member(dict, "value")?,
+            )),
+            _ => Err(Error::constraint_err(
+                "enum",
+                "expect one of `add`, `remove`, `replace`, `move`, `copy` or `test`",
+                Some(op.clone()),
+            )
+            .at_key("op")),
+        }
+    }
+
+    fn apply(&self, target: &mut JsonValue) -> Result<()> {
+        match *self {
+            Operation::Add(path, value) => {
+                target
+                    .pointer_insert(path, value.clone())
+                    .map_err(|err| err.at_key("path"))?;
+            }
+            Operation::Remove(path) => {
+                remove(target, path).map_err(|err| err.at_key("path"))?;
+            }
+            Operation::Replace(path, value) => {
+                let old = target
+                    .pointer_mut(path)
+                    .ok_or_else(|| pointer_err("no value at the pointer", path).at_key("path"))?;
+                *old = value.clone();
+            }
+            Operation::Move(from, path) => {
+                // A value cannot be moved into itself.
+                if path.len() > from.len()
+                    && path.starts_with(from)
+                    && path[from.len()..].starts_with('/')
+                {
+                    return Err(
+                        pointer_err("expect a pointer outside of `from`", path).at_key("path")
+                    );
+                }
+                let value = remove(target, from).map_err(|err| err.at_key("from"))?;
+                target
+                    .pointer_insert(path, value)
+                    .map_err(|err| err.at_key("path"))?;
+            }
+            Operation::Copy(from, path) => {
+                let value = target
+                    .pointer(from)
+                    .ok_or_else(|| pointer_err("no value at the pointer", from).at_key("from"))?
+                    .clone();
+                target
+                    .pointer_insert(path, value)
+                    .map_err(|err| err.at_key("path"))?;
+            }
+            Operation::Test(path, value) => {
+                let actual = target
+                    .pointer(path)
+                    .ok_or_else(|| pointer_err("no value at the pointer", path).at_key("path"))?;
+                if actual != value {
+                    return Err(Error::constraint_err(
+                        "test",
+                        "value is not equal",
+                        Some(actual.clone()),
+                    )
+                    .with_limit(value.clone())
+                    .at_key("value"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn remove(target: &mut JsonValue, pointer: &str) -> Result<JsonValue> {
+    target
+        .pointer_remove(pointer)
+        .ok_or_else(|| pointer_err("no value at the pointer", pointer))
+}
+
+fn push_operation(ops: &mut Vec<JsonValue>, op: &str, path: &str, value: Option<&JsonValue>) {
+    let mut dict = Map::from([
+        ("op".to_string(), JsonValue::String(op.to_string())),
+        ("path".to_string(), JsonValue::String(path.to_string())),
+    ]);
+    if let Some(value) = value {
+        dict.insert("value".to_string(), value.clone());
+    }
+    ops.push(JsonValue::Object(dict));
+}
+
+fn diff_into(source: &JsonValue, target: &JsonValue, path: &mut String, ops: &mut Vec<JsonValue>) {
+    if source == target {
+        return;
+    }
+    let len = path.len();
+    match (source, target) {
+        (JsonValue::Object(src), JsonValue::Object(dst)) => {
+            for key in src.keys().filter(|key| !dst.contains_key(key.as_str())) {
+                path.push('/');
+                path.push_str(&escape_token(key));
+                push_operation(ops, "remove", path, None);
+                path.truncate(len);
+            }
+            for (key, value) in dst.iter() {
+                path.push('/');
+                path.push_str(&escape_token(key));
+                match src.get(key.as_str()) {
+                    Some(old) => diff_into(old, value, path, ops),
+                    None => push_operation(ops, "add", path, Some(value)),
+                }
+                path.truncate(len);
+            }
+        }
+        (JsonValue::Array(src), JsonValue::Array(dst)) => {
+            for (idx, (old, value)) in src.iter().zip(dst.iter()).enumerate() {
+                path.push_str(&format!("/{}", idx));
+                diff_into(old, value, path, ops);
+                path.truncate(len);
+            }
+            // Remove from the end, so that the indices of the rest are kept.
+            for idx in (dst.len()..src.len()).rev() {
+                path.push_str(&format!("/{}", idx));
+                push_operation(ops, "remove", path, None);
+                path.truncate(len);
+            }
+            for (idx, value) in dst.iter().enumerate().skip(src.len()) {
+                path.push_str(&format!("/{}", idx));
+                push_operation(ops, "add", path, Some(value));
+                path.truncate(len);
+            }
+        }
+        _ => push_operation(ops, "replace", path, Some(target)),
+    }
+}
+
+impl JsonValue {
+    /// Applies a JSON Patch (RFC 6902), which is an array of operations.
+    ///
+    /// The operations are applied in order, and the value is unchanged if any of them fails,
+    /// e.g. a pointer refers to nothing or a `test` operation does not match.
+    /// The paths of errors start from the patch, e.g. `[1].path`.
+    ///
+    /// For example,
+    ///
+    /// ```rust
+    /// use dade::json;
+    ///
+    /// let mut value = json!({"a": [1, 2], "b": "x"});
+    /// let patch = json!([
+    ///     {"op": "test", "path": "/b", "value": "x"},
+    ///     {"op": "add", "path": "/a/-", "value": 3},
+    ///     {"op": "move", "from": "/b", "path": "/c"},
+    /// ]);
+    /// value.apply_patch(&patch).unwrap();
+    /// assert_eq!(value, json!({"a": [1, 2, 3], "c": "x"}));
+    ///
+    /// // Nothing is applied if an operation fails.
+    /// let patch = json!([
+    ///     {"op": "remove", "path": "/a"},
+    ///     {"op": "test", "path": "/c", "value": "y"},
+    /// ]);
+    /// assert!(value.apply_patch(&patch).is_err());
+    /// assert_eq!(value, json!({"a": [1, 2, 3], "c": "x"}));
+    /// ```
+    pub fn apply_patch(&mut self, patch: &JsonValue) -> Result<()> {
+        let operations = match patch {
+            JsonValue::Array(arr) => arr
+                .iter()
+                .enumerate()
+                .map(|(idx, op)| Operation::new(op).map_err(|err| err.at_index(idx)))
+                .collect::<Result<Vec<_>>>()?,
+            _ => {
+                return Err(Error::constraint_err(
+                    "type",
+                    "expect `JsonValue::Array`",
+                    Some(patch.clone()),
+                ))
+            }
+        };
+        let mut patched = self.clone();
+        for (idx, op) in operations.iter().enumerate() {
+            op.apply(&mut patched).map_err(|err| err.at_index(idx))?;
+        }
+        *self = patched;
+        Ok(())
+    }
+
+    /// Computes a JSON Patch which changes this value to `target`.
+    ///
+    /// The patch consists of `add`, `remove` and `replace` operations, and applying it to this
+    /// value results in a value equal to `target`.
+    ///
+    /// For example,
+    ///
+    /// ```rust
+    /// use dade::json;
+    ///
+    /// let source = json!({"a": 1, "b": [1, 2], "c/d": null});
+    /// let target = json!({"a": 2, "b": [1], "e": true});
+    /// let patch = source.diff(&target);
+    /// assert_eq!(
+    ///     patch,
+    ///     json!([
+    ///         {"op": "remove", "path": "/c~1d"},
+    ///         {"op": "replace", "path": "/a", "value": 2},
+    ///         {"op": "remove", "path": "/b/1"},
+    ///         {"op": "add", "path": "/e", "value": true},
+    ///     ])
+    /// );
+    ///
+    /// let mut patched = source.clone();
+    /// patched.apply_patch(&patch).unwrap();
+    /// assert_eq!(patched, target);
+    /// ```
+    pub fn diff(&self, target: &JsonValue) -> JsonValue {
+        let mut ops = Vec::new();
+        diff_into(self, target, &mut String::new(), &mut ops);
+        JsonValue::Array(ops)
+    }
+
+    /// Merges a JSON Merge Patch (RFC 7396) into this value.
+    ///
+    /// A member of an object in the patch is merged recursively, where null removes the member,
+    /// and any other value in the patch replaces the value as it is.
+    ///
+    /// For example,
+    ///
+    /// ```rust
+    /// use dade::json;
+    ///
+    /// let mut value = json!({"title": "Hello", "author": {"name": "a", "email": "b"}, "tags": ["x"]});
+    /// value.merge_patch(&json!({"title": "Bye", "author": {"email": null}, "tags": ["y"]}));
+    /// assert_eq!(value, json!({"title": "Bye", "author": {"name": "a"}, "tags": ["y"]}));
+    /// ```
+    pub fn merge_patch(&mut self, patch: &JsonValue) {
+        let members = match patch {
+            JsonValue::Object(members) => members,
+            _ => {
+                *self = patch.clone();
+                return;
+            }
+        };
+        if !matches!(self, JsonValue::Object(_)) {
+            *self = JsonValue::Object(Map::new());
+        }
+        if let JsonValue::Object(dict) = self {
+            for (key, value) in members.iter() {
+                if value.is_null() {
+                    dict.remove(key.as_str());
+                } else if let Some(old) = dict.get_mut(key.as_str()) {
+                    old.merge_patch(value);
+                } else {
+                    let mut new = JsonValue::Null;
+                    new.merge_patch(value);
+                    dict.insert(key.clone(), new);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::error::{Error, LocItem, Result};
+use crate::json::JsonValue;
+
+/// Splits a JSON Pointer (RFC 6901) to unescaped reference tokens.
+///
+/// The empty pointer refers to the whole document, and any other pointer starts with `/`.
+pub(crate) fn parse_pointer(pointer: &str) -> Result<Vec<String>> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    pointer
+        .strip_prefix('/')
+        .ok_or_else(|| pointer_err("invalid JSON Pointer", pointer))?
+        .split('/')
+        .map(|token| unescape(token).ok_or_else(|| pointer_err("invalid JSON Pointer", pointer)))
+        .collect()
+}
+
+/// Escapes a key to a reference token, i.e. `~` to `~0` and `/` to `~1`.
+pub(crate) fn escape_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape(token: &str) -> Option<String> {
+    let mut buffer = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '~' => match chars.next() {
+                Some('0') => buffer.push('~'),
+                Some('1') => buffer.push('/'),
+                _ => return None,
+            },
+            _ => buffer.push(c),
+        }
+    }
+    Some(buffer)
+}
+
+/// Parses an index of an array, which has no leading zeros.
+fn parse_index(token: &str) -> Option<usize> {
+    if token.is_empty()
+        || (token.len() > 1 && token.starts_with('0'))
+        || !token.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    token.parse().ok()
+}
+
+pub(crate) fn pointer_err(message: &str, pointer: &str) -> Error {
+    Error::constraint_err(
+        "pointer",
+        message,
+        Some(JsonValue::String(pointer.to_string())),
+    )
+}
+
+fn step<'v>(value: &'v JsonValue, token: &str) -> Option<&'v JsonValue> {
+    match value {
+        JsonValue::Object(dict) => dict.get(token),
+        JsonValue::Array(arr) => arr.get(parse_index(token)?),
+        _ => None,
+    }
+}
+
+fn step_mut<'v>(value: &'v mut JsonValue, token: &str) -> Option<&'v mut JsonValue> {
+    match value {
+        JsonValue::Object(dict) => dict.get_mut(token),
+        JsonValue::Array(arr) => arr.get_mut(parse_index(token)?),
+        _ => None,
+    }
+}
+
+/// Returns the value referred by a pointer and the location of it.
+pub(crate) fn locate<'v>(
+    value: &'v JsonValue,
+    pointer: &str,
+) -> Result<(&'v JsonValue, Vec<LocItem>)> {
+    let mut target = value;
+    let mut loc = Vec::new();
+    for token in parse_pointer(pointer)? {
+        let item = match (target, parse_index(&token)) {
+            (JsonValue::Array(_), Some(idx)) => LocItem::Index(idx),
+            _ => LocItem::Key(token.clone()),
+        };
+        target =
+            step(target, &token).ok_or_else(|| pointer_err("no value at the pointer", pointer))?;
+        loc.push(item);
+    }
+    Ok((target, loc))
+}
+
+impl JsonValue {
+    /// Returns the value referred by a JSON Pointer (RFC 6901), e.g. `/a/0/b`.
+    ///
+    /// In a reference token, `~0` means `~` and `~1` means `/`.
+    /// The empty pointer refers to the whole value.
+    ///
+    /// For example,
+    ///
+    /// ```rust
+    /// use dade::{json, JsonValue};
+    ///
+    /// let mut value = json!({"a": [{"b": 1}], "c/d": {"~": null}});
+    /// assert_eq!(value.pointer("/a/0/b"), Some(&json!(1)));
+    /// assert_eq!(value.pointer("/c~1d/~0"), Some(&JsonValue::Null));
+    /// assert_eq!(value.pointer("/a/1"), None);
+    ///
+    /// *value.pointer_mut("/a/0/b").unwrap() = json!(2);
+    /// value.pointer_insert("/a/-", json!("end")).unwrap();
+    /// assert_eq!(value.pointer_remove("/c~1d"), Some(json!({"~": null})));
+    /// assert_eq!(value, json!({"a": [{"b": 2}, "end"]}));
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&JsonValue> {
+        parse_pointer(pointer)
+            .ok()?
+            .iter()
+            .try_fold(self, |value, token| step(value, token))
+    }
+
+    /// Returns the mutable value referred by a JSON Pointer.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut JsonValue> {
+        parse_pointer(pointer)
+            .ok()?
+            .iter()
+            .try_fold(self, |value, token| step_mut(value, token))
+    }
+
+    /// Inserts a value at a JSON Pointer, whose parent must exist.
+    ///
+    /// A member of an object is added or replaced, returning the replaced value.
+    /// An item of an array is inserted before the index, where `-` or the length appends it.
+    /// The empty pointer replaces the whole value.
+    pub fn pointer_insert(&mut self, pointer: &str, value: JsonValue) -> Result<Option<JsonValue>> {
+        let mut tokens = parse_pointer(pointer)?;
+        let last = match tokens.pop() {
+            Some(last) => last,
+            None => return Ok(Some(std::mem::replace(self, value))),
+        };
+        let parent = tokens
+            .iter()
+            .try_fold(self, |value, token| step_mut(value, token))
+            .ok_or_else(|| pointer_err("no value at the parent of the pointer", pointer))?;
+        match parent {
+            JsonValue::Object(dict) => Ok(dict.insert(last, value)),
+            JsonValue::Array(arr) => {
+                let idx = if last == "-" {
+                    arr.len()
+                } else {
+                    parse_index(&last)
+                        .filter(|idx| *idx <= arr.len())
+                        .ok_or_else(|| pointer_err("index out of range", pointer))?
+                };
+                arr.insert(idx, value);
+                Ok(None)
+            }
+            _ => Err(pointer_err(
+                "expect `JsonValue::Object` or `JsonValue::Array` at the parent of the pointer",
+                pointer,
+            )),
+        }
+    }
+
+    /// Removes the value referred by a JSON Pointer, returning it.
+    ///
+    /// The items of an array after it are shifted, and the whole value cannot be removed.
+    pub fn pointer_remove(&mut self, pointer: &str) -> Option<JsonValue> {
+        let mut tokens = parse_pointer(pointer).ok()?;
+        let last = tokens.pop()?;
+        let parent = tokens
+            .iter()
+            .try_fold(self, |value, token| step_mut(value, token))?;
+        match parent {
+            JsonValue::Object(dict) => dict.remove(last.as_str()),
+            JsonValue::Array(arr) => {
+                let idx = parse_index(&last).filter(|idx| *idx < arr.len())?;
+                Some(arr.remove(idx))
+            }
+            _ => None,
+        }
+    }
+}
@@ -1,5 +1,7 @@
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 
+use crate::json;
 use crate::json::JsonValue;
 
 /// A trait defines the format to define the schema for a model or a field.
@@ -9,46 +11,42 @@ pub trait RegisterSchema {
 
 impl RegisterSchema for () {
     fn register_schema(_defs: &mut BTreeMap<String, JsonValue>) -> JsonValue {
-        JsonValue::Object(BTreeMap::from([(
-            "type".to_string(),
-            JsonValue::String("null".to_string()),
-        )]))
+        json!({"type": "null"})
     }
 }
 
-impl RegisterSchema for String {
+impl RegisterSchema for str {
     fn register_schema(_defs: &mut BTreeMap<String, JsonValue>) -> JsonValue {
-        JsonValue::Object(BTreeMap::from([(
-            "type".to_string(),
-            JsonValue::String("string".to_string()),
-        )]))
+        json!({"type": "string"})
+    }
+}
+
+impl RegisterSchema for String {
+    fn register_schema(defs: &mut BTreeMap<String, JsonValue>) -> JsonValue {
+        <str as RegisterSchema>::register_schema(defs)
+    }
+}
+
+impl<'a> RegisterSchema for Cow<'a, str> {
+    fn register_schema(defs: &mut BTreeMap<String, JsonValue>) -> JsonValue {
+        <str as RegisterSchema>::register_schema(defs)
     }
 }
 
 impl<T: RegisterSchema> RegisterSchema for Vec<T> {
     fn register_schema(defs: &mut BTreeMap<String, JsonValue>) -> JsonValue {
-        JsonValue::Object(BTreeMap::from([
-            ("type".to_string(), JsonValue::String("array".to_string())),
-            (
-                "items".to_string(),
-                <T as RegisterSchema>::register_schema(defs),
-            ),
-        ]))
+        json!({
+            "type": "array",
+            "items": <T as RegisterSchema>::register_schema(defs),
+        })
     }
 }
 
 impl<T: RegisterSchema> RegisterSchema for Option<T> {
     fn register_schema(defs: &mut BTreeMap<String, JsonValue>) -> JsonValue {
-        JsonValue::Object(BTreeMap::from([(
-            "anyOf".to_string(),
-            JsonValue::Array(Vec::from([
-                JsonValue::Object(BTreeMap::from([(
-                    "type".to_string(),
-                    JsonValue::String("null".to_string()),
-                )])),
-                <T as RegisterSchema>::register_schema(defs),
-            ])),
-        )]))
+        json!({
+            "anyOf": [{"type": "null"}, <T as RegisterSchema>::register_schema(defs)],
+        })
     }
 }
 
@@ -58,12 +56,15 @@ impl<T: RegisterSchema> RegisterSchema for Box<T> {
     }
 }
 
+impl<T: RegisterSchema + ?Sized> RegisterSchema for &T {
+    fn register_schema(defs: &mut BTreeMap<String, JsonValue>) -> JsonValue {
+        <T as RegisterSchema>::register_schema(defs)
+    }
+}
+
 impl RegisterSchema for bool {
     fn register_schema(_defs: &mut BTreeMap<String, JsonValue>) -> JsonValue {
-        JsonValue::Object(BTreeMap::from([(
-            "type".to_string(),
-            JsonValue::String("boolean".to_string()),
-        )]))
+        json!({"type": "boolean"})
     }
 }
 
@@ -72,10 +73,7 @@ macro_rules! int_schema {
         $(
             impl RegisterSchema for $i {
                 fn register_schema(_defs: &mut BTreeMap<String, JsonValue>) -> JsonValue {
-                    JsonValue::Object(BTreeMap::from([(
-                        "type".to_string(),
-                        JsonValue::String("integer".to_string()),
-                    )]))
+                    json!({"type": "integer"})
                 }
             }
         )*
@@ -89,10 +87,7 @@ macro_rules! float_schema {
         $(
             impl RegisterSchema for $i {
                 fn register_schema(_defs: &mut BTreeMap<String, JsonValue>) -> JsonValue {
-                    JsonValue::Object(BTreeMap::from([(
-                        "type".to_string(),
-                        JsonValue::String("number".to_string()),
-                    )]))
+                    json!({"type": "number"})
                 }
             }
         )*
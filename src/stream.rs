@@ -1,3 +1,5 @@
+use std::io::BufRead;
+
 pub trait Stream<T> {
     fn peek(&mut self) -> Option<&T>;
     fn next(&mut self) -> Option<&T>;
@@ -10,10 +12,28 @@ pub trait Stream<T> {
     fn expect(&mut self, it: &[T]) -> bool;
     fn position(&self) -> usize;
     fn finish(&self) -> bool;
+    /// Returns true if all items are consumed, reading more of the input if needed.
+    ///
+    /// By default, this is the same as `finish`.
+    fn at_end(&mut self) -> bool {
+        self.finish()
+    }
     fn skip(&mut self);
     fn skip_by(&mut self, size: usize);
     fn anchor(&mut self);
     fn read(&mut self) -> &[T];
+    /// Returns the line and the column, both starting from 1, of the given position.
+    ///
+    /// By default, the input is treated as one line.
+    fn line_column(&self, pos: usize) -> (usize, usize) {
+        (1, pos + 1)
+    }
+    /// Returns the items around the given position which are still available.
+    ///
+    /// By default, nothing is available.
+    fn around(&self, _pos: usize, _size: usize) -> &[T] {
+        &[]
+    }
 }
 
 pub struct SliceBytes<'a> {
@@ -24,7 +44,7 @@ pub struct SliceBytes<'a> {
 }
 
 impl<'a> SliceBytes<'a> {
-    pub fn new(bytes: &'a [u8]) -> SliceBytes {
+    pub fn new(bytes: &'a [u8]) -> SliceBytes<'a> {
         SliceBytes {
             bytes,
             pos: 0,
@@ -32,6 +52,12 @@ impl<'a> SliceBytes<'a> {
             length: bytes.len(),
         }
     }
+
+    /// Returns the bytes between the given positions, which live as long as the input.
+    #[inline]
+    pub(crate) fn slice(&self, start: usize, end: usize) -> &'a [u8] {
+        &self.bytes[start..end]
+    }
 }
 
 impl<'a> Stream<u8> for SliceBytes<'a> {
@@ -107,4 +133,233 @@ impl<'a> Stream<u8> for SliceBytes<'a> {
     fn read(&mut self) -> &[u8] {
         &self.bytes[self.anchor_point..self.pos]
     }
+
+    fn line_column(&self, pos: usize) -> (usize, usize) {
+        let consumed = &self.bytes[..pos.min(self.length)];
+        let line = consumed.iter().filter(|&&b| b == b'\n').count() + 1;
+        let line_start = consumed
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        (line, pos - line_start + 1)
+    }
+
+    fn around(&self, pos: usize, size: usize) -> &[u8] {
+        let pos = pos.min(self.length);
+        &self.bytes[pos.saturating_sub(size)..(pos + size).min(self.length)]
+    }
+}
+
+const READ_CHUNK: usize = 8 * 1024;
+/// The number of consumed bytes kept before the current position, for snippets of errors.
+const KEEP_BEHIND: usize = 16;
+
+pub struct ReadBytes<R: BufRead> {
+    reader: R,
+    bytes: Vec<u8>,
+    pos: usize,
+    offset: usize,
+    anchor_point: Option<usize>,
+    eof: bool,
+    error: Option<std::io::Error>,
+    lines: usize,
+    line_start: usize,
+}
+
+impl<R: BufRead> ReadBytes<R> {
+    pub fn new(reader: R) -> ReadBytes<R> {
+        ReadBytes {
+            reader,
+            bytes: Vec::with_capacity(READ_CHUNK),
+            pos: 0,
+            offset: 0,
+            anchor_point: None,
+            eof: false,
+            error: None,
+            lines: 0,
+            line_start: 0,
+        }
+    }
+
+    /// Returns an I/O error that occurred while reading, if any.
+    ///
+    /// When the underlying reader fails, the stream behaves as if it reached the end of input,
+    /// so the caller should check this after a load fails.
+    pub fn take_error(&mut self) -> Option<std::io::Error> {
+        self.error.take()
+    }
+
+    /// Returns the capacity of the internal buffer.
+    ///
+    /// Consumed bytes are dropped on each refill, so this is bounded by the longest token
+    /// and the chunk size of the reader rather than the size of the input.
+    pub fn buffer_capacity(&self) -> usize {
+        self.bytes.capacity()
+    }
+
+    /// Ensures that at least `size` bytes are buffered from the current position,
+    /// unless the reader is exhausted.
+    #[inline]
+    fn fill(&mut self, size: usize) -> bool {
+        if self.pos + size <= self.bytes.len() {
+            return true;
+        }
+        self.refill(size)
+    }
+
+    #[cold]
+    fn refill(&mut self, size: usize) -> bool {
+        // Drop the bytes that are no longer reachable by `read`, except a few bytes
+        // before the current position. An anchored token is moved to the front only once,
+        // since nothing can be dropped on the following refills until it is read.
+        let keep = self
+            .anchor_point
+            .unwrap_or(self.pos)
+            .min(self.pos)
+            .saturating_sub(KEEP_BEHIND);
+        if keep > 0 {
+            // Keep track of lines in the dropped bytes for `line_column`.
+            for (i, &b) in self.bytes[..keep].iter().enumerate() {
+                if b == b'\n' {
+                    self.lines += 1;
+                    self.line_start = self.offset + i + 1;
+                }
+            }
+            self.bytes.drain(..keep);
+            self.pos -= keep;
+            self.offset += keep;
+            if let Some(anchor_point) = self.anchor_point.as_mut() {
+                *anchor_point -= keep;
+            }
+        }
+        while !self.eof && self.bytes.len() < self.pos + size {
+            match self.reader.fill_buf() {
+                Ok([]) => self.eof = true,
+                Ok(chunk) => {
+                    let length = chunk.len();
+                    self.bytes.extend_from_slice(chunk);
+                    self.reader.consume(length);
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(err) => {
+                    self.error = Some(err);
+                    self.eof = true;
+                }
+            }
+        }
+        self.pos + size <= self.bytes.len()
+    }
+}
+
+impl<R: BufRead> Stream<u8> for ReadBytes<R> {
+    #[inline]
+    fn peek(&mut self) -> Option<&u8> {
+        self.fill(1);
+        self.bytes.get(self.pos)
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<&u8> {
+        if self.fill(1) {
+            self.pos += 1;
+            self.bytes.get(self.pos - 1)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn while_if<F>(&mut self, cond: F)
+    where
+        F: Fn(&u8) -> bool,
+    {
+        while self.next_if(&cond) {}
+    }
+
+    #[inline]
+    fn next_if<F>(&mut self, cond: F) -> bool
+    where
+        F: Fn(&u8) -> bool,
+    {
+        if self.fill(1) && cond(&self.bytes[self.pos]) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    #[inline]
+    fn expect(&mut self, it: &[u8]) -> bool {
+        if !self.fill(it.len()) || it != &self.bytes[self.pos..self.pos + it.len()] {
+            false
+        } else {
+            self.pos += it.len();
+            true
+        }
+    }
+
+    #[inline]
+    fn position(&self) -> usize {
+        self.offset + self.pos
+    }
+
+    /// Returns true if all bytes are consumed and the reader is known to be exhausted.
+    ///
+    /// This does not read from the reader, so use `at_end` to find the end of input.
+    #[inline]
+    fn finish(&self) -> bool {
+        self.eof && self.bytes.len() <= self.pos
+    }
+
+    /// Returns true if all bytes are consumed, reading from the reader if needed.
+    #[inline]
+    fn at_end(&mut self) -> bool {
+        !self.fill(1)
+    }
+
+    #[inline]
+    fn skip(&mut self) {
+        if self.fill(1) {
+            self.pos += 1;
+        }
+    }
+
+    #[inline]
+    fn skip_by(&mut self, size: usize) {
+        self.fill(size);
+        self.pos = (self.pos + size).min(self.bytes.len());
+    }
+
+    #[inline]
+    fn anchor(&mut self) {
+        self.anchor_point = Some(self.pos)
+    }
+
+    /// Returns bytes from an anchor point to the current position.
+    ///
+    /// The anchor point is released after this call, so the consumed bytes can be dropped
+    /// on the next refill.
+    #[inline]
+    fn read(&mut self) -> &[u8] {
+        let start = self.anchor_point.take().unwrap_or(self.pos);
+        &self.bytes[start..self.pos]
+    }
+
+    /// Returns the line and the column of the given position.
+    ///
+    /// The position must not be before the bytes which are still buffered.
+    fn line_column(&self, pos: usize) -> (usize, usize) {
+        let buffered = &self.bytes[..pos.saturating_sub(self.offset).min(self.bytes.len())];
+        let line = self.lines + buffered.iter().filter(|&&b| b == b'\n').count() + 1;
+        let line_start = buffered
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(self.line_start, |i| self.offset + i + 1);
+        (line, pos.saturating_sub(line_start) + 1)
+    }
+
+    fn around(&self, pos: usize, size: usize) -> &[u8] {
+        let pos = pos.saturating_sub(self.offset).min(self.bytes.len());
+        &self.bytes[pos.saturating_sub(size)..(pos + size).min(self.bytes.len())]
+    }
 }
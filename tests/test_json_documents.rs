@@ -0,0 +1,118 @@
+use dade::{json_dump, JsonDocuments, JsonLoader, ParseErrorKind, ReadBytes};
+use std::io::{BufReader, Read};
+
+fn collect<S: dade::Stream<u8>>(documents: JsonDocuments<S>) -> Vec<(usize, String)> {
+    documents
+        .map(|item| {
+            let (offset, value) = item.unwrap();
+            (offset, json_dump(&value, false))
+        })
+        .collect()
+}
+
+#[test]
+fn test_whitespace_separated() {
+    let json = " {\"a\": [1, 2]}\n{}{\"b\": null}\r\n\t\"c\" 1 -2.5 true[]  \n";
+    let expected = [
+        (1, "{\"a\":[1,2]}".to_string()),
+        (15, "{}".to_string()),
+        (17, "{\"b\":null}".to_string()),
+        (31, "\"c\"".to_string()),
+        (35, "1".to_string()),
+        (37, "-2.5".to_string()),
+        (42, "true".to_string()),
+        (46, "[]".to_string()),
+    ];
+    assert_eq!(
+        collect(JsonDocuments::new(JsonLoader::from(json))),
+        expected
+    );
+
+    let reader = BufReader::with_capacity(1, json.as_bytes());
+    let documents = JsonDocuments::new(JsonLoader::from(ReadBytes::new(reader)));
+    assert_eq!(collect(documents), expected);
+
+    assert!(collect(JsonDocuments::new(JsonLoader::from(""))).is_empty());
+    assert!(collect(JsonDocuments::new(JsonLoader::from(" \n "))).is_empty());
+}
+
+#[test]
+fn test_record_separator() {
+    let json = "\u{1e}{\"a\": 1}\n\u{1e}\u{1e} [2]\n\u{1e}\"b\"\n";
+    let documents = JsonDocuments::new(JsonLoader::from(json)).with_record_separator(true);
+    assert_eq!(
+        collect(documents),
+        [
+            (1, "{\"a\":1}".to_string()),
+            (13, "[2]".to_string()),
+            (18, "\"b\"".to_string()),
+        ]
+    );
+
+    let mut documents =
+        JsonDocuments::new(JsonLoader::from("\u{1e}1\n2\n")).with_record_separator(true);
+    assert_eq!(json_dump(&documents.next().unwrap().unwrap().1, false), "1");
+    let err = documents.next().unwrap().err().unwrap();
+    assert_eq!(
+        err.parse_error_kind(),
+        Some(ParseErrorKind::ExpectedRecordSeparator)
+    );
+    assert_eq!(err.offset(), Some(3));
+    assert!(documents.next().is_none());
+
+    // A record separator is not whitespace without the option.
+    let mut documents = JsonDocuments::new(JsonLoader::from("\u{1e}1"));
+    let err = documents.next().unwrap().err().unwrap();
+    assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::ExpectedValue));
+}
+
+#[test]
+fn test_errors() {
+    let mut documents = JsonDocuments::new(JsonLoader::from("{} [1,] {}"));
+    assert!(documents.next().unwrap().is_ok());
+    let err = documents.next().unwrap().err().unwrap();
+    assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::TrailingComma));
+    assert_eq!(err.offset(), Some(6));
+    assert!(documents.next().is_none());
+
+    let mut documents = JsonDocuments::new(JsonLoader::from("[[1]] [[[1]]]").with_max_depth(2));
+    assert!(documents.next().unwrap().is_ok());
+    let err = documents.next().unwrap().err().unwrap();
+    assert_eq!(
+        err.parse_error_kind(),
+        Some(ParseErrorKind::DepthLimitExceeded)
+    );
+
+    let mut documents = JsonDocuments::new(JsonLoader::from("1 2 3").with_max_input_size(3));
+    assert!(documents.next().unwrap().is_ok());
+    assert!(documents.next().unwrap().is_ok());
+    let err = documents.next().unwrap().err().unwrap();
+    assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::InputTooLarge));
+}
+
+#[test]
+fn test_io_error() {
+    struct BrokenReader {
+        sent: bool,
+    }
+
+    impl Read for BrokenReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.sent {
+                Err(std::io::Error::other("broken"))
+            } else {
+                self.sent = true;
+                buf[..5].copy_from_slice(b"1 [2,");
+                Ok(5)
+            }
+        }
+    }
+
+    let mut documents = JsonDocuments::new(JsonLoader::from(ReadBytes::new(BufReader::new(
+        BrokenReader { sent: false },
+    ))));
+    assert!(documents.next().unwrap().is_ok());
+    assert!(documents.next().unwrap().is_err());
+    let err = documents.stream_mut().take_error().unwrap();
+    assert_eq!(err.to_string(), "broken");
+}
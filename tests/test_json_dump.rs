@@ -1,6 +1,9 @@
-use dade::{json_dump, json_load};
+use dade::{
+    json_dump, json_dump_pretty, json_load, json_load_reader, model, ErrorType, Indent, JsonDumper,
+    JsonValue, Model, Newline, PrettyFormat,
+};
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{BufReader, BufWriter, Read, Write};
 
 #[test]
 fn unicode() {
@@ -27,3 +30,164 @@ fn unicode_with_ascii() {
     let dumped = json_dump(&json_value, true);
     assert_eq!(dumped, contents);
 }
+
+#[test]
+fn pretty() {
+    let json_value =
+        json_load("{\"a\": [1, {\"b\": null}, []], \"c\": {}, \"d\": \"\u{3042}\"}").unwrap();
+    assert_eq!(
+        json_dump_pretty(&json_value, false, PrettyFormat::new()),
+        "{\n  \"a\": [\n    1,\n    {\n      \"b\": null\n    },\n    []\n  ],\n  \"c\": {},\n  \"d\": \"\u{3042}\"\n}"
+    );
+    assert_eq!(
+        json_dump_pretty(&json_value, true, PrettyFormat::new()),
+        "{\n  \"a\": [\n    1,\n    {\n      \"b\": null\n    },\n    []\n  ],\n  \"c\": {},\n  \"d\": \"\\u3042\"\n}"
+    );
+    assert_eq!(
+        json_dump_pretty(
+            &json_value,
+            false,
+            PrettyFormat::new()
+                .with_indent(Indent::Tabs(1))
+                .with_newline(Newline::CrLf)
+                .with_space_after_colon(false)
+        ),
+        "{\r\n\t\"a\":[\r\n\t\t1,\r\n\t\t{\r\n\t\t\t\"b\":null\r\n\t\t},\r\n\t\t[]\r\n\t],\r\n\t\"c\":{},\r\n\t\"d\":\"\u{3042}\"\r\n}"
+    );
+    assert_eq!(
+        json_dump_pretty(
+            &json_value,
+            false,
+            PrettyFormat::new().with_indent(Indent::Spaces(0))
+        ),
+        "{\n\"a\": [\n1,\n{\n\"b\": null\n},\n[]\n],\n\"c\": {},\n\"d\": \"\u{3042}\"\n}"
+    );
+    assert_eq!(
+        json_dump_pretty(&JsonValue::Null, false, PrettyFormat::new()),
+        "null"
+    );
+}
+
+#[test]
+fn pretty_roundtrip() {
+    let file = File::open("./tests/data/twitter.json").unwrap();
+    let mut buf_reader = BufReader::new(file);
+    let mut contents = String::new();
+    buf_reader.read_to_string(&mut contents).unwrap();
+    let json_value = json_load(contents.as_str()).unwrap();
+    let dumped = json_dump_pretty(&json_value, false, PrettyFormat::new());
+    assert_eq!(
+        json_dump(&json_load(dumped.as_str()).unwrap(), false),
+        json_dump(&json_value, false)
+    );
+}
+
+#[test]
+fn pretty_model() {
+    #[model]
+    struct Item {
+        id: u64,
+        tags: Vec<String>,
+    }
+
+    let item = Item::parse("{\"id\": 1, \"tags\": [\"a\", \"b\"]}").unwrap();
+    assert_eq!(
+        item.json_pretty(false, PrettyFormat::new().with_indent(Indent::Spaces(4))),
+        "{\n    \"id\": 1,\n    \"tags\": [\n        \"a\",\n        \"b\"\n    ]\n}"
+    );
+}
+
+#[test]
+fn dump_to() {
+    let file = File::open("./tests/data/citm_catalog.json").unwrap();
+    let json_value = json_load_reader(file).unwrap();
+    let mut buffer = Vec::new();
+    JsonDumper::new(false)
+        .dump_to(&json_value, &mut buffer)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        json_dump(&json_value, false)
+    );
+
+    let mut buffer = Vec::new();
+    JsonDumper::new(true)
+        .with_pretty(PrettyFormat::new())
+        .dump_to(&json_value, BufWriter::new(&mut buffer))
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        json_dump_pretty(&json_value, true, PrettyFormat::new())
+    );
+}
+
+#[test]
+fn dump_to_broken_writer() {
+    struct BrokenWriter;
+
+    impl Write for BrokenWriter {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("broken"))
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let err = JsonDumper::new(false)
+        .dump_to(&JsonValue::Null, BrokenWriter)
+        .err()
+        .unwrap();
+    assert!(matches!(err.err_type(), ErrorType::IoError));
+
+    // The dumper can be reused after an error in the middle of nesting.
+    let json_value = json_load("{\"a\": [1, {\"b\": []}]}").unwrap();
+    let mut dumper = JsonDumper::new(false).with_pretty(PrettyFormat::new());
+    assert!(dumper.dump_to(&json_value, BrokenWriter).is_err());
+    let expected = json_dump_pretty(&json_value, false, PrettyFormat::new());
+    assert_eq!(dumper.dump(&json_value), expected);
+    assert!(dumper.dump_to(&json_value, BrokenWriter).is_err());
+    let mut buffer = Vec::new();
+    dumper.dump_to(&json_value, &mut buffer).unwrap();
+    assert_eq!(String::from_utf8(buffer).unwrap(), expected);
+}
+
+#[test]
+fn write_json() {
+    #[model]
+    struct Item {
+        id: u64,
+        name: String,
+    }
+
+    let mut buffer = Vec::new();
+    for json in [
+        "{\"id\": 1, \"name\": \"a\"}",
+        "{\"id\": 2, \"name\": \"\u{3042}\"}",
+    ] {
+        let item = Item::parse(json).unwrap();
+        item.write_json(&mut buffer, true).unwrap();
+        buffer.push(b'\n');
+    }
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "{\"id\":1,\"name\":\"a\"}\n{\"id\":2,\"name\":\"\\u3042\"}\n"
+    );
+}
+
+#[test]
+fn escape() {
+    let json_value =
+        JsonValue::String("a\"\\/\u{0}\u{8}\u{c}\n\r\t\u{1f}\u{7f}\u{3042}\u{1f600}".to_string());
+    assert_eq!(
+        json_dump(&json_value, false),
+        "\"a\\\"\\\\/\\u0000\\b\\f\\n\\r\\t\\u001f\\u007f\u{3042}\\ud83d\\ude00\""
+    );
+    assert_eq!(
+        json_dump(&json_value, true),
+        "\"a\\\"\\\\/\\u0000\\b\\f\\n\\r\\t\\u001f\\u007f\\u3042\\ud83d\\ude00\""
+    );
+    let mut buffer = String::from("abc");
+    JsonDumper::escape("\u{3042}\n", &mut buffer, true);
+    assert_eq!(buffer, "\"\\u3042\\n\"");
+}
@@ -0,0 +1,134 @@
+use dade::{
+    json_dump, json_load, JsonEvent, JsonEventReader, JsonLoader, JsonValue, Map, ParseErrorKind,
+    ReadBytes, Result, Stream,
+};
+use std::fs::File;
+use std::io::BufReader;
+
+fn describe(event: &JsonEvent) -> String {
+    match event {
+        JsonEvent::StartObject => "{".to_string(),
+        JsonEvent::Key(key) => format!("key:{}", key),
+        JsonEvent::EndObject => "}".to_string(),
+        JsonEvent::StartArray => "[".to_string(),
+        JsonEvent::EndArray => "]".to_string(),
+        JsonEvent::String(val) => format!("str:{}", val),
+        JsonEvent::Number(val) => format!("num:{}", val),
+        JsonEvent::Bool(val) => format!("bool:{}", val),
+        JsonEvent::Null => "null".to_string(),
+    }
+}
+
+fn collect(json: &str) -> Vec<(usize, String)> {
+    JsonEventReader::new(JsonLoader::from(json))
+        .map(|item| {
+            let (offset, event) = item.unwrap();
+            (offset, describe(&event))
+        })
+        .collect()
+}
+
+/// Builds a value from events, to compare with `json_load`.
+fn build<S: Stream<u8>>(events: &mut JsonEventReader<S>, event: JsonEvent) -> Result<JsonValue> {
+    Ok(match event {
+        JsonEvent::StartObject => {
+            let mut dict = Map::new();
+            loop {
+                match events.next().unwrap()?.1 {
+                    JsonEvent::Key(key) => {
+                        let event = events.next().unwrap()?.1;
+                        dict.insert(key, build(events, event)?);
+                    }
+                    JsonEvent::EndObject => break JsonValue::Object(dict),
+                    _ => unreachable!(),
+                }
+            }
+        }
+        JsonEvent::StartArray => {
+            let mut vec = Vec::new();
+            loop {
+                match events.next().unwrap()?.1 {
+                    JsonEvent::EndArray => break JsonValue::Array(vec),
+                    event => vec.push(build(events, event)?),
+                }
+            }
+        }
+        JsonEvent::String(val) => JsonValue::String(val),
+        JsonEvent::Number(val) => JsonValue::Number(val),
+        JsonEvent::Bool(val) => JsonValue::Bool(val),
+        JsonEvent::Null => JsonValue::Null,
+        JsonEvent::Key(_) | JsonEvent::EndObject | JsonEvent::EndArray => unreachable!(),
+    })
+}
+
+#[test]
+fn test_events() {
+    assert_eq!(
+        collect(" {\"a\": [1, \"\\u3042\", true, false, null], \"b\": {}, \"c\": [] } "),
+        [
+            (1, "{".to_string()),
+            (2, "key:a".to_string()),
+            (7, "[".to_string()),
+            (8, "num:1".to_string()),
+            (11, "str:\u{3042}".to_string()),
+            (21, "bool:true".to_string()),
+            (27, "bool:false".to_string()),
+            (34, "null".to_string()),
+            (38, "]".to_string()),
+            (41, "key:b".to_string()),
+            (46, "{".to_string()),
+            (47, "}".to_string()),
+            (50, "key:c".to_string()),
+            (55, "[".to_string()),
+            (56, "]".to_string()),
+            (58, "}".to_string()),
+        ]
+    );
+    assert_eq!(collect("-1.5e3"), [(0, "num:-1.5e3".to_string())]);
+    assert_eq!(collect(" \"a\" "), [(1, "str:a".to_string())]);
+}
+
+#[test]
+fn test_same_as_load() {
+    for file in [
+        "./tests/data/twitter.json",
+        "./tests/data/citm_catalog.json",
+        "./tests/data/unicode.json",
+    ] {
+        let reader = BufReader::with_capacity(3, File::open(file).unwrap());
+        let mut events = JsonEventReader::new(JsonLoader::from(ReadBytes::new(reader)));
+        let event = events.next().unwrap().unwrap().1;
+        let value = build(&mut events, event).unwrap();
+        assert!(events.next().is_none());
+        assert_eq!(events.depth(), 0);
+
+        let expected = json_load(&std::fs::read_to_string(file).unwrap()).unwrap();
+        assert_eq!(json_dump(&value, false), json_dump(&expected, false));
+    }
+}
+
+#[test]
+fn test_errors() {
+    for (json, kind, offset) in [
+        ("[1,]", ParseErrorKind::TrailingComma, 3),
+        ("{\"a\" 1}", ParseErrorKind::ExpectedColon, 5),
+        ("{\"a\": 1,}", ParseErrorKind::TrailingComma, 8),
+        ("[1] 2", ParseErrorKind::TrailingData, 4),
+        ("[1 2]", ParseErrorKind::ExpectedCommaOrEnd, 3),
+        ("", ParseErrorKind::UnexpectedEof, 0),
+    ] {
+        let mut events = JsonEventReader::new(JsonLoader::from(json));
+        let err = events.find_map(|item| item.err()).unwrap();
+        assert_eq!(err.parse_error_kind(), Some(kind), "{}", json);
+        assert_eq!(err.offset(), Some(offset), "{}", json);
+        assert!(events.next().is_none());
+    }
+
+    let events = JsonEventReader::new(JsonLoader::from("[[[]]]").with_max_depth(2));
+    let items: Vec<_> = events.collect();
+    assert_eq!(items.len(), 3);
+    assert_eq!(
+        items[2].as_ref().err().unwrap().parse_error_kind(),
+        Some(ParseErrorKind::DepthLimitExceeded)
+    );
+}
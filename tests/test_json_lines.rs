@@ -0,0 +1,142 @@
+use dade::{
+    json_load, model, ErrorType, JsonLines, JsonLinesWriter, JsonValue, Model, ParseErrorKind,
+};
+use std::io::{BufReader, Read};
+
+#[model]
+struct Log {
+    #[field(ge = 1)]
+    id: u64,
+    message: String,
+}
+
+#[test]
+fn test_parse_lines() {
+    let input = "{\"id\": 1, \"message\": \"a\"}\n{\"id\": 2, \"message\": \"b\"}\r\n{\"id\": 3, \"message\": \"c\"}";
+    let logs: Vec<Log> = Log::parse_lines(input.as_bytes())
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(logs.len(), 3);
+    assert_eq!(logs[0].message, "a");
+    assert_eq!(logs[1].message, "b");
+    assert_eq!(logs[2].id, 3);
+
+    // A line feed at the end does not make a line.
+    assert_eq!(
+        Log::parse_lines("{\"id\": 1, \"message\": \"a\"}\n".as_bytes()).count(),
+        1
+    );
+    assert_eq!(Log::parse_lines("".as_bytes()).count(), 0);
+}
+
+#[test]
+fn test_errors_have_line() {
+    let input = "{\"id\": 1, \"message\": \"a\"}\n{\"id\": 0, \"message\": \"b\"}\n{\"id\": 3, \"message\": \"c\"\n{\"id\": 4, \"message\": \"d\"}\n";
+    let ret: Vec<_> = Log::parse_lines(input.as_bytes()).collect();
+    assert_eq!(ret.len(), 4);
+    assert!(ret[0].is_ok());
+    assert!(ret[3].is_ok());
+
+    let err = ret[1].as_ref().err().unwrap();
+    assert!(matches!(err.err_type(), ErrorType::ValidateError));
+    assert_eq!(err.line(), Some(2));
+    assert_eq!(err.errors()[0].loc_path(), "id");
+    assert!(err
+        .to_string()
+        .starts_with("Validate Error at line 2: id: "));
+
+    let err = ret[2].as_ref().err().unwrap();
+    assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::UnexpectedEof));
+    assert_eq!(err.line(), Some(3));
+    assert_eq!(err.column(), Some(25));
+    assert_eq!(err.offset(), Some(76));
+
+    // The line 0 is treated as 1.
+    let err = json_load("[1,\n2,").err().unwrap().at_line(0, 10);
+    assert_eq!(err.line(), Some(2));
+    assert_eq!(err.offset(), Some(16));
+}
+
+#[test]
+fn test_blank_lines() {
+    let input = "{\"id\": 1, \"message\": \"a\"}\n\n \t\r\n{\"id\": 2, \"message\": \"b\"}\n";
+    let ret: Vec<_> = Log::parse_lines(input.as_bytes()).collect();
+    assert_eq!(ret.len(), 4);
+    let err = ret[1].as_ref().err().unwrap();
+    assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::UnexpectedEof));
+    assert_eq!(err.line(), Some(2));
+    assert_eq!(ret[2].as_ref().err().unwrap().line(), Some(3));
+
+    let ret: Vec<_> = JsonLines::<_, Log>::new(BufReader::new(input.as_bytes()))
+        .with_skip_blank_lines(true)
+        .collect();
+    assert_eq!(ret.len(), 2);
+    assert_eq!(ret[1].as_ref().unwrap().id, 2);
+
+    let input = "{\"id\": 1, \"message\": \"a\"}\n\n{\"id\": 0, \"message\": \"b\"}\n";
+    let err = JsonLines::<_, Log>::new(BufReader::new(input.as_bytes()))
+        .with_skip_blank_lines(true)
+        .find_map(|ret| ret.err())
+        .unwrap();
+    assert_eq!(err.line(), Some(3));
+}
+
+#[test]
+fn test_io_error() {
+    struct BrokenReader {
+        sent: bool,
+    }
+
+    impl Read for BrokenReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.sent {
+                Err(std::io::Error::other("broken"))
+            } else {
+                self.sent = true;
+                let line = b"{\"id\": 1, \"message\": \"a\"}\n{";
+                buf[..line.len()].copy_from_slice(line);
+                Ok(line.len())
+            }
+        }
+    }
+
+    let ret: Vec<_> = Log::parse_lines(BrokenReader { sent: false }).collect();
+    assert_eq!(ret.len(), 2);
+    assert!(ret[0].is_ok());
+    let err = ret[1].as_ref().err().unwrap();
+    assert!(matches!(err.err_type(), ErrorType::IoError));
+    assert_eq!(err.line(), Some(2));
+}
+
+#[test]
+fn test_writer() {
+    let mut writer = JsonLinesWriter::new(Vec::new(), true);
+    for (id, message) in [(1, "a\nb"), (2, "\u{3042}")] {
+        let log = Log::parse(&format!(
+            "{{\"id\": {}, \"message\": {}}}",
+            id,
+            JsonValue::String(message.to_string())
+        ))
+        .unwrap();
+        writer.write(&log).unwrap();
+    }
+    writer.write(&json_load("[1, {}]").unwrap()).unwrap();
+    writer.flush().unwrap();
+    let output = String::from_utf8(writer.into_inner()).unwrap();
+    assert_eq!(
+        output,
+        "{\"id\":1,\"message\":\"a\\nb\"}\n{\"id\":2,\"message\":\"\\u3042\"}\n[1,{}]\n"
+    );
+
+    let mut buffer = Vec::new();
+    {
+        let mut writer = JsonLinesWriter::new(&mut buffer, false);
+        for log in Log::parse_lines(output.as_bytes()).take(2) {
+            writer.write(&log.unwrap()).unwrap();
+        }
+    }
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "{\"id\":1,\"message\":\"a\\nb\"}\n{\"id\":2,\"message\":\"\u{3042}\"}\n"
+    );
+}
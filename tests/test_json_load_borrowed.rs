@@ -0,0 +1,168 @@
+use dade::{
+    json_dump, json_load, FromJsonRef, FromJsonValue, JsonLoader, JsonValueRef, ParseErrorKind,
+    ToJsonValue,
+};
+use std::borrow::Cow;
+
+#[test]
+fn test_borrow_from_input() {
+    let json = "{\"a\": \"abc\", \"b\\n\": [\"\\u3042\", 1.5e3, -0, true, false, null], \"\": {}}";
+    let value = JsonLoader::from(json).load_borrowed().unwrap();
+    let dict = match &value {
+        JsonValueRef::Object(dict) => dict,
+        _ => unreachable!(),
+    };
+    let keys: Vec<_> = dict.keys().collect();
+    assert!(matches!(keys[0], Cow::Borrowed("a")));
+    assert!(matches!(keys[1], Cow::Owned(key) if key == "b\n"));
+    assert!(matches!(keys[2], Cow::Borrowed("")));
+    assert!(matches!(
+        &dict["a"],
+        JsonValueRef::String(Cow::Borrowed("abc"))
+    ));
+    match &dict["b\n"] {
+        JsonValueRef::Array(vec) => {
+            assert!(matches!(&vec[0], JsonValueRef::String(Cow::Owned(s)) if s == "\u{3042}"));
+            assert!(matches!(vec[1], JsonValueRef::Number("1.5e3")));
+            assert!(matches!(vec[2], JsonValueRef::Number("-0")));
+            assert!(matches!(vec[3], JsonValueRef::Bool(true)));
+            assert!(matches!(vec[4], JsonValueRef::Bool(false)));
+            assert!(matches!(vec[5], JsonValueRef::Null));
+        }
+        _ => unreachable!(),
+    }
+    assert!(matches!(&dict[""], JsonValueRef::Object(dict) if dict.is_empty()));
+
+    // The borrowed slices point into the input.
+    if let JsonValueRef::String(Cow::Borrowed(s)) = &dict["a"] {
+        assert_eq!(s.as_ptr(), json[7..].as_ptr());
+    }
+}
+
+#[test]
+fn test_same_as_load() {
+    for file in [
+        "./tests/data/twitter.json",
+        "./tests/data/citm_catalog.json",
+        "./tests/data/unicode.json",
+    ] {
+        let contents = std::fs::read_to_string(file).unwrap();
+        let value = JsonLoader::from(contents.as_str()).load_borrowed().unwrap();
+        let expected = json_load(&contents).unwrap();
+        assert_eq!(value.to_string(), json_dump(&expected, false));
+        assert_eq!(
+            json_dump(&value.to_json_value(), false),
+            json_dump(&expected, false)
+        );
+        assert_eq!(
+            json_dump(&value.into_owned(), false),
+            json_dump(&expected, false)
+        );
+    }
+}
+
+#[test]
+fn test_errors() {
+    for (json, kind, offset) in [
+        ("[1,]", ParseErrorKind::TrailingComma, 3),
+        ("{\"a\": 1, \"a\": 2}", ParseErrorKind::DuplicateKey, 9),
+        ("\"a", ParseErrorKind::UnexpectedEof, 2),
+        ("\"\\x\"", ParseErrorKind::InvalidEscape, 2),
+        ("[1] 2", ParseErrorKind::TrailingData, 4),
+        ("01", ParseErrorKind::TrailingData, 1),
+    ] {
+        let err = JsonLoader::from(json).load_borrowed().err().unwrap();
+        assert_eq!(err.parse_error_kind(), Some(kind), "{}", json);
+        assert_eq!(err.offset(), Some(offset), "{}", json);
+    }
+
+    let err = JsonLoader::from("[[1]]")
+        .with_max_depth(1)
+        .load_borrowed()
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.parse_error_kind(),
+        Some(ParseErrorKind::DepthLimitExceeded)
+    );
+    let err = JsonLoader::from("[\"abcd\"]")
+        .with_max_string_length(3)
+        .load_borrowed()
+        .err()
+        .unwrap();
+    assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::StringTooLong));
+}
+
+#[test]
+fn test_relaxed() {
+    let json = "{key: 'a\\'b', other: 'c', /* comment */ list: [1,],}";
+    let value = JsonLoader::from(json)
+        .with_relaxed(true)
+        .load_borrowed()
+        .unwrap();
+    let dict = match &value {
+        JsonValueRef::Object(dict) => dict,
+        _ => unreachable!(),
+    };
+    assert!(dict.keys().all(|key| matches!(key, Cow::Borrowed(_))));
+    assert!(matches!(&dict["key"], JsonValueRef::String(Cow::Owned(s)) if s == "a'b"));
+    assert!(matches!(
+        &dict["other"],
+        JsonValueRef::String(Cow::Borrowed("c"))
+    ));
+    assert_eq!(
+        value.to_string(),
+        "{\"key\":\"a'b\",\"other\":\"c\",\"list\":[1]}"
+    );
+}
+
+fn describe<T: std::fmt::Debug>(ret: dade::Result<T>) -> String {
+    match ret {
+        Ok(val) => format!("{:?}", val),
+        Err(err) => err.to_string(),
+    }
+}
+
+macro_rules! assert_same_conversion {
+    ( $json: expr, $( $i:ty ),* ) => {
+        let owned = json_load($json).unwrap();
+        let borrowed = JsonLoader::from($json).load_borrowed().unwrap();
+        $(
+            assert_eq!(
+                describe(<$i>::from_json_value(&owned)),
+                describe(<$i>::from_json_ref(&borrowed)),
+                "{} as {}",
+                $json,
+                stringify!($i)
+            );
+            assert_eq!(
+                describe(<$i>::from_json_value_strict(&owned)),
+                describe(<$i>::from_json_ref_strict(&borrowed)),
+                "{} as {}",
+                $json,
+                stringify!($i)
+            );
+        )*
+    };
+}
+
+#[test]
+fn test_same_conversion_as_json_value() {
+    for json in [
+        "0",
+        "-0",
+        "255",
+        "256",
+        "-1",
+        "1.0",
+        "1.5",
+        "1e3",
+        "-1e40",
+        "1e400",
+        "[1, -1, 2.5]",
+        "\"1\"",
+        "null",
+    ] {
+        assert_same_conversion!(json, u8, i8, u64, i128, f32, f64, Vec<i32>, Option<u8>);
+    }
+}
@@ -0,0 +1,234 @@
+use dade::{
+    json_dump, model, BorrowedModel, DuplicateKeys, JsonLoader, Model, ParseErrorKind, ReadBytes,
+    Result,
+};
+use std::io::BufReader;
+
+const POLICIES: [DuplicateKeys; 4] = [
+    DuplicateKeys::Reject,
+    DuplicateKeys::KeepFirst,
+    DuplicateKeys::KeepLast,
+    DuplicateKeys::CollectAll,
+];
+
+fn load(json: &str, policy: DuplicateKeys) -> Result<String> {
+    let from_str = JsonLoader::from(json)
+        .with_duplicate_keys(policy)
+        .load()
+        .map(|value| json_dump(&value, false));
+    let reader = ReadBytes::new(BufReader::with_capacity(1, json.as_bytes()));
+    let from_reader = JsonLoader::from(reader)
+        .with_duplicate_keys(policy)
+        .load()
+        .map(|value| json_dump(&value, false));
+    let borrowed = JsonLoader::from(json)
+        .with_duplicate_keys(policy)
+        .load_borrowed()
+        .map(|value| value.to_string());
+    for other in [from_reader, borrowed] {
+        assert_eq!(
+            other
+                .as_ref()
+                .map_err(|err| (err.parse_error_kind(), err.offset())),
+            from_str
+                .as_ref()
+                .map_err(|err| (err.parse_error_kind(), err.offset())),
+            "{}",
+            json
+        );
+    }
+    from_str
+}
+
+#[test]
+fn test_policies() {
+    let json = "{\"a\": 1, \"b\": [0], \"a\": 2, \"b\": {}, \"a\": [3], \"c\": null}";
+    let err = load(json, DuplicateKeys::Reject).err().unwrap();
+    assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::DuplicateKey));
+    assert_eq!(err.offset(), Some(19));
+    assert_eq!(
+        load(json, DuplicateKeys::KeepFirst).unwrap(),
+        "{\"a\":1,\"b\":[0],\"c\":null}"
+    );
+    assert_eq!(
+        load(json, DuplicateKeys::KeepLast).unwrap(),
+        "{\"a\":[3],\"b\":{},\"c\":null}"
+    );
+    assert_eq!(
+        load(json, DuplicateKeys::CollectAll).unwrap(),
+        "{\"a\":[1,2,[3]],\"b\":[[0],{}],\"c\":null}"
+    );
+
+    for policy in POLICIES {
+        // Without duplicates, every policy loads the same.
+        let json = "{\"a\":[1],\"b\":{\"c\":{}}}";
+        assert_eq!(load(json, policy).unwrap(), json);
+        // Nested objects follow the same policy.
+        let nested = load("[{\"x\": {\"y\": 1, \"y\": 2}}]", policy);
+        assert_eq!(nested.is_err(), policy == DuplicateKeys::Reject);
+        // A skipped value is still checked.
+        for json in ["{\"a\": 1, \"a\": [1,]}", "{\"a\": 1, \"a\": 1, }"] {
+            assert!(load(json, policy).is_err(), "{}", json);
+        }
+    }
+    assert_eq!(
+        JsonLoader::from("{}").duplicate_keys(),
+        DuplicateKeys::Reject
+    );
+}
+
+#[test]
+fn test_member_limit_counts_duplicates() {
+    let json = "{\"a\": 1, \"a\": 2, \"a\": 3}";
+    for policy in POLICIES {
+        let err = JsonLoader::from(json)
+            .with_duplicate_keys(policy)
+            .with_max_object_members(2)
+            .load()
+            .err()
+            .unwrap();
+        let kind = if policy == DuplicateKeys::Reject {
+            ParseErrorKind::DuplicateKey
+        } else {
+            ParseErrorKind::TooManyMembers
+        };
+        assert_eq!(err.parse_error_kind(), Some(kind));
+    }
+}
+
+#[model]
+struct Item {
+    id: u64,
+    tags: Option<Vec<String>>,
+    child: Option<Box<Item>>,
+}
+
+fn parse(json: &str, policy: DuplicateKeys) -> Result<String> {
+    let owned = Item::parse_loader(&mut JsonLoader::from(json).with_duplicate_keys(policy));
+    let borrowed =
+        Item::parse_loader_borrowed(&mut JsonLoader::from(json).with_duplicate_keys(policy));
+    assert_eq!(
+        owned.as_ref().map(|item| item.json(false)).ok(),
+        borrowed.as_ref().map(|item| item.json(false)).ok(),
+        "{}",
+        json
+    );
+    owned.map(|item| item.json(false))
+}
+
+#[test]
+fn test_models() {
+    let json = "{\"id\": 1, \"x\": 0, \"tags\": [\"a\"], \"x\": 1, \"id\": 2, \"child\": {\"id\": 3, \"id\": 4}}";
+    let err = Item::parse(json).err().unwrap();
+    assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::DuplicateKey));
+    assert!(parse(json, DuplicateKeys::Reject).is_err());
+    assert_eq!(
+        parse(json, DuplicateKeys::KeepFirst).unwrap(),
+        "{\"id\":1,\"tags\":[\"a\"],\"child\":{\"id\":3,\"tags\":null,\"child\":null}}"
+    );
+    assert_eq!(
+        parse(json, DuplicateKeys::KeepLast).unwrap(),
+        "{\"id\":2,\"tags\":[\"a\"],\"child\":{\"id\":4,\"tags\":null,\"child\":null}}"
+    );
+
+    // Collected values are validated as an array.
+    let err = parse(json, DuplicateKeys::CollectAll).err().unwrap();
+    let errors = err.errors();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].loc_path(), "id");
+    assert_eq!(errors[1].loc_path(), "child.id");
+    let json = "{\"id\": 1, \"tags\": \"a\", \"tags\": \"b\", \"x\": 1, \"x\": 2}";
+    assert_eq!(
+        parse(json, DuplicateKeys::CollectAll).unwrap(),
+        "{\"id\":1,\"tags\":[\"a\",\"b\"],\"child\":null}"
+    );
+}
+
+#[test]
+fn test_models_from_reader() {
+    // The whole object is loaded as a value to collect the values while streaming.
+    let json = "{\"id\": 1, \"tags\": \"a\", \"child\": {\"id\": 2, \"tags\": \"b\", \"tags\": \"c\"}, \"tags\": \"b\"}";
+    let reader = ReadBytes::new(BufReader::with_capacity(1, json.as_bytes()));
+    let mut loader = JsonLoader::from(reader).with_duplicate_keys(DuplicateKeys::CollectAll);
+    let expected =
+        "{\"id\":1,\"tags\":[\"a\",\"b\"],\"child\":{\"id\":2,\"tags\":[\"b\",\"c\"],\"child\":null}}";
+    assert_eq!(
+        Item::parse_loader(&mut loader).unwrap().json(false),
+        expected
+    );
+    assert_eq!(parse(json, DuplicateKeys::CollectAll).unwrap(), expected);
+}
+
+#[test]
+fn test_load_members() {
+    let json = "{\"a\": 1, \"b\": 2, \"a\": [3], \"c\": 4}";
+    for policy in POLICIES {
+        let mut loader = JsonLoader::from(json).with_duplicate_keys(policy);
+        let mut keys = Vec::new();
+        assert!(loader.begin_object().unwrap());
+        let ret = loader.load_members(|loader, key| {
+            keys.push(key.to_string());
+            loader.load_value()?;
+            Ok(true)
+        });
+        match policy {
+            DuplicateKeys::Reject => {
+                // The duplicate key fails before its value is loaded.
+                let err = ret.err().unwrap();
+                assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::DuplicateKey));
+                assert_eq!(err.offset(), Some(17));
+                assert_eq!(keys, ["a", "b"]);
+            }
+            DuplicateKeys::KeepFirst => {
+                ret.unwrap();
+                assert_eq!(keys, ["a", "b", "c"]);
+            }
+            DuplicateKeys::KeepLast | DuplicateKeys::CollectAll => {
+                ret.unwrap();
+                assert_eq!(keys, ["a", "b", "a", "c"]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_load_members_skipped_keys() {
+    // Skipped keys are also checked, including unquoted ones.
+    let json = "{b: {\"x\": 1}, 'a': 1, \"\\u0062\": 2, a: 3}";
+    for policy in POLICIES {
+        let mut loader = JsonLoader::from(json)
+            .with_relaxed(true)
+            .with_duplicate_keys(policy);
+        let mut keys = Vec::new();
+        let mut values = Vec::new();
+        assert!(loader.begin_object().unwrap());
+        let ret = loader.load_members(|loader, key| {
+            keys.push(key.to_string());
+            if key == "a" {
+                values.push(loader.load_value()?.to_string());
+                Ok(true)
+            } else {
+                Ok(false)
+            }
+        });
+        match policy {
+            DuplicateKeys::Reject => {
+                let err = ret.err().unwrap();
+                assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::DuplicateKey));
+                assert_eq!(err.offset(), Some(22));
+                assert_eq!(keys, ["b", "a"]);
+            }
+            DuplicateKeys::KeepFirst => {
+                // A skipped key is not kept, so it is given again to be skipped.
+                ret.unwrap();
+                assert_eq!(keys, ["b", "a", "b"]);
+                assert_eq!(values, ["1"]);
+            }
+            DuplicateKeys::KeepLast | DuplicateKeys::CollectAll => {
+                ret.unwrap();
+                assert_eq!(keys, ["b", "a", "b", "a"]);
+                assert_eq!(values, ["1", "3"]);
+            }
+        }
+    }
+}
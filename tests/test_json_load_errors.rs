@@ -0,0 +1,512 @@
+use dade::{
+    json_load, json_load_reader, model, JsonLoader, Model, ParseErrorKind, ReadBytes, SliceBytes,
+    Stream, DEFAULT_MAX_DEPTH,
+};
+use std::io::BufReader;
+
+macro_rules! test_location {
+    ($test_name: ident, $json: literal, $offset: literal, $line: literal, $column: literal, $snippet: literal) => {
+        #[test]
+        fn $test_name() {
+            let err = json_load($json).err().unwrap();
+            assert_eq!(err.offset(), Some($offset), "{}", err);
+            assert_eq!(err.line(), Some($line), "{}", err);
+            assert_eq!(err.column(), Some($column), "{}", err);
+            assert_eq!(err.snippet(), Some($snippet), "{}", err);
+
+            let err = json_load_reader($json.as_bytes()).err().unwrap();
+            assert_eq!(err.offset(), Some($offset), "{}", err);
+            assert_eq!(err.line(), Some($line), "{}", err);
+            assert_eq!(err.column(), Some($column), "{}", err);
+
+            let reader = BufReader::with_capacity(1, $json.as_bytes());
+            let err = JsonLoader::from(ReadBytes::new(reader))
+                .load()
+                .err()
+                .unwrap();
+            assert_eq!(err.offset(), Some($offset), "{}", err);
+            assert_eq!(err.line(), Some($line), "{}", err);
+            assert_eq!(err.column(), Some($column), "{}", err);
+        }
+    };
+}
+
+test_location!(test_trailing_comma, "[1,]", 3, 1, 4, "[1,]");
+test_location!(test_missing_colon, "{\"a\" 1}", 5, 1, 6, "{\"a\" 1}");
+test_location!(test_bad_literal, "[\n  nul\n]", 4, 2, 3, "[   nul ]");
+test_location!(test_truncated_number, "[\n1.]", 4, 2, 3, "[ 1.]");
+test_location!(test_trailing_data, "{}\r\n\r\n x", 7, 3, 2, "{}     x");
+test_location!(test_bad_escape, "\"ab\\x\"", 4, 1, 5, "\"ab\\x\"");
+test_location!(test_lone_surrogate, "\"\\udc00\"", 1, 1, 2, "\"\\udc00\"");
+test_location!(test_unterminated, "\"abc", 4, 1, 5, "\"abc");
+test_location!(test_empty, "", 0, 1, 1, "");
+test_location!(
+    test_long_line,
+    "[\"0123456789abcdefghij\", \"0123456789abcdefghij\" 1]",
+    48,
+    1,
+    49,
+    "6789abcdefghij\" 1]"
+);
+
+#[test]
+fn test_display() {
+    let err = json_load("[1,\n2,\n]").err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Parse Error: trailing comma at line 3 column 1 (offset 7) near `[1, 2, ]`"
+    );
+}
+
+macro_rules! test_kind {
+    ($test_name: ident, $json: literal, $kind: ident) => {
+        #[test]
+        fn $test_name() {
+            let err = json_load($json).err().unwrap();
+            assert_eq!(
+                err.parse_error_kind(),
+                Some(ParseErrorKind::$kind),
+                "{}",
+                err
+            );
+
+            let reader = BufReader::with_capacity(1, $json.as_bytes());
+            let err = JsonLoader::from(ReadBytes::new(reader))
+                .load()
+                .err()
+                .unwrap();
+            assert_eq!(
+                err.parse_error_kind(),
+                Some(ParseErrorKind::$kind),
+                "{}",
+                err
+            );
+        }
+    };
+}
+
+test_kind!(test_kind_empty, "", UnexpectedEof);
+test_kind!(test_kind_unclosed_array, "[1, 2", UnexpectedEof);
+test_kind!(test_kind_unclosed_object, "{\"a\": 1", UnexpectedEof);
+test_kind!(test_kind_unclosed_key, "{\"a\"", UnexpectedEof);
+test_kind!(test_kind_unterminated_string, "\"abc", UnexpectedEof);
+test_kind!(test_kind_truncated_number, "-", UnexpectedEof);
+test_kind!(test_kind_expected_value, "[1, ?]", ExpectedValue);
+test_kind!(test_kind_expected_colon, "{\"a\" 1}", ExpectedColon);
+test_kind!(
+    test_kind_expected_colon_by_comma,
+    "{\"a\", 1}",
+    ExpectedColon
+);
+test_kind!(test_kind_array_without_comma, "[1 2]", ExpectedCommaOrEnd);
+test_kind!(
+    test_kind_object_without_comma,
+    "{\"a\": 1 \"b\": 2}",
+    ExpectedCommaOrEnd
+);
+test_kind!(test_kind_key_must_be_string, "{1: 2}", KeyMustBeString);
+test_kind!(
+    test_kind_key_must_be_string_after_comma,
+    "{\"a\": 1, b: 2}",
+    KeyMustBeString
+);
+test_kind!(test_kind_trailing_comma_in_array, "[1, 2, ]", TrailingComma);
+test_kind!(
+    test_kind_trailing_comma_in_object,
+    "{\"a\": 1,}",
+    TrailingComma
+);
+test_kind!(test_kind_leading_zero, "[-a]", InvalidNumber);
+test_kind!(test_kind_no_fraction, "1.e3", InvalidNumber);
+test_kind!(test_kind_no_exponent, "1e+", UnexpectedEof);
+test_kind!(test_kind_no_exponent_digit, "[1e+]", InvalidNumber);
+test_kind!(test_kind_invalid_literal, "[tru]", InvalidLiteral);
+test_kind!(test_kind_invalid_literal_case, "Null", ExpectedValue);
+test_kind!(test_kind_invalid_escape, "\"\\a\"", InvalidEscape);
+test_kind!(
+    test_kind_invalid_unicode_escape,
+    "\"\\u12x4\"",
+    InvalidEscape
+);
+test_kind!(test_kind_low_surrogate_first, "\"\\udc00\"", LoneSurrogate);
+test_kind!(
+    test_kind_high_surrogate_alone,
+    "\"\\ud800abc\"",
+    LoneSurrogate
+);
+test_kind!(
+    test_kind_high_surrogate_pair,
+    "\"\\ud800\\u0041\"",
+    LoneSurrogate
+);
+test_kind!(test_kind_control_character, "\"a\tb\"", ControlCharacter);
+test_kind!(
+    test_kind_duplicate_key,
+    "{\"a\": 1, \"a\": 2}",
+    DuplicateKey
+);
+test_kind!(test_kind_trailing_data, "[1] [2]", TrailingData);
+
+#[test]
+fn test_kind_invalid_utf8() {
+    for bytes in [
+        &b"\"\xff\""[..],
+        &b"\"\xc3\x28\""[..],
+        &b"\"\xed\xa0\x80\""[..],
+    ] {
+        let err = JsonLoader::from(bytes).load().err().unwrap();
+        assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::InvalidUtf8));
+    }
+}
+
+#[test]
+fn test_kind_of_other_errors() {
+    assert_eq!(dade::Error::parse_err("abc").parse_error_kind(), None);
+    assert_eq!(dade::Error::validate_err("abc").parse_error_kind(), None);
+}
+
+#[test]
+fn test_location_after_refill() {
+    let json = format!("[{}\n\n  x]", "1,\n".repeat(10000));
+    let reader = BufReader::with_capacity(5, json.as_bytes());
+    let err = JsonLoader::from(ReadBytes::new(reader))
+        .load()
+        .err()
+        .unwrap();
+    assert_eq!(err.offset(), Some(30005));
+    assert_eq!(err.line(), Some(10003));
+    assert_eq!(err.column(), Some(3));
+}
+
+/// A stream which implements only the required methods.
+struct Minimal<'a>(SliceBytes<'a>);
+
+impl<'a> Stream<u8> for Minimal<'a> {
+    fn peek(&mut self) -> Option<&u8> {
+        self.0.peek()
+    }
+    fn next(&mut self) -> Option<&u8> {
+        self.0.next()
+    }
+    fn while_if<F>(&mut self, cond: F)
+    where
+        F: Fn(&u8) -> bool,
+    {
+        self.0.while_if(cond)
+    }
+    fn next_if<F>(&mut self, cond: F) -> bool
+    where
+        F: Fn(&u8) -> bool,
+    {
+        self.0.next_if(cond)
+    }
+    fn expect(&mut self, it: &[u8]) -> bool {
+        self.0.expect(it)
+    }
+    fn position(&self) -> usize {
+        self.0.position()
+    }
+    fn finish(&self) -> bool {
+        self.0.finish()
+    }
+    fn skip(&mut self) {
+        self.0.skip()
+    }
+    fn skip_by(&mut self, size: usize) {
+        self.0.skip_by(size)
+    }
+    fn anchor(&mut self) {
+        self.0.anchor()
+    }
+    fn read(&mut self) -> &[u8] {
+        self.0.read()
+    }
+}
+
+#[test]
+fn test_location_of_minimal_stream() {
+    // The input is treated as one line without a snippet.
+    let stream = Minimal(SliceBytes::new(b"[1,\n  x]"));
+    let err = JsonLoader::new(stream).load().err().unwrap();
+    assert_eq!(err.offset(), Some(6));
+    assert_eq!(err.line(), Some(1));
+    assert_eq!(err.column(), Some(7));
+    assert_eq!(err.snippet(), Some(""));
+    assert!(JsonLoader::new(Minimal(SliceBytes::new(b"[1] ")))
+        .load()
+        .is_ok());
+}
+
+#[test]
+fn test_depth_limit() {
+    let json = "[".repeat(100000);
+    let err = json_load(json.as_str()).err().unwrap();
+    assert_eq!(
+        err.parse_error_kind(),
+        Some(ParseErrorKind::DepthLimitExceeded)
+    );
+    assert_eq!(err.offset(), Some(DEFAULT_MAX_DEPTH));
+
+    let json = "{\"a\":".repeat(100000);
+    let err = json_load_reader(json.as_bytes()).err().unwrap();
+    assert_eq!(
+        err.parse_error_kind(),
+        Some(ParseErrorKind::DepthLimitExceeded)
+    );
+
+    let json = format!(
+        "{}{}",
+        "[".repeat(DEFAULT_MAX_DEPTH),
+        "]".repeat(DEFAULT_MAX_DEPTH)
+    );
+    assert!(json_load(json.as_str()).is_ok());
+}
+
+#[test]
+fn test_max_depth() {
+    for json in ["[[1], {\"a\": 2}]", "{\"a\": [], \"b\": {}}", "1"] {
+        assert!(JsonLoader::from(json).with_max_depth(2).load().is_ok());
+    }
+    for json in ["[[[1]]]", "{\"a\": [{}]}"] {
+        let err = JsonLoader::from(json)
+            .with_max_depth(2)
+            .load()
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.parse_error_kind(),
+            Some(ParseErrorKind::DepthLimitExceeded)
+        );
+    }
+    assert!(JsonLoader::from("1").with_max_depth(0).load().is_ok());
+    assert!(JsonLoader::from("[]").with_max_depth(0).load().is_err());
+
+    // The depth is reset for each load.
+    let mut loader = JsonLoader::from("[[1]]").with_max_depth(2);
+    assert!(loader.load().is_ok());
+}
+
+#[test]
+fn test_depth_limit_in_model() {
+    #[model]
+    struct Item {
+        children: Vec<Item>,
+    }
+
+    let json = "{\"children\": [".repeat(100000);
+    let err = Item::parse(json.as_str()).err().unwrap();
+    assert_eq!(
+        err.parse_error_kind(),
+        Some(ParseErrorKind::DepthLimitExceeded)
+    );
+}
+
+macro_rules! test_limit {
+    ($test_name: ident, $limit: ident, $max: literal, $ok: literal, $ng: literal, $kind: ident) => {
+        #[test]
+        fn $test_name() {
+            let ret = JsonLoader::from($ok).$limit($max).load();
+            assert!(ret.is_ok(), "{}", ret.err().unwrap());
+            let err = JsonLoader::from($ng).$limit($max).load().err().unwrap();
+            assert_eq!(
+                err.parse_error_kind(),
+                Some(ParseErrorKind::$kind),
+                "{}",
+                err
+            );
+
+            let reader = BufReader::with_capacity(1, $ok.as_bytes());
+            let ret = JsonLoader::from(ReadBytes::new(reader)).$limit($max).load();
+            assert!(ret.is_ok(), "{}", ret.err().unwrap());
+            let reader = BufReader::with_capacity(1, $ng.as_bytes());
+            let err = JsonLoader::from(ReadBytes::new(reader))
+                .$limit($max)
+                .load()
+                .err()
+                .unwrap();
+            assert_eq!(
+                err.parse_error_kind(),
+                Some(ParseErrorKind::$kind),
+                "{}",
+                err
+            );
+        }
+    };
+}
+
+test_limit!(
+    test_max_input_size,
+    with_max_input_size,
+    8,
+    "[1, 2]  ",
+    "[1, 2]   ",
+    InputTooLarge
+);
+test_limit!(
+    test_max_input_size_in_string,
+    with_max_input_size,
+    8,
+    "\"abcdef\"",
+    "\"abcdefg\"",
+    InputTooLarge
+);
+test_limit!(
+    test_max_input_size_in_number,
+    with_max_input_size,
+    4,
+    "1234",
+    "12345",
+    InputTooLarge
+);
+test_limit!(
+    test_max_string_length,
+    with_max_string_length,
+    3,
+    "[\"abc\", \"\\u3042\"]",
+    "[\"abc\", \"abcd\"]",
+    StringTooLong
+);
+test_limit!(
+    test_max_string_length_with_escape,
+    with_max_string_length,
+    3,
+    "\"a\\nb\"",
+    "\"ab\\u3042\"",
+    StringTooLong
+);
+test_limit!(
+    test_max_string_length_of_key,
+    with_max_string_length,
+    3,
+    "{\"abc\": 1}",
+    "{\"abcd\": 1}",
+    StringTooLong
+);
+test_limit!(
+    test_max_number_length,
+    with_max_number_length,
+    4,
+    "[-1.5, 1e10]",
+    "[1.2e-3]",
+    NumberTooLong
+);
+test_limit!(
+    test_max_array_length,
+    with_max_array_length,
+    2,
+    "[[1, 2], [3]]",
+    "[[1, 2, 3]]",
+    TooManyItems
+);
+test_limit!(
+    test_max_object_members,
+    with_max_object_members,
+    2,
+    "{\"a\": {\"b\": 1, \"c\": 2}}",
+    "{\"a\": 1, \"b\": 2, \"c\": 3}",
+    TooManyMembers
+);
+
+#[test]
+fn test_limit_location() {
+    let err = JsonLoader::from("[1, \"abcdef\"]")
+        .with_max_string_length(3)
+        .load()
+        .err()
+        .unwrap();
+    assert_eq!(err.offset(), Some(8));
+
+    let err = JsonLoader::from("[1, 123456]")
+        .with_max_number_length(3)
+        .load()
+        .err()
+        .unwrap();
+    assert_eq!(err.offset(), Some(4));
+
+    let err = JsonLoader::from("[1, 2,  3]")
+        .with_max_array_length(2)
+        .load()
+        .err()
+        .unwrap();
+    assert_eq!(err.offset(), Some(8));
+
+    let err = JsonLoader::from("{\"a\": 1, \"b\": 2}")
+        .with_max_object_members(1)
+        .load()
+        .err()
+        .unwrap();
+    assert_eq!(err.offset(), Some(9));
+}
+
+#[test]
+fn test_limit_before_reading_all() {
+    // The loader stops reading a long token as soon as it exceeds the limit.
+    struct Endless {
+        head: &'static [u8],
+        fill: u8,
+        read: usize,
+    }
+
+    impl std::io::Read for Endless {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            buf[0] = *self.head.get(self.read).unwrap_or(&self.fill);
+            self.read += 1;
+            Ok(1)
+        }
+    }
+
+    fn endless(head: &'static [u8], fill: u8) -> Endless {
+        Endless {
+            head,
+            fill,
+            read: 0,
+        }
+    }
+
+    let mut reader = endless(b"\"", b'a');
+    let err = JsonLoader::from(ReadBytes::new(BufReader::new(&mut reader)))
+        .with_max_string_length(100)
+        .load()
+        .err()
+        .unwrap();
+    assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::StringTooLong));
+    assert!(reader.read < 200);
+
+    for head in [&b"1"[..], b"1.", b"1e"] {
+        let mut reader = endless(head, b'0');
+        let err = JsonLoader::from(ReadBytes::new(BufReader::new(&mut reader)))
+            .with_max_number_length(100)
+            .load()
+            .err()
+            .unwrap();
+        assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::NumberTooLong));
+        assert_eq!(err.offset(), Some(0));
+        assert!(reader.read < 200);
+
+        let mut reader = endless(head, b'0');
+        let err = JsonLoader::from(ReadBytes::new(BufReader::new(&mut reader)))
+            .with_max_input_size(100)
+            .load()
+            .err()
+            .unwrap();
+        assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::InputTooLarge));
+        assert!(reader.read < 200);
+    }
+
+    for (head, fill) in [
+        (&b"["[..], b' '),
+        (b"[1] ", b'\n'),
+        (b"// ", b'a'),
+        (b"/* ", b'a'),
+    ] {
+        let mut reader = endless(head, fill);
+        let err = JsonLoader::from(ReadBytes::new(BufReader::new(&mut reader)))
+            .with_comments(true)
+            .with_max_input_size(100)
+            .load()
+            .err()
+            .unwrap();
+        assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::InputTooLarge));
+        assert!(reader.read < 200);
+    }
+}
@@ -0,0 +1,156 @@
+use dade::{
+    json_dump, json_load, json_load_reader, model, ErrorType, JsonEvent, JsonEventReader,
+    JsonLoader, Model, ReadBytes, Stream,
+};
+use std::fs::File;
+use std::io::{BufReader, Read};
+
+fn read_contents(file: &str) -> String {
+    let file = File::open(file).unwrap();
+    let mut buf_reader = BufReader::new(file);
+    let mut contents = String::new();
+    buf_reader.read_to_string(&mut contents).unwrap();
+    contents
+}
+
+macro_rules! test_same_as_str {
+    ($test_name: ident, $file: literal) => {
+        #[test]
+        fn $test_name() {
+            let expected = json_dump(&json_load(read_contents($file).as_str()).unwrap(), false);
+
+            let ret = json_load_reader(File::open($file).unwrap());
+            assert!(ret.is_ok(), "{}", ret.err().unwrap());
+            assert_eq!(json_dump(&ret.unwrap(), false), expected);
+
+            // A tiny buffer forces refills in the middle of every token.
+            for capacity in [1, 2, 3, 7] {
+                let reader = BufReader::with_capacity(capacity, File::open($file).unwrap());
+                let ret = JsonLoader::from(ReadBytes::new(reader)).load();
+                assert!(ret.is_ok(), "{}", ret.err().unwrap());
+                assert_eq!(json_dump(&ret.unwrap(), false), expected);
+            }
+        }
+    };
+}
+
+test_same_as_str!(test_citm_catalog, "./tests/data/citm_catalog.json");
+test_same_as_str!(test_twitter, "./tests/data/twitter.json");
+test_same_as_str!(test_unicode, "./tests/data/unicode.json");
+test_same_as_str!(test_roundtrip13, "./tests/data/roundtrip/roundtrip13.json");
+test_same_as_str!(test_roundtrip24, "./tests/data/roundtrip/roundtrip24.json");
+test_same_as_str!(test_pass01, "./tests/data/jsonchecker/pass01.json");
+
+#[test]
+fn test_invalid_input() {
+    for json in ["[1,]", "{\"a\" 1}", "\"abc", "[1] 2", "nul", ""] {
+        let reader = BufReader::with_capacity(1, json.as_bytes());
+        assert!(JsonLoader::from(ReadBytes::new(reader)).load().is_err());
+        assert!(json_load_reader(json.as_bytes()).is_err());
+    }
+}
+
+#[test]
+fn test_io_error() {
+    struct BrokenReader {
+        sent: bool,
+    }
+
+    impl Read for BrokenReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.sent {
+                Err(std::io::Error::other("broken"))
+            } else {
+                self.sent = true;
+                buf[..3].copy_from_slice(b"[1,");
+                Ok(3)
+            }
+        }
+    }
+
+    let ret = json_load_reader(BrokenReader { sent: false });
+    assert!(matches!(ret.err().unwrap().err_type(), ErrorType::IoError));
+}
+
+#[test]
+fn test_model() {
+    #[model]
+    struct Item {
+        #[field(ge = 1)]
+        id: u64,
+        name: String,
+    }
+
+    let ret = Item::parse_reader("{\"id\": 1, \"name\": \"abc\"}".as_bytes());
+    assert!(ret.is_ok());
+    assert_eq!(ret.unwrap().json(false), "{\"id\":1,\"name\":\"abc\"}");
+
+    assert!(Item::parse_reader("{\"id\": 0, \"name\": \"abc\"}".as_bytes()).is_err());
+    assert!(Item::parse_reader("{\"id\": 1, \"name\": \"abc\"".as_bytes()).is_err());
+}
+
+#[test]
+fn test_bounded_buffer() {
+    /// Generates `[{"id": 0, "name": "..."}, ...]` without holding it in memory.
+    struct Generator {
+        count: usize,
+        total: usize,
+        pending: Vec<u8>,
+    }
+
+    impl Read for Generator {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.pending.is_empty() {
+                self.pending = if self.count == 0 {
+                    b"[".to_vec()
+                } else if self.count <= self.total {
+                    format!("{{\"id\": {}, \"name\": \"item\"}},\n", self.count).into_bytes()
+                } else if self.count == self.total + 1 {
+                    b"{}]".to_vec()
+                } else {
+                    return Ok(0);
+                };
+                self.count += 1;
+            }
+            let length = self.pending.len().min(buf.len());
+            buf[..length].copy_from_slice(&self.pending[..length]);
+            self.pending.drain(..length);
+            Ok(length)
+        }
+    }
+
+    let total = 500_000;
+    let reader = BufReader::new(Generator {
+        count: 0,
+        total,
+        pending: Vec::new(),
+    });
+    let mut events = JsonEventReader::new(JsonLoader::from(ReadBytes::new(reader)));
+    let mut objects = 0;
+    for item in events.by_ref() {
+        if let JsonEvent::StartObject = item.unwrap().1 {
+            objects += 1;
+        }
+    }
+    assert_eq!(objects, total + 1);
+    assert!(events.stream_mut().position() > 10_000_000);
+    assert!(events.stream_mut().buffer_capacity() <= 64 * 1024);
+
+    // A long token is kept as a whole across refills.
+    let json = format!("[\"{}\", 1]", "a".repeat(1_000_000));
+    let reader = BufReader::with_capacity(64, json.as_bytes());
+    let value = JsonLoader::from(ReadBytes::new(reader)).load().unwrap();
+    assert_eq!(value[0].as_str().unwrap().len(), 1_000_000);
+}
+
+#[test]
+fn test_finish() {
+    // The end of input is found without a failed peek.
+    let mut stream = ReadBytes::new(BufReader::with_capacity(1, "ab".as_bytes()));
+    assert!(!stream.finish());
+    assert!(!stream.at_end());
+    stream.skip_by(2);
+    assert!(!stream.finish());
+    assert!(stream.at_end());
+    assert!(stream.finish());
+}
@@ -0,0 +1,198 @@
+use dade::{json_dump, model, JsonLoader, Model, ParseErrorKind, ReadBytes, Result, SliceBytes};
+use std::io::BufReader;
+
+type Configure = fn(JsonLoader<u8, SliceBytes>) -> JsonLoader<u8, SliceBytes>;
+
+fn load(json: &str, configure: Configure) -> Result<String> {
+    configure(JsonLoader::from(json))
+        .load()
+        .map(|value| json_dump(&value, false))
+}
+
+fn load_relaxed(json: &str) -> Result<String> {
+    let reader = BufReader::with_capacity(1, json.as_bytes());
+    let from_reader = JsonLoader::from(ReadBytes::new(reader))
+        .with_relaxed(true)
+        .load()
+        .map(|value| json_dump(&value, false));
+    let from_str = load(json, |loader| loader.with_relaxed(true));
+    assert_eq!(
+        from_reader
+            .as_ref()
+            .map_err(|err| (err.parse_error_kind(), err.offset())),
+        from_str
+            .as_ref()
+            .map_err(|err| (err.parse_error_kind(), err.offset())),
+        "{}",
+        json
+    );
+    from_str
+}
+
+fn error_kind(ret: Result<String>) -> Option<ParseErrorKind> {
+    ret.err().unwrap().parse_error_kind()
+}
+
+#[test]
+fn test_strict_by_default() {
+    for (json, kind) in [
+        ("[1] // a", ParseErrorKind::TrailingData),
+        ("/* a */ 1", ParseErrorKind::ExpectedValue),
+        ("[1,]", ParseErrorKind::TrailingComma),
+        ("{\"a\": 1,}", ParseErrorKind::TrailingComma),
+        ("'a'", ParseErrorKind::ExpectedValue),
+        ("{'a': 1}", ParseErrorKind::KeyMustBeString),
+        ("{a: 1}", ParseErrorKind::KeyMustBeString),
+    ] {
+        assert_eq!(
+            error_kind(load(json, |loader| loader)),
+            Some(kind),
+            "{}",
+            json
+        );
+    }
+    assert_eq!(
+        error_kind(load("{\"a\": 1,}", |loader| loader.with_relaxed(false))),
+        Some(ParseErrorKind::TrailingComma)
+    );
+}
+
+#[test]
+fn test_comments() {
+    let json = "// head\n{ /* a */ \"a\" /* b */ : // c\n [1, /* d */ 2 /**/] // e\n, \"b\": 1}/* f */\n// g";
+    assert_eq!(
+        load(json, |loader| loader.with_comments(true)).unwrap(),
+        "{\"a\":[1,2],\"b\":1}"
+    );
+    assert_eq!(load_relaxed(json).unwrap(), "{\"a\":[1,2],\"b\":1}");
+    assert_eq!(load_relaxed("1 // a").unwrap(), "1");
+    assert_eq!(load_relaxed("\"/* a */\"").unwrap(), "\"/* a */\"");
+
+    let err = load_relaxed("[1] /* a").err().unwrap();
+    assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::UnexpectedEof));
+    assert_eq!(err.offset(), Some(8));
+    assert_eq!(
+        error_kind(load_relaxed("[1, /* a */")),
+        Some(ParseErrorKind::UnexpectedEof)
+    );
+    assert_eq!(
+        error_kind(load_relaxed("[1] / a")),
+        Some(ParseErrorKind::TrailingData)
+    );
+    // Comments are the only extension which is enabled.
+    assert_eq!(
+        error_kind(load("[1, /* a */]", |loader| loader.with_comments(true))),
+        Some(ParseErrorKind::TrailingComma)
+    );
+}
+
+#[test]
+fn test_trailing_commas() {
+    let enable: Configure = |loader| loader.with_trailing_commas(true);
+    assert_eq!(load("[1, 2,]", enable).unwrap(), "[1,2]");
+    assert_eq!(load("{\"a\": [1 , ] , }", enable).unwrap(), "{\"a\":[1]}");
+    assert_eq!(load_relaxed("[[1,],\n]").unwrap(), "[[1]]");
+
+    for json in ["[,]", "{,}", "[1,,]", "{\"a\": 1,,}"] {
+        assert!(load(json, enable).is_err(), "{}", json);
+    }
+}
+
+#[test]
+fn test_single_quotes() {
+    let enable: Configure = |loader| loader.with_single_quotes(true);
+    assert_eq!(
+        load("['a\"b', 'c\\'d', \"e'f\"]", enable).unwrap(),
+        "[\"a\\\"b\",\"c'd\",\"e'f\"]"
+    );
+    assert_eq!(
+        load("{'a': '\\u3042'}", enable).unwrap(),
+        "{\"a\":\"\u{3042}\"}"
+    );
+    assert_eq!(load_relaxed("['', '\\n']").unwrap(), "[\"\",\"\\n\"]");
+    assert_eq!(
+        error_kind(load("['a]", enable)),
+        Some(ParseErrorKind::UnexpectedEof)
+    );
+    // `\'` is an escape only in the relaxed grammar.
+    assert!(load("\"\\'\"", |loader| loader).is_err());
+}
+
+#[test]
+fn test_unquoted_keys() {
+    let enable: Configure = |loader| loader.with_unquoted_keys(true);
+    assert_eq!(
+        load("{a: 1, _b2: 2, $c: 3, \u{3042}: 4, \"d\": 5}", enable).unwrap(),
+        "{\"a\":1,\"_b2\":2,\"$c\":3,\"\u{3042}\":4,\"d\":5}"
+    );
+    assert_eq!(
+        load_relaxed("{ key : 'value' }").unwrap(),
+        "{\"key\":\"value\"}"
+    );
+
+    let err = load("{1a: 1}", enable).err().unwrap();
+    assert_eq!(
+        err.parse_error_kind(),
+        Some(ParseErrorKind::KeyMustBeString)
+    );
+    assert_eq!(err.offset(), Some(1));
+    let err = load("{a-b: 1}", enable).err().unwrap();
+    assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::ExpectedColon));
+    assert_eq!(err.offset(), Some(2));
+    let err = load("{a: 1, a: 2}", enable).err().unwrap();
+    assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::DuplicateKey));
+    assert_eq!(err.offset(), Some(7));
+    // Values are not identifiers.
+    assert_eq!(
+        error_kind(load("{a: b}", enable)),
+        Some(ParseErrorKind::ExpectedValue)
+    );
+
+    assert_eq!(
+        error_kind(load("{abcd: 1}", |loader| loader
+            .with_unquoted_keys(true)
+            .with_max_string_length(3))),
+        Some(ParseErrorKind::StringTooLong)
+    );
+    assert!(load("{abc: 1}", |loader| loader
+        .with_unquoted_keys(true)
+        .with_max_string_length(3))
+    .is_ok());
+}
+
+#[model]
+struct Server {
+    host: String,
+    #[field(ge = 1)]
+    port: u16,
+    tags: Vec<String>,
+}
+
+#[model]
+struct Config {
+    name: String,
+    servers: Vec<Server>,
+}
+
+#[test]
+fn test_parse_config() {
+    let json = "// servers\n{\n  name: 'prod',\n  servers: [\n    {host: 'a', port: 80, tags: ['x',],},\n    /* backup */ {host: \"b\", port: 8080, tags: [],},\n  ],\n}\n";
+    let config = Config::parse_loader(&mut JsonLoader::from(json).with_relaxed(true)).unwrap();
+    assert_eq!(config.name, "prod");
+    assert_eq!(config.servers.len(), 2);
+    assert_eq!(config.servers[0].host, "a");
+    assert_eq!(config.servers[0].tags, ["x"]);
+    assert_eq!(config.servers[1].port, 8080);
+
+    let reader = ReadBytes::new(BufReader::with_capacity(1, json.as_bytes()));
+    let config = Config::parse_loader(&mut JsonLoader::from(reader).with_relaxed(true)).unwrap();
+    assert_eq!(config.servers[1].host, "b");
+
+    assert!(Config::parse(json).is_err());
+
+    let json = "{name: 'prod', servers: [{host: 'a', port: 0, tags: []}]}";
+    let err = Config::parse_loader(&mut JsonLoader::from(json).with_relaxed(true))
+        .err()
+        .unwrap();
+    assert_eq!(err.errors()[0].loc_path(), "servers[0].port");
+}
@@ -0,0 +1,89 @@
+use dade::{json, json_dump, json_load, model, JsonValue, Map, Model};
+
+#[test]
+fn test_literals() {
+    assert_eq!(json!(null), JsonValue::Null);
+    assert_eq!(json!(true), JsonValue::Bool(true));
+    assert_eq!(json!(false), JsonValue::Bool(false));
+    assert_eq!(json!(1), JsonValue::from(1));
+    assert_eq!(json!(-1.5), JsonValue::from(-1.5));
+    assert_eq!(json!("a"), JsonValue::from("a"));
+    assert_eq!(json!([]), JsonValue::Array(Vec::new()));
+    assert_eq!(json!({}), JsonValue::Object(Map::new()));
+
+    let value = json!({
+        "a": [1, 2.5, -3, null, true, false, "x", [], {}],
+        "b": {"c": {"d": [[null]]}},
+        "e": null,
+    });
+    let expected = json_load(
+        "{\"a\": [1, 2.5, -3, null, true, false, \"x\", [], {}], \"b\": {\"c\": {\"d\": [[null]]}}, \"e\": null}",
+    )
+    .unwrap();
+    assert_eq!(value, expected);
+    // The order of keys is as written.
+    assert_eq!(
+        json_dump(&json!({"z": 1, "y": 2}), false),
+        "{\"z\":1,\"y\":2}"
+    );
+    // Without trailing commas.
+    assert_eq!(json!([1, [2]]).to_string(), "[1,[2]]");
+    assert_eq!(json!({"a": {"b": 1}}).to_string(), "{\"a\":{\"b\":1}}");
+    // A later duplicate replaces the value.
+    assert_eq!(json!({"a": 1, "a": 2}).to_string(), "{\"a\":2}");
+}
+
+#[model]
+struct Point {
+    x: i64,
+    y: i64,
+}
+
+#[test]
+fn test_interpolation() {
+    let name = "dade".to_string();
+    let count = 3u8;
+    let items = vec![Some(1.5), None];
+    let point = Point { x: 1, y: -2 };
+    let nested = json!([1, 2]);
+    let value = json!({
+        "name": name,
+        "count": count + 1,
+        "items": items,
+        "point": point,
+        "nested": nested,
+        "list": [name.len(), point.x * 10, nested[1]],
+        (format!("key{}", count)): true,
+        count: "a key by `ToString`",
+    });
+    assert_eq!(
+        value.to_string(),
+        "{\"name\":\"dade\",\"count\":4,\"items\":[1.5,null],\"point\":{\"x\":1,\"y\":-2},\
+         \"nested\":[1,2],\"list\":[4,10,2],\"key3\":true,\"3\":\"a key by `ToString`\"}"
+    );
+    // Interpolated values are borrowed.
+    assert_eq!(name, "dade");
+    assert_eq!(point.json(false), "{\"x\":1,\"y\":-2}");
+}
+
+#[test]
+fn test_schema_unchanged() {
+    assert_eq!(
+        Point::schema(),
+        json!({
+            "$ref": "#/definitions/Point",
+            "definitions": {
+                "Point": {
+                    "properties": {
+                        "x": {"title": "X", "type": "integer"},
+                        "y": {"title": "Y", "type": "integer"},
+                    },
+                    "required": ["x", "y"],
+                    "title": "Point",
+                    "type": "object",
+                },
+            },
+        })
+        .to_string()
+    );
+}
@@ -0,0 +1,101 @@
+use dade::{json, model, Model};
+
+#[test]
+fn test_rfc_examples() {
+    for (target, patch, expected) in [
+        (json!({"a": "b"}), json!({"a": "c"}), json!({"a": "c"})),
+        (
+            json!({"a": "b"}),
+            json!({"b": "c"}),
+            json!({"a": "b", "b": "c"}),
+        ),
+        (json!({"a": "b"}), json!({"a": null}), json!({})),
+        (
+            json!({"a": "b", "b": "c"}),
+            json!({"a": null}),
+            json!({"b": "c"}),
+        ),
+        (json!({"a": ["b"]}), json!({"a": "c"}), json!({"a": "c"})),
+        (json!({"a": "c"}), json!({"a": ["b"]}), json!({"a": ["b"]})),
+        (
+            json!({"a": {"b": "c"}}),
+            json!({"a": {"b": "d", "c": null}}),
+            json!({"a": {"b": "d"}}),
+        ),
+        (
+            json!({"a": [{"b": "c"}]}),
+            json!({"a": [1]}),
+            json!({"a": [1]}),
+        ),
+        (json!(["a", "b"]), json!(["c", "d"]), json!(["c", "d"])),
+        (json!({"a": "b"}), json!(["c"]), json!(["c"])),
+        (json!({"a": "foo"}), json!(null), json!(null)),
+        (json!({"a": "foo"}), json!("bar"), json!("bar")),
+        (
+            json!({"e": null}),
+            json!({"a": 1}),
+            json!({"e": null, "a": 1}),
+        ),
+        (
+            json!([1, 2]),
+            json!({"a": "b", "c": null}),
+            json!({"a": "b"}),
+        ),
+        (
+            json!({}),
+            json!({"a": {"bb": {"ccc": null}}}),
+            json!({"a": {"bb": {}}}),
+        ),
+    ] {
+        let mut value = target.clone();
+        value.merge_patch(&patch);
+        assert_eq!(value, expected, "{} + {}", target, patch);
+    }
+}
+
+#[model]
+struct Profile {
+    #[field(min_length = 1)]
+    name: String,
+    #[field(ge = 0, le = 150)]
+    age: u8,
+    #[field(default = null)]
+    bio: Option<String>,
+    tags: Vec<String>,
+}
+
+#[test]
+fn test_model() {
+    let profile =
+        Profile::parse("{\"name\": \"a\", \"age\": 20, \"bio\": \"hi\", \"tags\": [\"x\"]}")
+            .unwrap();
+
+    let updated = profile
+        .apply_merge_patch("{\"age\": 21, \"bio\": null, \"tags\": [\"y\", \"z\"]}")
+        .unwrap();
+    assert_eq!(
+        updated.json(false),
+        "{\"name\":\"a\",\"age\":21,\"bio\":null,\"tags\":[\"y\",\"z\"]}"
+    );
+    // The original is unchanged.
+    assert_eq!(profile.age, 20);
+    assert_eq!(
+        profile.apply_merge_patch("{}").unwrap().json(false),
+        profile.json(false)
+    );
+
+    // Validation runs on the result.
+    let err = profile
+        .apply_merge_patch("{\"name\": \"\", \"age\": 151}")
+        .err()
+        .unwrap();
+    let paths: Vec<_> = err.errors().iter().map(|err| err.loc_path()).collect();
+    assert_eq!(paths, ["name", "age"]);
+    // A required field cannot be removed.
+    let err = profile.apply_merge_patch("{\"tags\": null}").err().unwrap();
+    assert_eq!(err.errors()[0].constraint(), "missing");
+    // The patch must be valid JSON.
+    assert!(profile.apply_merge_patch("{\"age\": }").is_err());
+    // A patch other than an object replaces the whole.
+    assert!(profile.apply_merge_patch("[]").is_err());
+}
@@ -0,0 +1,118 @@
+use dade::{json_dump, json_load, model, Map, Model};
+
+#[test]
+fn test_load_and_dump_keep_order() {
+    for json in [
+        "{\"b\":1,\"a\":2,\"c\":{\"z\":null,\"y\":[{\"2\":true,\"1\":false}]}}",
+        "{\"\":0,\"\u{3042}\":1,\"a\":2,\"A\":3}",
+    ] {
+        assert_eq!(json_dump(&json_load(json).unwrap(), false), json);
+    }
+    let mut value = json_load("{\"b\":1,\"a\":{\"d\":2,\"c\":3}}").unwrap();
+    value.sort_keys();
+    assert_eq!(
+        json_dump(&value, false),
+        "{\"a\":{\"c\":3,\"d\":2},\"b\":1}"
+    );
+}
+
+#[test]
+fn test_map() {
+    // Over the size where keys are indexed by a hash table.
+    for size in [3, 40] {
+        let mut dict = Map::new();
+        for idx in (0..size).rev() {
+            assert_eq!(dict.insert(idx.to_string(), idx), None);
+        }
+        assert_eq!(dict.len(), size);
+        assert_eq!(dict.insert("1".to_string(), 100), Some(1));
+        assert_eq!(dict["1"], 100);
+        assert_eq!(dict.keys().last(), Some(&"0".to_string()));
+
+        assert_eq!(dict.remove("2"), Some(2));
+        assert_eq!(dict.remove("2"), None);
+        assert!(!dict.contains_key("2"));
+        let keys: Vec<_> = dict.keys().rev().take(2).collect();
+        assert_eq!(keys, ["0", "1"]);
+        assert_eq!(dict.get("0"), Some(&0));
+        *dict.get_mut("0").unwrap() = 1000;
+        assert_eq!(dict["0"], 1000);
+
+        dict.sort_keys();
+        assert_eq!(dict.keys().next(), Some(&"0".to_string()));
+        assert_eq!(dict["1"], 100);
+        assert_eq!(dict.iter().count(), size - 1);
+    }
+}
+
+#[test]
+fn test_map_remove() {
+    for size in [10, 100] {
+        let mut dict = Map::new();
+        for idx in 0..size {
+            dict.insert(idx.to_string(), idx);
+        }
+        // Every other member is removed, keeping the order of the rest.
+        for idx in (0..size).step_by(2) {
+            assert_eq!(dict.remove(idx.to_string().as_str()), Some(idx));
+        }
+        let expected: Vec<_> = (1..size).step_by(2).collect();
+        assert_eq!(dict.values().copied().collect::<Vec<_>>(), expected);
+        for idx in expected.iter() {
+            assert_eq!(dict.get(idx.to_string().as_str()), Some(idx));
+        }
+
+        // The last member is moved to the removed position.
+        assert_eq!(dict.swap_remove("1"), Some(1));
+        assert_eq!(dict.swap_remove("1"), None);
+        assert_eq!(dict.values().next(), Some(&(size - 1)));
+        assert_eq!(dict.len(), size / 2 - 1);
+        for idx in expected.iter().skip(1) {
+            assert_eq!(dict.get(idx.to_string().as_str()), Some(idx));
+        }
+        assert_eq!(
+            dict.swap_remove((size - 1).to_string().as_str()),
+            Some(size - 1)
+        );
+        assert_eq!(dict.get("3"), Some(&3));
+    }
+}
+
+#[model]
+struct Point {
+    y: f64,
+    #[field(alias = "X")]
+    x: f64,
+    #[field(default = "origin")]
+    label: String,
+}
+
+#[model]
+enum Shape {
+    Circle { radius: f64, center: Point },
+    Line(Point, Point),
+}
+
+#[test]
+fn test_model_in_declaration_order() {
+    let point = Point::parse("{\"label\": \"a\", \"X\": 1, \"y\": 2}").unwrap();
+    assert_eq!(point.json(false), "{\"y\":2,\"X\":1,\"label\":\"a\"}");
+    assert_eq!(
+        json_dump(&dade::ToJsonValue::to_json_value(&point), false),
+        point.json(false)
+    );
+
+    let shape = Shape::parse("{\"center\": {\"X\": 0, \"y\": 1}, \"radius\": 2}").unwrap();
+    assert_eq!(
+        shape.json(false),
+        "{\"radius\":2,\"center\":{\"y\":1,\"X\":0,\"label\":\"origin\"}}"
+    );
+
+    // The schema is still sorted by keys.
+    let schema = Point::schema();
+    let positions: Vec<_> = ["\"X\":", "\"label\":", "\"y\":"]
+        .iter()
+        .map(|key| schema.find(key).unwrap())
+        .collect();
+    assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+}
@@ -0,0 +1,246 @@
+use dade::{json, json_load, model, FromJsonValue, JsonValue, ToJsonValue};
+
+fn apply(value: JsonValue, patch: JsonValue) -> dade::Result<JsonValue> {
+    let mut patched = value.clone();
+    let ret = patched.apply_patch(&patch);
+    if ret.is_err() {
+        // The value is unchanged on an error.
+        assert_eq!(patched, value);
+    }
+    ret.map(|_| patched)
+}
+
+#[test]
+fn test_rfc_examples() {
+    for (doc, patch, expected) in [
+        (
+            json!({"foo": "bar"}),
+            json!([{"op": "add", "path": "/baz", "value": "qux"}]),
+            json!({"baz": "qux", "foo": "bar"}),
+        ),
+        (
+            json!({"foo": ["bar", "baz"]}),
+            json!([{"op": "add", "path": "/foo/1", "value": "qux"}]),
+            json!({"foo": ["bar", "qux", "baz"]}),
+        ),
+        (
+            json!({"baz": "qux", "foo": "bar"}),
+            json!([{"op": "remove", "path": "/baz"}]),
+            json!({"foo": "bar"}),
+        ),
+        (
+            json!({"foo": ["bar", "qux", "baz"]}),
+            json!([{"op": "remove", "path": "/foo/1"}]),
+            json!({"foo": ["bar", "baz"]}),
+        ),
+        (
+            json!({"baz": "qux", "foo": "bar"}),
+            json!([{"op": "replace", "path": "/baz", "value": "boo"}]),
+            json!({"baz": "boo", "foo": "bar"}),
+        ),
+        (
+            json!({"foo": {"bar": "baz", "waldo": "fred"}, "qux": {"corge": "grault"}}),
+            json!([{"op": "move", "from": "/foo/waldo", "path": "/qux/thud"}]),
+            json!({"foo": {"bar": "baz"}, "qux": {"corge": "grault", "thud": "fred"}}),
+        ),
+        (
+            json!({"foo": ["all", "grass", "cows", "eat"]}),
+            json!([{"op": "move", "from": "/foo/1", "path": "/foo/3"}]),
+            json!({"foo": ["all", "cows", "eat", "grass"]}),
+        ),
+        (
+            json!({"baz": "qux", "foo": ["a", 2, "c"]}),
+            json!([
+                {"op": "test", "path": "/baz", "value": "qux"},
+                {"op": "test", "path": "/foo/1", "value": 2},
+            ]),
+            json!({"baz": "qux", "foo": ["a", 2, "c"]}),
+        ),
+        (
+            json!({"foo": "bar"}),
+            json!([{"op": "add", "path": "/child", "value": {"grandchild": {}}}]),
+            json!({"foo": "bar", "child": {"grandchild": {}}}),
+        ),
+        (
+            json!({"foo": "bar"}),
+            json!([{"op": "add", "path": "/baz", "value": "qux", "xyz": 123}]),
+            json!({"foo": "bar", "baz": "qux"}),
+        ),
+        (
+            json!({"foo": ["bar"]}),
+            json!([{"op": "add", "path": "/foo/-", "value": ["abc", "def"]}]),
+            json!({"foo": ["bar", ["abc", "def"]]}),
+        ),
+        (
+            json!({"/": 9, "~1": 10}),
+            json!([{"op": "test", "path": "/~01", "value": 10}]),
+            json!({"/": 9, "~1": 10}),
+        ),
+        (
+            json!({"foo": 1}),
+            json!([{"op": "copy", "from": "/foo", "path": "/bar"}]),
+            json!({"foo": 1, "bar": 1}),
+        ),
+        (
+            json!({"foo": 1}),
+            json!([{"op": "replace", "path": "", "value": [1]}]),
+            json!([1]),
+        ),
+        (
+            json!({"foo": {"bar": 1}}),
+            json!([{"op": "move", "from": "/foo", "path": "/foo"}]),
+            json!({"foo": {"bar": 1}}),
+        ),
+    ] {
+        assert_eq!(apply(doc, patch.clone()).unwrap(), expected, "{}", patch);
+    }
+}
+
+#[test]
+fn test_errors() {
+    let doc = json!({"foo": "bar", "arr": [1]});
+    for (patch, path, constraint) in [
+        (json!({}), "", "type"),
+        (json!([1]), "[0]", "type"),
+        (json!([{"path": "/a"}]), "[0].op", "missing"),
+        (json!([{"op": "delete", "path": "/a"}]), "[0].op", "enum"),
+        (json!([{"op": "add", "value": 1}]), "[0].path", "missing"),
+        (
+            json!([{"op": "add", "path": 1, "value": 1}]),
+            "[0].path",
+            "type",
+        ),
+        (json!([{"op": "add", "path": "/a"}]), "[0].value", "missing"),
+        (json!([{"op": "move", "path": "/a"}]), "[0].from", "missing"),
+        (
+            json!([{"op": "add", "path": "/baz/bat", "value": "qux"}]),
+            "[0].path",
+            "pointer",
+        ),
+        (
+            json!([{"op": "add", "path": "/arr/2", "value": 2}]),
+            "[0].path",
+            "pointer",
+        ),
+        (
+            json!([{"op": "remove", "path": "/baz"}]),
+            "[0].path",
+            "pointer",
+        ),
+        (
+            json!([{"op": "replace", "path": "/baz", "value": 1}]),
+            "[0].path",
+            "pointer",
+        ),
+        (
+            json!([{"op": "move", "from": "/baz", "path": "/foo"}]),
+            "[0].from",
+            "pointer",
+        ),
+        (
+            json!([{"op": "move", "from": "/arr", "path": "/arr/0"}]),
+            "[0].path",
+            "pointer",
+        ),
+        (
+            json!([{"op": "copy", "from": "/baz", "path": "/foo"}]),
+            "[0].from",
+            "pointer",
+        ),
+        (
+            json!([{"op": "test", "path": "/baz", "value": 1}]),
+            "[0].path",
+            "pointer",
+        ),
+        (
+            json!([
+                {"op": "remove", "path": "/foo"},
+                {"op": "add", "path": "/foo/x", "value": 1},
+            ]),
+            "[1].path",
+            "pointer",
+        ),
+    ] {
+        let err = apply(doc.clone(), patch.clone()).err().unwrap();
+        assert_eq!(err.errors()[0].loc_path(), path, "{}", patch);
+        assert_eq!(err.errors()[0].constraint(), constraint, "{}", patch);
+    }
+
+    let patch = json!([
+        {"op": "add", "path": "/x", "value": 1},
+        {"op": "test", "path": "/foo", "value": "baz"},
+    ]);
+    let err = apply(doc, patch).err().unwrap();
+    let err = &err.errors()[0];
+    assert_eq!(err.loc_path(), "[1].value");
+    assert_eq!(err.constraint(), "test");
+    assert_eq!(err.value(), Some(&json!("bar")));
+    assert_eq!(err.limit(), Some(&json!("baz")));
+}
+
+#[test]
+fn test_diff() {
+    let cases = [
+        (json!(null), json!(null)),
+        (json!(1), json!(1.0)),
+        (json!({"a": 1}), json!({"a": 1})),
+        (json!({"a": 1, "b": 2}), json!({"b": 2, "a": 1})),
+        (json!(1), json!("1")),
+        (json!({"a": [1, 2, 3]}), json!({"a": [1]})),
+        (json!({"a": [1]}), json!({"a": [0, 1, 2]})),
+        (json!([{"a": 1}, 2]), json!([{"a": 2, "b": []}, 2, 3])),
+        (json!({"a/b": {"~": 1}}), json!({"a/b": {"~": 2}, "": null})),
+        (json!({"a": {}}), json!({"a": []})),
+        (json!([]), json!({})),
+    ];
+    for (source, target) in cases {
+        let patch = source.diff(&target);
+        if source == target {
+            assert_eq!(patch, json!([]));
+        }
+        let mut patched = source.clone();
+        patched.apply_patch(&patch).unwrap();
+        assert_eq!(patched, target, "{}", patch);
+        // The patch is valid for the loaded document.
+        let mut loaded = json_load(&source.to_string()).unwrap();
+        loaded
+            .apply_patch(&json_load(&patch.to_string()).unwrap())
+            .unwrap();
+        assert_eq!(loaded, target);
+    }
+    assert_eq!(
+        json!({"a/b": {"~": 1}}).diff(&json!({"a/b": {"~": 2}})),
+        json!([{"op": "replace", "path": "/a~1b/~0", "value": 2}])
+    );
+    assert_eq!(
+        json!([1, 2]).diff(&json!(null)),
+        json!([{"op": "replace", "path": "", "value": null}])
+    );
+}
+
+#[model]
+struct Account {
+    #[field(min_length = 1)]
+    name: String,
+    #[field(ge = 0)]
+    balance: i64,
+}
+
+#[test]
+fn test_revalidate_by_model() {
+    let account = Account {
+        name: "a".to_string(),
+        balance: 10,
+    };
+    let mut value = account.to_json_value();
+    value
+        .apply_patch(&json!([{"op": "replace", "path": "/balance", "value": 5}]))
+        .unwrap();
+    assert_eq!(Account::from_json_value(&value).unwrap().balance, 5);
+
+    value
+        .apply_patch(&json!([{"op": "replace", "path": "/balance", "value": -1}]))
+        .unwrap();
+    let err = Account::from_json_value(&value).err().unwrap();
+    assert_eq!(err.errors()[0].loc_path(), "balance");
+}
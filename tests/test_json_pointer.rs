@@ -0,0 +1,133 @@
+use dade::{json, model, JsonValue, Model};
+
+#[test]
+fn test_pointer() {
+    // The example of RFC 6901.
+    let value = json!({
+        "foo": ["bar", "baz"],
+        "": 0,
+        "a/b": 1,
+        "c%d": 2,
+        "e^f": 3,
+        "g|h": 4,
+        "i\\j": 5,
+        "k\"l": 6,
+        " ": 7,
+        "m~n": 8,
+    });
+    for (pointer, expected) in [
+        ("", value.clone()),
+        ("/foo", json!(["bar", "baz"])),
+        ("/foo/0", json!("bar")),
+        ("/", json!(0)),
+        ("/a~1b", json!(1)),
+        ("/c%d", json!(2)),
+        ("/e^f", json!(3)),
+        ("/g|h", json!(4)),
+        ("/i\\j", json!(5)),
+        ("/k\"l", json!(6)),
+        ("/ ", json!(7)),
+        ("/m~0n", json!(8)),
+    ] {
+        assert_eq!(value.pointer(pointer), Some(&expected), "{}", pointer);
+    }
+    for pointer in [
+        "foo", "/foo/2", "/foo/-", "/foo/01", "/foo/+1", "/foo/0/x", "/a/b", "/m~2n", "/m~",
+        "/none",
+    ] {
+        assert_eq!(value.pointer(pointer), None, "{}", pointer);
+    }
+    // `~01` is `~1` rather than `/`.
+    let value = json!({"~1": "tilde", "/": "slash"});
+    assert_eq!(value.pointer("/~01"), Some(&json!("tilde")));
+    assert_eq!(value.pointer("/~1"), Some(&json!("slash")));
+}
+
+#[test]
+fn test_pointer_mut() {
+    let mut value = json!({"a": [{"b": 1}]});
+    *value.pointer_mut("/a/0/b").unwrap() = json!([2]);
+    value
+        .pointer_mut("/a/0/b")
+        .unwrap()
+        .as_array_mut()
+        .unwrap()
+        .push(json!(3));
+    assert_eq!(value, json!({"a": [{"b": [2, 3]}]}));
+    assert!(value.pointer_mut("/a/1").is_none());
+    *value.pointer_mut("").unwrap() = json!(null);
+    assert!(value.is_null());
+}
+
+#[test]
+fn test_insert_and_remove() {
+    let mut value = json!({"a": [1, 2], "b": {}});
+    assert_eq!(value.pointer_insert("/a/0", json!(0)).unwrap(), None);
+    assert_eq!(value.pointer_insert("/a/3", json!(3)).unwrap(), None);
+    assert_eq!(value.pointer_insert("/a/-", json!(4)).unwrap(), None);
+    assert_eq!(value.pointer_insert("/b/x~1y", json!(true)).unwrap(), None);
+    assert_eq!(
+        value.pointer_insert("/b/x~1y", json!(false)).unwrap(),
+        Some(json!(true))
+    );
+    assert_eq!(value, json!({"a": [0, 1, 2, 3, 4], "b": {"x/y": false}}));
+
+    for pointer in ["/a/6", "/a/01", "/c/d", "/a/0/x", "a"] {
+        let err = value.pointer_insert(pointer, json!(null)).err().unwrap();
+        assert_eq!(err.errors()[0].constraint(), "pointer", "{}", pointer);
+        assert_eq!(err.errors()[0].value(), Some(&json!(pointer)));
+    }
+
+    assert_eq!(value.pointer_remove("/a/1"), Some(json!(1)));
+    assert_eq!(value.pointer_remove("/b/x~1y"), Some(json!(false)));
+    assert_eq!(value.pointer_remove("/b/x~1y"), None);
+    assert_eq!(value.pointer_remove("/a/4"), None);
+    assert_eq!(value.pointer_remove("/a/-"), None);
+    assert_eq!(value.pointer_remove(""), None);
+    assert_eq!(value, json!({"a": [0, 2, 3, 4], "b": {}}));
+
+    assert_eq!(
+        value.pointer_insert("", json!([])).unwrap(),
+        Some(json!({"a": [0, 2, 3, 4], "b": {}}))
+    );
+    assert_eq!(value, json!([]));
+}
+
+#[model]
+struct Item {
+    #[field(ge = 1)]
+    id: u64,
+    name: String,
+}
+
+#[test]
+fn test_parse_at() {
+    let json = "{\"data\": {\"items\": [{\"id\": 1, \"name\": \"a\"}, {\"id\": 0, \"name\": 2}]}, \"x\": null}";
+    let item = Item::parse_at(json, "/data/items/0").unwrap();
+    assert_eq!(item.json(false), "{\"id\":1,\"name\":\"a\"}");
+    let items = Vec::<Item>::parse_at(json, "/data/items");
+    assert!(items.is_err());
+
+    let err = Item::parse_at(json, "/data/items/1").err().unwrap();
+    let paths: Vec<_> = err.errors().iter().map(|err| err.loc_path()).collect();
+    assert_eq!(paths, ["data.items[1].id", "data.items[1].name"]);
+
+    let err = Item::parse_at(json, "/data/items/2").err().unwrap();
+    assert_eq!(err.errors()[0].constraint(), "pointer");
+    assert_eq!(err.errors()[0].message(), "no value at the pointer");
+    let err = Item::parse_at(json, "data").err().unwrap();
+    assert_eq!(err.errors()[0].message(), "invalid JSON Pointer");
+    // The whole input must be valid.
+    assert!(Item::parse_at("[{\"id\": 1, \"name\": \"a\"}, ]", "/0").is_err());
+    // An object key looks like an index.
+    let item = Item::parse_at("{\"0\": {\"id\": 1, \"name\": \"b\"}}", "/0").unwrap();
+    assert_eq!(item.name, "b");
+    let err = Item::parse_at("{\"0\": {\"id\": 0, \"name\": \"b\"}}", "/0")
+        .err()
+        .unwrap();
+    assert_eq!(err.errors()[0].loc_path(), "0.id");
+    assert!(matches!(
+        err.errors()[0].value(),
+        Some(JsonValue::Number(num)) if num.as_u64() == Some(0)
+    ));
+}
@@ -0,0 +1,150 @@
+use dade::{json_load, JsonValue, Map, Number};
+use std::borrow::Cow;
+
+#[test]
+fn test_eq() {
+    let value = json_load("{\"a\": [1, 2.0, null], \"b\": {\"c\": true, \"d\": \"e\"}}").unwrap();
+    let other = json_load("{\"b\": {\"d\": \"e\", \"c\": true}, \"a\": [1.0, 2, null]}").unwrap();
+    assert_eq!(value, other);
+    assert_eq!(value.clone(), value);
+    for json in [
+        "{\"a\": [1, 2.0], \"b\": {\"c\": true, \"d\": \"e\"}}",
+        "{\"a\": [2, 1, null], \"b\": {\"c\": true, \"d\": \"e\"}}",
+        "{\"a\": [1, 2, null], \"b\": {\"c\": true, \"d\": \"e\", \"f\": null}}",
+        "{\"a\": [1, 2, null], \"b\": {\"c\": 1, \"d\": \"e\"}}",
+    ] {
+        assert_ne!(value, json_load(json).unwrap(), "{}", json);
+    }
+    assert_ne!(JsonValue::Null, JsonValue::Bool(false));
+    assert_ne!(JsonValue::from(0), JsonValue::from("0"));
+}
+
+#[test]
+fn test_debug_and_display() {
+    let value = json_load("{\"a\": [1.0, null], \"b\": \"x\"}").unwrap();
+    assert_eq!(
+        format!("{:?}", value),
+        "Object({\"a\": Array([Number(1.0), Null]), \"b\": String(\"x\")})"
+    );
+    assert_eq!(value.to_string(), "{\"a\":[1.0,null],\"b\":\"x\"}");
+    assert_eq!(format!("{:?}", Number::from(-3)), "Number(-3)");
+}
+
+#[test]
+fn test_from_str() {
+    let value: JsonValue = " [true, {\"a\": 1e2}] ".parse().unwrap();
+    assert_eq!(
+        value,
+        JsonValue::from(vec![
+            JsonValue::from(true),
+            [("a", 100)].into_iter().collect()
+        ])
+    );
+    assert!("[1,]".parse::<JsonValue>().is_err());
+
+    let num: Number = "-1.5e3".parse().unwrap();
+    assert_eq!(num.as_str(), "-1.5e3");
+    assert_eq!(num.as_i64(), Some(-1500));
+    for literal in [
+        "", " 1", "1 ", "01", "1.", ".5", "+1", "0x10", "NaN", "\"1\"", "[1]",
+    ] {
+        assert!(literal.parse::<Number>().is_err(), "{}", literal);
+    }
+}
+
+#[test]
+fn test_accessors() {
+    let mut value =
+        json_load("{\"s\": \"x\", \"b\": false, \"n\": 2, \"a\": [0, {\"k\": null}]}").unwrap();
+    assert_eq!(value["s"].as_str(), Some("x"));
+    assert_eq!(value["b"].as_bool(), Some(false));
+    assert_eq!(value["n"].as_number().and_then(Number::as_u64), Some(2));
+    assert_eq!(value["a"].as_array().map(Vec::len), Some(2));
+    assert_eq!(value.as_object().map(Map::len), Some(4));
+    assert_eq!(value["s"].as_bool(), None);
+    assert_eq!(value["n"].as_str(), None);
+    assert_eq!(value["b"].as_array(), None);
+    assert_eq!(value["a"].as_object(), None);
+
+    assert!(value["a"][1]["k"].is_null());
+    assert!(value.get("a").and_then(|a| a.get(1)).is_some());
+    assert!(value["a"][1].get("k").is_some());
+    // Missing values are null by indexing, but `None` by `get`.
+    assert!(value["missing"][0]["x"].is_null());
+    assert_eq!(value.get("missing"), None);
+    assert_eq!(value["a"].get(2), None);
+    assert_eq!(value["a"].get("0"), None);
+    assert_eq!(value.get(0), None);
+    let key = "s".to_string();
+    assert_eq!(value.get(&key), Some(&JsonValue::from("x")));
+
+    *value.get_mut("n").unwrap() = JsonValue::from(3);
+    value["a"][0] = JsonValue::from("zero");
+    if let Some(arr) = value["a"].as_array_mut() {
+        arr.push(JsonValue::Null);
+    }
+    value
+        .as_object_mut()
+        .unwrap()
+        .insert("new".to_string(), JsonValue::from(1.5));
+    assert_eq!(
+        value.to_string(),
+        "{\"s\":\"x\",\"b\":false,\"n\":3,\"a\":[\"zero\",{\"k\":null},null],\"new\":1.5}"
+    );
+
+    let taken = value["a"].take();
+    assert_eq!(taken.as_array().map(Vec::len), Some(3));
+    assert!(value["a"].is_null());
+    assert!(value.get("a").is_some());
+}
+
+#[test]
+#[should_panic(expected = "no entry found for index")]
+fn test_index_mut_missing() {
+    let mut value = json_load("{}").unwrap();
+    value["a"] = JsonValue::Null;
+}
+
+#[test]
+fn test_from() {
+    assert_eq!(JsonValue::from(()), JsonValue::Null);
+    assert_eq!(JsonValue::from(true), JsonValue::Bool(true));
+    assert_eq!(
+        JsonValue::from(u128::MAX).to_string(),
+        u128::MAX.to_string()
+    );
+    assert_eq!(JsonValue::from(-1i8).to_string(), "-1");
+    assert_eq!(JsonValue::from(0.5f32).to_string(), "0.5");
+    assert_eq!(JsonValue::from(f64::NAN), JsonValue::Null);
+    assert_eq!(JsonValue::from(Number::from(2)), JsonValue::from(2.0));
+    assert_eq!(JsonValue::from("a"), JsonValue::String("a".to_string()));
+    assert_eq!(
+        JsonValue::from(Cow::Borrowed("a")),
+        JsonValue::from("a".to_string())
+    );
+    assert_eq!(JsonValue::from(None::<u8>), JsonValue::Null);
+    assert_eq!(JsonValue::from(Some("a")), JsonValue::from("a"));
+    assert_eq!(JsonValue::from(vec![1, 2]).to_string(), "[1,2]");
+    assert_eq!(
+        JsonValue::from(&["a", "b"][..]).to_string(),
+        "[\"a\",\"b\"]"
+    );
+    assert_eq!(
+        JsonValue::from(Map::from([("a".to_string(), JsonValue::Null)])).to_string(),
+        "{\"a\":null}"
+    );
+    let arr: JsonValue = (1..4).collect();
+    assert_eq!(arr.to_string(), "[1,2,3]");
+    let obj: JsonValue = [("b", Some(1)), ("a", None)].into_iter().collect();
+    assert_eq!(obj.to_string(), "{\"b\":1,\"a\":null}");
+}
+
+#[test]
+fn test_map() {
+    let a = Map::from([("x", 1), ("y", 2)]);
+    let b = Map::from([("y", 2), ("x", 1)]);
+    assert_eq!(a, b);
+    assert_ne!(a, Map::from([("x", 1)]));
+    assert_ne!(a, Map::from([("x", 1), ("y", 3)]));
+    assert_eq!(format!("{:?}", a), "{\"x\": 1, \"y\": 2}");
+}
@@ -0,0 +1,177 @@
+use dade::{
+    json_dump, model, BorrowedModel, ErrorType, JsonDumper, JsonLoader, Model, RegisterSchema,
+};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+
+#[model]
+struct User<'a> {
+    id: u64,
+    #[field(min_length = 1)]
+    screen_name: &'a str,
+    url: Option<&'a str>,
+}
+
+#[model]
+struct Status<'a> {
+    id: u64,
+    text: Cow<'a, str>,
+    user: User<'a>,
+    lang: String,
+}
+
+#[model]
+struct SearchMetadata {
+    count: u64,
+    query: String,
+}
+
+#[model]
+struct Twitter<'a> {
+    statuses: Vec<Status<'a>>,
+    search_metadata: SearchMetadata,
+}
+
+#[model]
+struct Owned {
+    statuses: Vec<OwnedStatus>,
+}
+
+#[model]
+struct OwnedStatus {
+    id: u64,
+    text: String,
+}
+
+#[model]
+struct Tag<'a> {
+    #[field(default = "none")]
+    name: &'a str,
+    #[field(max_length = 3, default = "abc")]
+    short: Option<Cow<'a, str>>,
+    aliases: Vec<&'a str>,
+}
+
+#[model]
+struct Name<'a>(#[field(min_length = 1)] &'a str);
+
+#[model]
+struct Pair<'a, 'b>(&'a str, Cow<'b, str>, u8);
+
+#[test]
+fn test_parse_borrowed() {
+    let mut contents = String::new();
+    File::open("./tests/data/twitter.json")
+        .unwrap()
+        .read_to_string(&mut contents)
+        .unwrap();
+    let twitter = Twitter::parse_borrowed(&contents).unwrap();
+    assert_eq!(twitter.statuses.len(), 100);
+    let status = &twitter.statuses[0];
+    assert!(contents.contains(status.user.screen_name));
+    let range = contents.as_bytes().as_ptr_range();
+    assert!(range.contains(&status.user.screen_name.as_ptr()));
+
+    // The same as an owned model.
+    let owned = Owned::parse(&contents).unwrap();
+    for (status, owned) in twitter.statuses.iter().zip(owned.statuses.iter()) {
+        assert_eq!(status.id, owned.id);
+        assert_eq!(status.text, owned.text);
+    }
+    let dumped = JsonDumper::new(false).dump(&twitter);
+    assert_eq!(
+        Owned::parse(&dumped).unwrap().json(false),
+        owned.json(false)
+    );
+
+    // An owned model also implements `BorrowedModel`.
+    let ret = Owned::parse_borrowed(&contents).unwrap();
+    assert_eq!(ret.json(false), owned.json(false));
+    let ret = Owned::parse_bytes_borrowed(contents.as_bytes()).unwrap();
+    assert_eq!(ret.json(false), owned.json(false));
+}
+
+#[test]
+fn test_escapes() {
+    let json = "{\"id\": 1, \"text\": \"a\\nb\", \"user\": {\"id\": 2, \"screen_name\": \"c\", \"url\": null}, \"lang\": \"en\"}";
+    let status = Status::parse_borrowed(json).unwrap();
+    assert!(matches!(&status.text, Cow::Owned(text) if text == "a\nb"));
+    assert_eq!(status.user.screen_name, "c");
+    assert_eq!(status.user.url, None);
+
+    // `&str` cannot borrow a string with escapes.
+    let json = "{\"id\": 1, \"screen_name\": \"\\u0041\", \"url\": \"\\/\"}";
+    let err = User::parse_borrowed(json).err().unwrap();
+    assert!(matches!(err.err_type(), ErrorType::ValidateError));
+    let errors = err.errors();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].loc_path(), "screen_name");
+    assert_eq!(errors[0].constraint(), "type");
+    assert_eq!(errors[1].loc_path(), "url");
+}
+
+#[test]
+fn test_validation() {
+    let err = User::parse_borrowed("{\"id\": \"1\", \"screen_name\": \"\"}")
+        .err()
+        .unwrap();
+    let errors = err.errors();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].loc_path(), "id");
+    assert_eq!(errors[1].loc_path(), "screen_name");
+    assert_eq!(errors[1].constraint(), "min_length");
+
+    let tag = Tag::parse_borrowed("{\"aliases\": [\"a\", \"b\"]}").unwrap();
+    assert_eq!(tag.name, "none");
+    assert_eq!(tag.short.as_deref(), Some("abc"));
+    assert_eq!(tag.aliases, ["a", "b"]);
+    let tag = Tag::parse_borrowed("{\"name\": \"x\", \"short\": null, \"aliases\": []}").unwrap();
+    assert_eq!(tag.name, "x");
+    assert_eq!(tag.short, None);
+    let err = Tag::parse_borrowed("{\"short\": \"abcd\", \"aliases\": [1]}")
+        .err()
+        .unwrap();
+    let errors = err.errors();
+    assert_eq!(errors[0].loc_path(), "short");
+    assert_eq!(errors[0].constraint(), "max_length");
+    assert_eq!(errors[1].loc_path(), "aliases[0]");
+
+    // A syntax error is reported before validation.
+    let err = User::parse_borrowed("{\"id\": \"1\", ").err().unwrap();
+    assert!(matches!(err.err_type(), ErrorType::ParseError));
+}
+
+#[test]
+fn test_unnamed() {
+    let name = Name::parse_borrowed("\"alice\"").unwrap();
+    assert_eq!(name.0, "alice");
+    assert!(Name::parse_borrowed("\"\"").is_err());
+
+    let json = String::from("[\"a\", \"b\\tc\", 3]");
+    let pair = Pair::parse_borrowed(&json).unwrap();
+    assert_eq!(pair.0, "a");
+    assert_eq!(pair.1, "b\tc");
+    assert_eq!(pair.2, 3);
+    assert_eq!(
+        json_dump(&dade::ToJsonValue::to_json_value(&pair), false),
+        "[\"a\",\"b\\tc\",3]"
+    );
+    assert!(Pair::parse_borrowed("{}").is_err());
+}
+
+#[test]
+fn test_relaxed_and_schema() {
+    let json = "{name: 'x', aliases: ['y',], // comment\n}";
+    let tag = Tag::parse_loader_borrowed(&mut JsonLoader::from(json).with_relaxed(true)).unwrap();
+    assert_eq!(tag.name, "x");
+    assert_eq!(tag.aliases, ["y"]);
+
+    let mut defs = BTreeMap::new();
+    Tag::register_schema(&mut defs);
+    assert_eq!(
+        json_dump(&defs["Tag"], false),
+        "{\"title\":\"Tag\",\"type\":\"object\",\"properties\":{\"name\":{\"type\":\"string\",\"title\":\"Name\",\"default\":\"none\"},\"short\":{\"anyOf\":[{\"type\":\"null\"},{\"type\":\"string\"}],\"title\":\"Short\",\"default\":\"abc\",\"maxLength\":3},\"aliases\":{\"type\":\"array\",\"items\":{\"type\":\"string\"},\"title\":\"Aliases\"}},\"required\":[\"aliases\"]}"
+    );
+}
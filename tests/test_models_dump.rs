@@ -0,0 +1,95 @@
+use dade::{json_dump, json_dump_pretty, model, Model, PrettyFormat, ToJsonValue};
+
+macro_rules! assert_same_as_json_value {
+    ($model: ident, $json: literal) => {
+        let val = $model::parse($json).unwrap();
+        let json_value = val.to_json_value();
+        for ensure_ascii in [false, true] {
+            assert_eq!(val.json(ensure_ascii), json_dump(&json_value, ensure_ascii));
+            assert_eq!(
+                val.json_pretty(ensure_ascii, PrettyFormat::new()),
+                json_dump_pretty(&json_value, ensure_ascii, PrettyFormat::new())
+            );
+        }
+    };
+}
+
+#[model]
+struct Name {
+    #[field(alias = "LastName")]
+    last_name: String,
+    #[field(alias = "FirstName")]
+    first_name: String,
+}
+
+#[model]
+struct Point(f64, f64);
+
+#[model]
+struct Id(u64);
+
+#[model]
+struct Empty;
+
+#[model]
+enum Shape {
+    Circle {
+        center: Point,
+        radius: f64,
+    },
+    Square(Point, Point),
+    Id(Id),
+    #[field(alias = "none")]
+    Nothing,
+}
+
+#[model]
+struct User {
+    zip: Option<String>,
+    id: Id,
+    name: Name,
+    tags: Vec<String>,
+    shapes: Vec<Shape>,
+    empty: Empty,
+    extra: Option<Box<User>>,
+    nothing: (),
+}
+
+#[test]
+fn test_same_as_json_value() {
+    assert_same_as_json_value!(
+        User,
+        "{\
+            \"zip\": null,\
+            \"id\": 1,\
+            \"name\": {\"FirstName\": \"\u{592a}\u{90ce}\", \"LastName\": \"\\\"Yamada\\\"\"},\
+            \"tags\": [],\
+            \"shapes\": [\
+                {\"center\": [0.5, -1e3], \"radius\": 2},\
+                [[0, 0], [1, 1]],\
+                3,\
+                \"none\"\
+            ],\
+            \"empty\": {},\
+            \"extra\": {\
+                \"zip\": \"123\",\
+                \"id\": 2,\
+                \"name\": {\"FirstName\": \"a\", \"LastName\": \"b\"},\
+                \"tags\": [\"x\", \"y\"],\
+                \"shapes\": [],\
+                \"empty\": {},\
+                \"nothing\": null\
+            },\
+            \"nothing\": null\
+        }"
+    );
+    assert_same_as_json_value!(Point, "[1.5, 2]");
+    assert_same_as_json_value!(Id, "10");
+    assert_same_as_json_value!(Empty, "{}");
+}
+
+#[test]
+fn test_declaration_order() {
+    let name = Name::parse("{\"FirstName\": \"a\", \"LastName\": \"b\"}").unwrap();
+    assert_eq!(name.json(false), "{\"LastName\":\"b\",\"FirstName\":\"a\"}");
+}
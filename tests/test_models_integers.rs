@@ -0,0 +1,158 @@
+use dade::{
+    json_load, model, BorrowedModel, FromJsonRef, FromJsonValue, JsonLoader, Model, Result,
+};
+
+fn convert<T: FromJsonValue + for<'a> FromJsonRef<'a>>(json: &str) -> Result<T> {
+    let value = json_load(json).unwrap();
+    let borrowed = JsonLoader::from(json).load_borrowed().unwrap();
+    let from_ref = T::from_json_ref(&borrowed);
+    let from_value = T::from_json_value(&value);
+    assert_eq!(
+        from_ref
+            .as_ref()
+            .err()
+            .map(|err| err.errors()[0].message().to_string()),
+        from_value
+            .as_ref()
+            .err()
+            .map(|err| err.errors()[0].message().to_string()),
+        "{}",
+        json
+    );
+    from_value
+}
+
+fn message<T>(ret: Result<T>) -> String {
+    ret.err().unwrap().errors()[0].message().to_string()
+}
+
+#[test]
+fn test_exact_integer() {
+    assert_eq!(convert::<u8>("1.0").unwrap(), 1);
+    assert_eq!(convert::<u8>("2.55e2").unwrap(), 255);
+    assert_eq!(convert::<u8>("-0").unwrap(), 0);
+    assert_eq!(convert::<u8>("-0.0e-5").unwrap(), 0);
+    assert_eq!(convert::<i16>("-12300e-2").unwrap(), -123);
+    assert_eq!(convert::<u64>("1e19").unwrap(), 10u64.pow(19));
+    assert_eq!(
+        convert::<i128>("-170141183460469231731687303715884105728").unwrap(),
+        i128::MIN
+    );
+    assert_eq!(convert::<usize>("1E+3").unwrap(), 1000);
+
+    assert_eq!(message(convert::<u8>("1.5")), "number is not an integer");
+    assert_eq!(message(convert::<i64>("1e-1")), "number is not an integer");
+    assert_eq!(
+        message(convert::<u8>("2.56e2")),
+        "number too large to fit in target type"
+    );
+    assert_eq!(
+        message(convert::<u8>("-1.0")),
+        "number too small to fit in target type"
+    );
+    assert_eq!(
+        message(convert::<i8>("-1.29e2")),
+        "number too small to fit in target type"
+    );
+    assert_eq!(
+        message(convert::<u128>("1e400")),
+        "number too large to fit in target type"
+    );
+    assert_eq!(
+        message(convert::<u32>("\"1\"")),
+        "expect `JsonValue::Number`"
+    );
+}
+
+#[test]
+fn test_strict_integer() {
+    let value = json_load("[1, 1.0]").unwrap();
+    assert_eq!(Vec::<u8>::from_json_value(&value).unwrap(), [1, 1]);
+    let err = Vec::<u8>::from_json_value_strict(&value).err().unwrap();
+    assert_eq!(err.errors().len(), 1);
+    assert_eq!(err.errors()[0].loc_path(), "[1]");
+    assert_eq!(err.errors()[0].message(), "invalid digit found in string");
+
+    let value = json_load("-0").unwrap();
+    assert!(u8::from_json_value(&value).is_ok());
+    assert!(u8::from_json_value_strict(&value).is_err());
+    // A float is the same either way.
+    let value = json_load("1.5").unwrap();
+    assert_eq!(f64::from_json_value_strict(&value).unwrap(), 1.5);
+}
+
+#[model]
+struct Lenient {
+    id: u32,
+    tags: Vec<i8>,
+    parent: Option<Box<u64>>,
+}
+
+#[model(strict)]
+struct Strict {
+    id: u32,
+    tags: Vec<i8>,
+    parent: Option<Box<u64>>,
+    inner: Option<Lenient>,
+}
+
+#[model(strict)]
+struct StrictPair(u8, Vec<u8>);
+
+#[model(strict)]
+enum StrictShape {
+    Square { side: u16 },
+    Point(i32, i32),
+}
+
+/// Parses by the stream, the value and the borrowed one, which must be the same.
+fn parse<T: Model + FromJsonValue + for<'a> BorrowedModel<'a>>(json: &str) -> Result<String> {
+    let from_stream = T::parse(json).map(|val| val.json(false));
+    let from_value = T::from_json_value(&json_load(json).unwrap()).map(|val| val.json(false));
+    let from_ref = T::parse_borrowed(json).map(|val| val.json(false));
+    for other in [from_value, from_ref] {
+        assert_eq!(
+            other.as_ref().map_err(|err| err.errors()[0].loc_path()),
+            from_stream
+                .as_ref()
+                .map_err(|err| err.errors()[0].loc_path()),
+            "{}",
+            json
+        );
+    }
+    from_stream
+}
+
+#[test]
+fn test_models() {
+    let json = "{\"id\": 1.0, \"tags\": [-1e1, 2], \"parent\": 3e0}";
+    assert_eq!(
+        parse::<Lenient>(json).unwrap(),
+        "{\"id\":1,\"tags\":[-10,2],\"parent\":3}"
+    );
+
+    let err = parse::<Strict>(json).err().unwrap();
+    let paths: Vec<_> = err.errors().iter().map(|err| err.loc_path()).collect();
+    assert_eq!(paths, ["id", "tags[0]", "parent"]);
+    assert_eq!(
+        parse::<Strict>("{\"id\": 1, \"tags\": [2], \"parent\": null, \"inner\": null}").unwrap(),
+        "{\"id\":1,\"tags\":[2],\"parent\":null,\"inner\":null}"
+    );
+    // A nested model follows its own definition.
+    assert_eq!(
+        parse::<Strict>(
+            "{\"id\": 1, \"tags\": [], \"parent\": null, \"inner\": {\"id\": 2.0, \"tags\": [], \"parent\": null}}"
+        )
+        .unwrap(),
+        "{\"id\":1,\"tags\":[],\"parent\":null,\"inner\":{\"id\":2,\"tags\":[],\"parent\":null}}"
+    );
+
+    assert_eq!(parse::<StrictPair>("[1, [2]]").unwrap(), "[1,[2]]");
+    let err = parse::<StrictPair>("[1, [2.0]]").err().unwrap();
+    assert_eq!(err.errors()[0].loc_path(), "[1][0]");
+
+    assert!(StrictShape::parse("{\"side\": 1}").is_ok());
+    assert!(StrictShape::parse("{\"side\": 1.0}").is_err());
+    assert!(StrictShape::parse("[1, -2]").is_ok());
+    assert!(StrictShape::parse("[1, -2e0]").is_err());
+}
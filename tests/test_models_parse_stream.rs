@@ -0,0 +1,178 @@
+use dade::{
+    json_load, model, ErrorType, FromJsonStream, FromJsonValue, JsonLoader, Model, ParseErrorKind,
+};
+use std::fs::File;
+use std::io::{BufReader, Read};
+
+#[model]
+struct User {
+    id: u64,
+    screen_name: String,
+    followers_count: u64,
+    url: Option<String>,
+}
+
+#[model]
+struct Status {
+    id: u64,
+    text: String,
+    user: User,
+    in_reply_to_status_id: Option<u64>,
+    retweet_count: u64,
+    favorited: bool,
+    lang: String,
+}
+
+#[model]
+struct SearchMetadata {
+    count: u64,
+    query: String,
+}
+
+#[model]
+struct Twitter {
+    statuses: Vec<Status>,
+    search_metadata: SearchMetadata,
+}
+
+#[model]
+struct Item {
+    #[field(ge = 1)]
+    id: u64,
+    tags: Vec<String>,
+}
+
+#[model]
+struct Pair(u64, String);
+
+#[test]
+fn test_same_as_json_value() {
+    let mut contents = String::new();
+    BufReader::new(File::open("./tests/data/twitter.json").unwrap())
+        .read_to_string(&mut contents)
+        .unwrap();
+    let expected = Twitter::from_json_value(&json_load(&contents).unwrap()).unwrap();
+
+    let ret = Twitter::parse(&contents);
+    assert!(ret.is_ok(), "{}", ret.err().unwrap());
+    assert_eq!(ret.unwrap().json(false), expected.json(false));
+
+    let ret = Twitter::parse_reader(File::open("./tests/data/twitter.json").unwrap());
+    assert!(ret.is_ok(), "{}", ret.err().unwrap());
+    assert_eq!(ret.unwrap().json(false), expected.json(false));
+}
+
+#[test]
+fn test_skip_unknown() {
+    let item = Item::parse(
+        "{\"x\": {\"y\": [1, -2.5e3, \"\\u3042\", true, false, null, {}]}, \"id\": 1, \"tags\": [\"a\"], \"z\": []}",
+    )
+    .unwrap();
+    assert_eq!(item.json(false), "{\"id\":1,\"tags\":[\"a\"]}");
+
+    let pair = Pair::parse("[1, \"a\", {\"b\": [2]}]").unwrap();
+    assert_eq!(pair.json(false), "[1,\"a\"]");
+}
+
+#[test]
+fn test_not_object() {
+    for json in ["[]", "1", "\"a\"", "null"] {
+        let err = Item::parse(json).err().unwrap();
+        assert!(matches!(err.err_type(), ErrorType::ValidateError));
+        assert_eq!(err.errors()[0].constraint(), "type");
+    }
+    let err = Pair::parse("{}").err().unwrap();
+    assert_eq!(err.errors()[0].constraint(), "type");
+}
+
+#[test]
+fn test_duplicate_key() {
+    for (json, offset) in [
+        ("{\"id\": 1, \"tags\": [], \"id\": 2}", 22),
+        ("{\"x\": 1, \"id\": 1, \"tags\": [], \"x\": 2}", 30),
+        ("{\"id\": 1, \"tags\": [], \"x\": {\"y\": 1, \"y\": 2}}", 36),
+    ] {
+        let err = Item::parse(json).err().unwrap();
+        assert_eq!(
+            err.parse_error_kind(),
+            Some(ParseErrorKind::DuplicateKey),
+            "{}",
+            err
+        );
+        assert_eq!(err.offset(), Some(offset), "{}", err);
+    }
+}
+
+#[test]
+fn test_syntax_error_takes_precedence() {
+    for json in [
+        "{\"id\": 0, \"tags\": [], \"x\": [1,]}",
+        "{\"id\": 0, \"tags\": [1, 2}",
+        "{\"id\": 0, \"tags\": []} 1",
+        "[\"a\", 1, nul]",
+    ] {
+        let err = Item::parse(json).err().unwrap();
+        assert!(matches!(err.err_type(), ErrorType::ParseError), "{}", err);
+    }
+    let err = Pair::parse("[1, \"a\", [}]").err().unwrap();
+    assert!(matches!(err.err_type(), ErrorType::ParseError), "{}", err);
+}
+
+#[test]
+fn test_validation_errors() {
+    let err = Item::parse("{\"id\": 0, \"tags\": [\"a\", 1, null]}")
+        .err()
+        .unwrap();
+    let errors = err.errors();
+    assert_eq!(errors.len(), 3, "{}", err);
+    assert_eq!(errors[0].loc_path(), "id");
+    assert_eq!(errors[1].loc_path(), "tags[1]");
+    assert_eq!(errors[2].loc_path(), "tags[2]");
+}
+
+#[test]
+fn test_limits() {
+    let json = "{\"id\": 1, \"tags\": [], \"x\": [[[1]]]}";
+    let mut loader = JsonLoader::from(json).with_max_depth(3);
+    let err = Item::from_json_stream(&mut loader).err().unwrap();
+    assert_eq!(
+        err.parse_error_kind(),
+        Some(ParseErrorKind::DepthLimitExceeded)
+    );
+
+    let json = "{\"id\": 1, \"tags\": [\"a\", \"b\"]}";
+    let mut loader = JsonLoader::from(json).with_max_array_length(1);
+    let err = Item::from_json_stream(&mut loader).err().unwrap();
+    assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::TooManyItems));
+
+    let json = "{\"id\": 1, \"tags\": [], \"x\": \"abcd\"}";
+    let mut loader = JsonLoader::from(json).with_max_string_length(3);
+    let err = Item::from_json_stream(&mut loader).err().unwrap();
+    assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::StringTooLong));
+
+    let mut loader = JsonLoader::from(json).with_max_object_members(2);
+    let err = Item::from_json_stream(&mut loader).err().unwrap();
+    assert_eq!(err.parse_error_kind(), Some(ParseErrorKind::TooManyMembers));
+}
+
+#[test]
+fn test_pull_api() {
+    let mut loader = JsonLoader::from(" {\"a\": [1, {\"b\": null}], \"c\": 2} ");
+    let mut keys = Vec::new();
+    assert!(loader.begin_object().unwrap());
+    loader
+        .load_members(|loader, key| {
+            keys.push(key.to_string());
+            if key == "c" {
+                assert!(!loader.begin_array().unwrap());
+                assert!(!loader.load_null().unwrap());
+                assert_eq!(loader.load_value().unwrap().to_string(), "2");
+                Ok(true)
+            } else {
+                Ok(false)
+            }
+        })
+        .unwrap();
+    assert_eq!(keys, ["a", "c"]);
+    assert!(loader.finish().is_ok());
+}
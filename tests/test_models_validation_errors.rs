@@ -0,0 +1,211 @@
+use dade::{json_dump, model, Error, ErrorType, JsonValue, LocItem, Model, Result};
+
+#[model]
+struct Item {
+    #[field(min_length = 1)]
+    name: String,
+    #[field(gt = 0.0)]
+    price: f64,
+}
+
+#[model]
+struct Order {
+    #[field(ge = 1)]
+    id: u64,
+    #[field(alias = "customerName", max_length = 3)]
+    customer_name: String,
+    items: Vec<Item>,
+    note: Option<String>,
+}
+
+#[model]
+struct Point(#[field(ge = 0)] i32, #[field(ge = 0)] i32);
+
+#[test]
+fn test_collect_all_errors() {
+    let err = Order::parse(
+        "{\
+            \"id\": 0,\
+            \"customerName\": \"abcd\",\
+            \"items\": [\
+                {\"name\": \"a\", \"price\": 1.0},\
+                {\"name\": \"\", \"price\": 1.0},\
+                {\"name\": \"c\", \"price\": 1.0},\
+                {\"name\": \"d\", \"price\": -1.0}\
+            ]\
+        }",
+    )
+    .err()
+    .unwrap();
+    assert!(matches!(err.err_type(), ErrorType::ValidateError));
+
+    let errors = err.errors();
+    assert_eq!(errors.len(), 4, "{}", err);
+    assert_eq!(errors[0].loc_path(), "id");
+    assert_eq!(errors[0].constraint(), "ge");
+    assert_eq!(errors[0].message(), "id must be equal to or greater than 1");
+    assert_eq!(
+        errors[0].value().map(|v| v.to_string()),
+        Some("0".to_string())
+    );
+    assert_eq!(errors[1].loc_path(), "customerName");
+    assert_eq!(errors[1].constraint(), "max_length");
+    assert_eq!(
+        errors[1].value().map(|v| v.to_string()),
+        Some("\"abcd\"".to_string())
+    );
+    assert_eq!(errors[2].loc_path(), "items[1].name");
+    assert_eq!(errors[2].constraint(), "min_length");
+    assert_eq!(errors[3].loc_path(), "items[3].price");
+    assert_eq!(errors[3].constraint(), "gt");
+    assert!(matches!(
+        errors[3].loc(),
+        [LocItem::Key(items), LocItem::Index(3), LocItem::Key(price)]
+            if items == "items" && price == "price"
+    ));
+}
+
+#[test]
+fn test_missing_and_type() {
+    let err = Order::parse("{\"customerName\": 1, \"items\": {}, \"note\": 1}")
+        .err()
+        .unwrap();
+    let errors = err.errors();
+    assert_eq!(errors.len(), 4, "{}", err);
+    assert_eq!(errors[0].loc_path(), "id");
+    assert_eq!(errors[0].constraint(), "missing");
+    assert!(errors[0].value().is_none());
+    assert_eq!(errors[1].loc_path(), "customerName");
+    assert_eq!(errors[1].constraint(), "type");
+    assert!(matches!(
+        errors[1].value(),
+        Some(JsonValue::Number(num)) if num.to_string() == "1"
+    ));
+    assert_eq!(errors[2].loc_path(), "items");
+    assert_eq!(errors[2].constraint(), "type");
+    assert_eq!(errors[3].loc_path(), "note");
+    assert_eq!(errors[3].constraint(), "type");
+}
+
+#[test]
+fn test_unnamed_struct() {
+    let err = Point::parse("[-1, -2]").err().unwrap();
+    let errors = err.errors();
+    assert_eq!(errors.len(), 2, "{}", err);
+    assert_eq!(errors[0].loc_path(), "[0]");
+    assert_eq!(errors[1].loc_path(), "[1]");
+
+    let err = Point::parse("{}").err().unwrap();
+    assert_eq!(err.errors().len(), 1);
+    assert_eq!(err.errors()[0].loc_path(), "");
+    assert_eq!(err.errors()[0].constraint(), "type");
+}
+
+#[test]
+fn test_display() {
+    let err = Order::parse("{\"id\": 0, \"customerName\": \"abc\", \"items\": [{\"name\": \"\"}]}")
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Validate Error: \
+        id: id must be equal to or greater than 1; \
+        items[0].name: the length of name must be equal to or greater than 1; \
+        items[0].price: not found key, \"price\""
+    );
+}
+
+fn check(value: i64) -> Result<i64> {
+    if value > 0 {
+        Ok(value)
+    } else {
+        Err(Error::new("must be positive", ErrorType::ValidateError))
+    }
+}
+
+#[model]
+struct Checked {
+    #[field(validate = check)]
+    a: i64,
+    b: Option<Vec<Checked>>,
+}
+
+#[test]
+fn test_error_without_field_errors() {
+    // An error made by `Error::new` is kept as a failure of the value.
+    let err = Checked::parse("{\"a\": -1, \"b\": [{\"a\": 1}, {\"a\": 0}]}")
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Validate Error: a: must be positive; b[1].a: must be positive"
+    );
+    assert_eq!(err.errors().len(), 2);
+    assert_eq!(err.errors()[1].constraint(), "value");
+    assert_eq!(
+        json_dump(&err.details(), false),
+        "[\
+            {\"loc\":[\"a\"],\"type\":\"value\",\"msg\":\"must be positive\"},\
+            {\"loc\":[\"b\",1,\"a\"],\"type\":\"value\",\"msg\":\"must be positive\"}\
+        ]"
+    );
+    let err = Error::merge(Vec::from([Error::new("failed", ErrorType::ValidateError)]));
+    assert_eq!(err.errors()[0].message(), "failed");
+}
+
+#[test]
+fn test_parse_error_has_no_field_errors() {
+    let err = Order::parse("{\"id\": 0,").err().unwrap();
+    assert!(matches!(err.err_type(), ErrorType::ParseError));
+    assert!(err.errors().is_empty());
+}
+
+#[test]
+fn test_details() {
+    let err = Order::parse(
+        "{\"id\": 0, \"customerName\": \"abc\", \"items\": [{\"price\": 0}], \"note\": null}",
+    )
+    .err()
+    .unwrap();
+    assert_eq!(
+        json_dump(&err.details(), false),
+        "[\
+            {\
+                \"loc\":[\"id\"],\
+                \"type\":\"ge\",\
+                \"msg\":\"id must be equal to or greater than 1\",\
+                \"ctx\":{\"ge\":1}\
+            },\
+            {\
+                \"loc\":[\"items\",0,\"name\"],\
+                \"type\":\"missing\",\
+                \"msg\":\"not found key, \\\"name\\\"\"\
+            },\
+            {\
+                \"loc\":[\"items\",0,\"price\"],\
+                \"type\":\"gt\",\
+                \"msg\":\"price must be greater than 0.0\",\
+                \"ctx\":{\"gt\":0}\
+            }\
+        ]"
+    );
+    assert_eq!(
+        err.errors()[2].limit().map(|v| v.to_string()),
+        Some("0".to_string())
+    );
+    assert!(err.errors()[1].limit().is_none());
+}
+
+#[test]
+fn test_details_of_parse_error() {
+    let err = Order::parse("{\n\"id\": 0,").err().unwrap();
+    assert_eq!(
+        json_dump(&err.details(), false),
+        "[{\
+            \"loc\":[],\
+            \"type\":\"parse_error\",\
+            \"msg\":\"unexpected end of input\",\
+            \"ctx\":{\"offset\":10,\"line\":2,\"column\":9}\
+        }]"
+    );
+}
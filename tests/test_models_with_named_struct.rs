@@ -64,11 +64,11 @@ fn test_null_model() {
             }\
         }"
     );
-    success_parse_model!(TestModel, "{\"v1\":null}", "{\"c2\":null,\"v1\":null}");
+    success_parse_model!(TestModel, "{\"v1\":null}", "{\"v1\":null,\"c2\":null}");
     success_parse_model!(
         TestModel,
         "{\"v1\":null,\"c2\":null}",
-        "{\"c2\":null,\"v1\":null}"
+        "{\"v1\":null,\"c2\":null}"
     );
 
     assert!(TestModel::parse("{}").is_err());
@@ -115,32 +115,32 @@ fn test_bool_model() {
     success_parse_model!(
         TestModel,
         "{\"b1\": true,\"b3\": true}",
-        "{\"b1\":true,\"b3\":true,\"c2\":false}"
+        "{\"b1\":true,\"c2\":false,\"b3\":true}"
     );
     success_parse_model!(
         TestModel,
         "{\"b1\": false,\"b3\": true}",
-        "{\"b1\":false,\"b3\":true,\"c2\":false}"
+        "{\"b1\":false,\"c2\":false,\"b3\":true}"
     );
     success_parse_model!(
         TestModel,
         "{\"c2\":false, \"b1\": true,\"b3\": true}",
-        "{\"b1\":true,\"b3\":true,\"c2\":false}"
+        "{\"b1\":true,\"c2\":false,\"b3\":true}"
     );
     success_parse_model!(
         TestModel,
         "{\"c2\":false, \"b1\": false,\"b3\": true}",
-        "{\"b1\":false,\"b3\":true,\"c2\":false}"
+        "{\"b1\":false,\"c2\":false,\"b3\":true}"
     );
     success_parse_model!(
         TestModel,
         "{\"c2\":true, \"b1\": true,\"b3\": true}",
-        "{\"b1\":true,\"b3\":true,\"c2\":true}"
+        "{\"b1\":true,\"c2\":true,\"b3\":true}"
     );
     success_parse_model!(
         TestModel,
         "{\"c2\":true, \"b1\": false,\"b3\": true}",
-        "{\"b1\":false,\"b3\":true,\"c2\":true}"
+        "{\"b1\":false,\"c2\":true,\"b3\":true}"
     );
 
     assert!(TestModel::parse("{\"b1\": true}").is_err());
@@ -195,12 +195,12 @@ macro_rules! test_signed_integer_model {
                 success_parse_model!(
                     TestModel,
                     "{\"v1\": 0,\"v3\": 1,\"v4\": 1,\"v5\": 2}",
-                    "{\"c2\":0,\"v1\":0,\"v3\":1,\"v4\":1,\"v5\":2}"
+                    "{\"v1\":0,\"c2\":0,\"v3\":1,\"v4\":1,\"v5\":2}"
                 );
                 success_parse_model!(
                     TestModel,
                     "{\"v1\": 0,\"v3\": 1,\"v4\": 1,\"v5\": 2,\"c2\":-2}",
-                    "{\"c2\":-2,\"v1\":0,\"v3\":1,\"v4\":1,\"v5\":2}"
+                    "{\"v1\":0,\"c2\":-2,\"v3\":1,\"v4\":1,\"v5\":2}"
                 );
                 assert!(TestModel::parse("{\"v3\": 1,\"v4\": 1,\"v5\": 2}").is_err());
                 assert!(TestModel::parse("{\"v1\": 0,\"v4\": 1,\"v5\": 2}").is_err());
@@ -263,12 +263,12 @@ macro_rules! test_unsigned_integer_model {
                 success_parse_model!(
                     TestModel,
                     "{\"v1\": 0,\"v3\": 1,\"v4\": 1,\"v5\": 2}",
-                    "{\"c2\":0,\"v1\":0,\"v3\":1,\"v4\":1,\"v5\":2}"
+                    "{\"v1\":0,\"c2\":0,\"v3\":1,\"v4\":1,\"v5\":2}"
                 );
                 success_parse_model!(
                     TestModel,
                     "{\"v1\": 0,\"v3\": 1,\"v4\": 1,\"v5\": 2,\"c2\":10}",
-                    "{\"c2\":10,\"v1\":0,\"v3\":1,\"v4\":1,\"v5\":2}"
+                    "{\"v1\":0,\"c2\":10,\"v3\":1,\"v4\":1,\"v5\":2}"
                 );
                 assert!(TestModel::parse("{\"v3\": 1,\"v4\": 1,\"v5\": 2}").is_err());
                 assert!(TestModel::parse("{\"v1\": 0,\"v4\": 1,\"v5\": 2}").is_err());
@@ -332,12 +332,12 @@ macro_rules! test_float_model {
                 success_parse_model!(
                     TestModel,
                     "{\"v1\": 0,\"v3\": 1,\"v4\": 1,\"v5\": 2}",
-                    "{\"c2\":0,\"v1\":0,\"v3\":1,\"v4\":1,\"v5\":2}"
+                    "{\"v1\":0,\"c2\":0,\"v3\":1,\"v4\":1,\"v5\":2}"
                 );
                 success_parse_model!(
                     TestModel,
                     "{\"v1\": 0,\"v3\": 1,\"v4\": 1,\"v5\": 2,\"c2\":-2.1}",
-                    "{\"c2\":-2.1,\"v1\":0,\"v3\":1,\"v4\":1,\"v5\":2}"
+                    "{\"v1\":0,\"c2\":-2.1,\"v3\":1,\"v4\":1,\"v5\":2}"
                 );
                 assert!(TestModel::parse("{\"v3\": 1,\"v4\": 1,\"v5\": 2}").is_err());
                 assert!(TestModel::parse("{\"v1\": 0,\"v4\": 1,\"v5\": 2}").is_err());
@@ -391,12 +391,12 @@ fn test_string_model() {
     success_parse_model!(
         TestModel,
         "{\"v1\":\"\",\"v3\":\"value3\",\"v4\":\"value4\"}",
-        "{\"c2\":\"DEFAULT\",\"v1\":\"\",\"v3\":\"Hello, value3\",\"v4\":\"value4\"}"
+        "{\"v1\":\"\",\"c2\":\"DEFAULT\",\"v3\":\"Hello, value3\",\"v4\":\"value4\"}"
     );
     success_parse_model!(
         TestModel,
         "{\"v1\":\"value1\",\"v3\":\"value3\",\"v4\":\"value4\",\"c2\":\"any string\"}",
-        "{\"c2\":\"any string\",\"v1\":\"value1\",\"v3\":\"Hello, value3\",\"v4\":\"value4\"}"
+        "{\"v1\":\"value1\",\"c2\":\"any string\",\"v3\":\"Hello, value3\",\"v4\":\"value4\"}"
     );
     assert!(TestModel::parse("{\"v3\": \"value3\",\"v4\": \"value4\"}").is_err());
     assert!(TestModel::parse("{\"v1\": \"value1\",\"v4\": \"value4\"}").is_err());
@@ -465,17 +465,17 @@ fn test_vec_model() {
     success_parse_model!(
         TestModel,
         "{\"v1\":[],\"c2\": [null],\"v3\":[null,null],\"v4\":[null,null,null]}",
-        "{\"c2\":[null],\"v1\":[],\"v3\":[null,null,null],\"v4\":[null,null,null]}"
+        "{\"v1\":[],\"c2\":[null],\"v3\":[null,null,null],\"v4\":[null,null,null]}"
     );
     success_parse_model!(
         TestModel,
         "{\"v1\":[],\"c2\": [null],\"v3\":[null,null],\"v4\":[null]}",
-        "{\"c2\":[null],\"v1\":[],\"v3\":[null,null,null],\"v4\":[null]}"
+        "{\"v1\":[],\"c2\":[null],\"v3\":[null,null,null],\"v4\":[null]}"
     );
     success_parse_model!(
         TestModel,
         "{\"v1\":[],\"c2\": [null],\"v3\":[null,null],\"v4\":[null,null,null,null,null,null,null,null,null,null]}",
-        "{\"c2\":[null],\"v1\":[],\"v3\":[null,null,null],\"v4\":[null,null,null,null,null,null,null,null,null,null]}"
+        "{\"v1\":[],\"c2\":[null],\"v3\":[null,null,null],\"v4\":[null,null,null,null,null,null,null,null,null,null]}"
     );
     assert!(
         TestModel::parse("{\"c2\": [null],\"v3\":[null,null],\"v4\":[null,null,null]}").is_err()
@@ -539,7 +539,7 @@ fn test_nested_model() {
     success_parse_model!(
         TestModel2,
         "{\"v1\":{\"v1\": 1},\"c2\":{\"v1\": 2}}",
-        "{\"c2\":{\"v1\":3},\"v1\":{\"v1\":1}}"
+        "{\"v1\":{\"v1\":1},\"c2\":{\"v1\":3}}"
     );
     assert!(TestModel2::parse("{\"c2\":{\"v1\": 2}}").is_err());
     assert!(TestModel2::parse("{\"v1\":{\"v1\": 1}}").is_err());
@@ -587,16 +587,16 @@ fn test_self_reference_model() {
             }\
         }"
     );
-    success_parse_model!(TestModel, "{\"v1\": 1}", "{\"self\":null,\"v1\":1}");
+    success_parse_model!(TestModel, "{\"v1\": 1}", "{\"v1\":1,\"self\":null}");
     success_parse_model!(
         TestModel,
         "{\"v1\": 1,\"self\":{\"v1\": 2}}",
-        "{\"self\":{\"self\":null,\"v1\":2},\"v1\":1}"
+        "{\"v1\":1,\"self\":{\"v1\":2,\"self\":null}}"
     );
     success_parse_model!(
         TestModel,
         "{\"v1\": 1,\"self\":{\"v1\": 2,\"self\":{\"v1\": 3}}}",
-        "{\"self\":{\"self\":{\"self\":null,\"v1\":3},\"v1\":2},\"v1\":1}"
+        "{\"v1\":1,\"self\":{\"v1\":2,\"self\":{\"v1\":3,\"self\":null}}}"
     );
     assert!(TestModel::parse("{}").is_err());
     assert!(TestModel::parse("{\"self\":{\"v1\": 2}}").is_err());
@@ -657,17 +657,17 @@ fn test_enum_model() {
     success_parse_model!(
         TestModel,
         "{\"c2\": \"Value1\",\"v1\": \"Value1\"}",
-        "{\"c2\":\"Value2\",\"v1\":\"Value1\"}"
+        "{\"v1\":\"Value1\",\"c2\":\"Value2\"}"
     );
     success_parse_model!(
         TestModel,
         "{\"c2\": \"Value1\",\"v1\": \"Value2\"}",
-        "{\"c2\":\"Value2\",\"v1\":\"Value2\"}"
+        "{\"v1\":\"Value2\",\"c2\":\"Value2\"}"
     );
     success_parse_model!(
         TestModel,
         "{\"c2\": \"Value2\",\"v1\": \"Value1\"}",
-        "{\"c2\":\"Value1\",\"v1\":\"Value1\"}"
+        "{\"v1\":\"Value1\",\"c2\":\"Value1\"}"
     );
     assert!(TestModel::parse("{\"c2\": \"Value2\",\"v1\": \"Value3\"}").is_err());
     assert!(TestModel::parse("{\"c2\": \"Value3\",\"v1\": \"Value1\"}").is_err());
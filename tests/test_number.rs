@@ -0,0 +1,204 @@
+use dade::{json_dump, json_load, model, JsonDumper, JsonValue, Model, Number, ToJsonValue};
+
+fn number(literal: &str) -> Number {
+    match json_load(literal).unwrap() {
+        JsonValue::Number(num) => num,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_keep_literal() {
+    for literal in [
+        "1.0",
+        "-0",
+        "1E+2",
+        "0.000",
+        "1e-999",
+        "10000000000000000999",
+    ] {
+        let num = number(literal);
+        assert_eq!(num.as_str(), literal);
+        assert_eq!(num.to_string(), literal);
+        assert_eq!(json_dump(&JsonValue::Number(num), false), literal);
+    }
+}
+
+#[test]
+fn test_is_integer() {
+    for literal in [
+        "0", "-0", "0.0", "1", "-1.000", "1e3", "1.5e1", "12300e-2", "1e400",
+    ] {
+        assert!(number(literal).is_integer(), "{}", literal);
+    }
+    for literal in ["0.5", "-1.01", "1e-1", "15e-1", "1.000000000000000005"] {
+        assert!(!number(literal).is_integer(), "{}", literal);
+    }
+}
+
+#[test]
+fn test_as_integer() {
+    for (literal, i, u) in [
+        ("0", Some(0), Some(0)),
+        ("-0.0", Some(0), Some(0)),
+        ("42", Some(42), Some(42)),
+        ("4.2e1", Some(42), Some(42)),
+        ("-42", Some(-42), None),
+        ("0.5", None, None),
+        ("9223372036854775807", Some(i64::MAX), Some(i64::MAX as u64)),
+        ("9223372036854775808", None, Some(1 << 63)),
+        ("-9223372036854775808", Some(i64::MIN), None),
+        ("-9223372036854775809", None, None),
+        ("18446744073709551615", None, Some(u64::MAX)),
+        ("18446744073709551616", None, None),
+        ("1e999999999999999999999", None, None),
+    ] {
+        let num = number(literal);
+        assert_eq!(num.as_i64(), i, "{}", literal);
+        assert_eq!(num.as_u64(), u, "{}", literal);
+    }
+    assert_eq!(
+        number("340282366920938463463374607431768211455").as_u128(),
+        Some(u128::MAX)
+    );
+    assert_eq!(
+        number("340282366920938463463374607431768211456").as_u128(),
+        None
+    );
+    assert_eq!(number("1e38").as_u128(), Some(10u128.pow(38)));
+    assert_eq!(number("-1").as_u128(), None);
+}
+
+#[test]
+fn test_as_f64() {
+    for (literal, expected) in [
+        ("0", Some(0.0)),
+        ("-0", Some(-0.0)),
+        ("0.5", Some(0.5)),
+        ("-1.25e2", Some(-125.0)),
+        ("9007199254740992", Some(9007199254740992.0)),
+        ("1152921504606846976", Some(1152921504606846976.0)),
+        ("5e-324", None),
+        ("4.9406564584124654e-324", None),
+        ("1.7976931348623157e308", None),
+        ("0.1", None),
+        ("9007199254740993", None),
+        ("1e400", None),
+    ] {
+        assert_eq!(number(literal).as_f64(), expected, "{}", literal);
+    }
+    // The exact expansions of the smallest and the largest values.
+    let min = format!("{:.766e}", f64::from_bits(1));
+    assert_eq!(number(&min).as_f64(), Some(f64::from_bits(1)));
+    let max = format!("{:.0}", f64::MAX);
+    assert_eq!(number(&max).as_f64(), Some(f64::MAX));
+    // The same as the exact expansion, while the neighbors are not exact.
+    let mut bits = 0x0123_4567_89ab_cdefu64;
+    for _ in 0..1000 {
+        bits = bits
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let val = f64::from_bits(bits);
+        if !val.is_finite() {
+            continue;
+        }
+        let exact = format!("{:.766e}", val);
+        assert_eq!(number(&exact).as_f64(), Some(val), "{}", exact);
+        let last = exact.find('e').unwrap() - 1;
+        for digit in ["1", "9"].into_iter().filter(|&d| d != &exact[last..=last]) {
+            let neighbor = format!("{}{}{}", &exact[..last], digit, &exact[last + 1..]);
+            assert_eq!(number(&neighbor).as_f64(), None, "{}", neighbor);
+        }
+    }
+    // The nearest value is available by `parse`.
+    assert_eq!(number("0.1").parse::<f64>(), Ok(0.1));
+}
+
+#[test]
+fn test_eq() {
+    for (a, b) in [
+        ("1", "1.0"),
+        ("1", "1e0"),
+        ("10", "1E1"),
+        ("1.5", "15e-1"),
+        ("0.00120", "1.2e-3"),
+        ("0", "-0"),
+        ("0", "0e10"),
+        ("-2", "-2.00"),
+        ("10000000000000000999", "1.0000000000000000999e19"),
+        ("1e9999999999999999999", "10e9999999999999999998"),
+        (
+            "1e1000000000000000000000000000000000000000",
+            "0.01e1000000000000000000000000000000000000002",
+        ),
+        (
+            "1e-1000000000000000000000000000000000000000",
+            "100e-1000000000000000000000000000000000000002",
+        ),
+        (
+            "1.5e999999999999999999999999999999999999",
+            "15e999999999999999999999999999999999998",
+        ),
+    ] {
+        assert!(number(a) == number(b), "{} == {}", a, b);
+    }
+    for (a, b) in [
+        ("1", "-1"),
+        ("1", "10"),
+        ("1.5", "1.05"),
+        ("10000000000000000999", "10000000000000001000"),
+        ("1e9999999999999999999", "1e9999999999999999998"),
+        ("1e9999999999999999999", "1e-9999999999999999999"),
+        (
+            "1e1000000000000000000000000000000000000000",
+            "1e1000000000000000000000000000000000000001",
+        ),
+        (
+            "1e1000000000000000000000000000000000000000",
+            "1e999999999999999999999999999999999999999",
+        ),
+    ] {
+        assert!(number(a) != number(b), "{} != {}", a, b);
+    }
+    assert!(Number::from(15u8) == number("1.5e1"));
+    assert!(Number::from(-3i64) == Number::from_f64(-3.0).unwrap());
+}
+
+#[test]
+fn test_construct() {
+    assert!(Number::from_f64(f64::NAN).is_none());
+    assert!(Number::from_f64(f64::INFINITY).is_none());
+    assert!(Number::from_f64(f64::NEG_INFINITY).is_none());
+    assert!(Number::from_f32(f32::NAN).is_none());
+    assert_eq!(Number::from_f64(1.5).unwrap().as_str(), "1.5");
+    assert_eq!(Number::from_f32(0.1).unwrap().as_str(), "0.1");
+    assert_eq!(Number::from(u128::MAX).as_u128(), Some(u128::MAX));
+    assert_eq!(Number::from(i64::MIN).as_i64(), Some(i64::MIN));
+
+    // JSON has no NaN nor infinity, so they are converted to null.
+    assert!(matches!(f64::NAN.to_json_value(), JsonValue::Null));
+    assert!(matches!(f32::INFINITY.to_json_value(), JsonValue::Null));
+    assert_eq!(
+        JsonDumper::new(false).dump(&vec![1.5, f64::NAN, f64::NEG_INFINITY]),
+        "[1.5,null,null]"
+    );
+}
+
+#[model]
+struct Measure {
+    #[field(ge = 0.5, le = 1e3)]
+    value: f64,
+}
+
+#[test]
+fn test_model() {
+    let measure = Measure::parse("{\"value\": 2}").unwrap();
+    assert_eq!(measure.json(false), "{\"value\":2}");
+    let nan = Measure { value: f64::NAN };
+    assert_eq!(nan.json(false), "{\"value\":null}");
+    let err = Measure::parse("{\"value\": 0.25}").err().unwrap();
+    assert!(matches!(
+        err.errors()[0].limit(),
+        Some(JsonValue::Number(num)) if *num == number("0.5")
+    ));
+}
@@ -72,7 +72,7 @@ test_success!(
 test_success_with_correct!(
     test_object_key_nfc_nfd,
     "./tests/data/transform/object_key_nfc_nfd.json",
-    "{\"e\\u0301\":\"NFD\",\"é\":\"NFC\"}"
+    "{\"é\":\"NFC\",\"e\\u0301\":\"NFD\"}"
 );
 //TODO; When fix dump, we will fix a correct.
 test_success_with_correct!(